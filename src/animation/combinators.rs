@@ -0,0 +1,361 @@
+use std::marker::PhantomData;
+
+use super::{Animation, Duration, TimeStamp};
+
+pub struct ConstantAnimation<T: Clone> {
+    pub duration: Duration,
+    pub value: T,
+}
+
+impl<T: Clone> ConstantAnimation<T> {
+    // Zero duration, so it never stretches the animation it is part of
+    pub fn timeless(value: T) -> Self {
+        ConstantAnimation {
+            duration: Duration::from_seconds(0.0),
+            value,
+        }
+    }
+}
+
+impl<T: Clone> Animation<T> for ConstantAnimation<T> {
+    fn at(&self, _t: TimeStamp) -> T {
+        self.value.clone()
+    }
+
+    fn duration(&self) -> Duration {
+        self.duration
+    }
+}
+
+pub struct Sequence<A, B> {
+    first: A,
+    second: B,
+}
+
+impl<A, B> Sequence<A, B> {
+    pub fn new(first: A, second: B) -> Self {
+        Sequence { first, second }
+    }
+}
+
+impl<T, A: Animation<T>, B: Animation<T>> Animation<T> for Sequence<A, B> {
+    fn at(&self, t: TimeStamp) -> T {
+        let first_duration = self.first.duration();
+
+        if t.value < first_duration.value {
+            self.first.at(t)
+        } else {
+            self.second.at(TimeStamp::new(t.value - first_duration.value))
+        }
+    }
+
+    fn duration(&self) -> Duration {
+        self.first.duration() + self.second.duration()
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Repetition {
+    Times(u32),
+    Forever,
+}
+
+pub struct Loop<A> {
+    animation: A,
+    repetition: Repetition,
+}
+
+impl<A> Loop<A> {
+    pub fn new(animation: A, repetition: Repetition) -> Self {
+        Loop { animation, repetition }
+    }
+}
+
+impl<T, A: Animation<T>> Animation<T> for Loop<A> {
+    fn at(&self, t: TimeStamp) -> T {
+        let period = self.animation.duration().value;
+
+        // Past the last repetition, hold the final value instead of wrapping around to the start;
+        // a timeless animation has nothing to wrap around either
+        if period <= 0.0 || t.value >= self.duration().value {
+            return self.animation.at(TimeStamp::new(period));
+        }
+
+        self.animation.at(TimeStamp::new(t.value.rem_euclid(period)))
+    }
+
+    fn duration(&self) -> Duration {
+        match self.repetition {
+            Repetition::Times(count) => self.animation.duration() * count as f64,
+            Repetition::Forever => Duration::from_seconds(f64::INFINITY),
+        }
+    }
+}
+
+pub struct PingPong<A> {
+    animation: A,
+}
+
+impl<A> PingPong<A> {
+    pub fn new(animation: A) -> Self {
+        PingPong { animation }
+    }
+}
+
+impl<T, A: Animation<T>> Animation<T> for PingPong<A> {
+    fn at(&self, t: TimeStamp) -> T {
+        let period = self.animation.duration().value;
+
+        if t.value <= period {
+            self.animation.at(t)
+        } else {
+            self.animation.at(TimeStamp::new((2.0 * period - t.value).max(0.0)))
+        }
+    }
+
+    fn duration(&self) -> Duration {
+        self.animation.duration() * 2.0
+    }
+}
+
+pub struct Delay<A> {
+    animation: A,
+    delay: Duration,
+}
+
+impl<A> Delay<A> {
+    pub fn new(animation: A, delay: Duration) -> Self {
+        Delay { animation, delay }
+    }
+}
+
+impl<T, A: Animation<T>> Animation<T> for Delay<A> {
+    fn at(&self, t: TimeStamp) -> T {
+        self.animation.at(TimeStamp::new((t.value - self.delay.value).max(0.0)))
+    }
+
+    fn duration(&self) -> Duration {
+        self.delay + self.animation.duration()
+    }
+}
+
+pub struct Speed<A> {
+    animation: A,
+    factor: f64,
+}
+
+impl<A> Speed<A> {
+    pub fn new(animation: A, factor: f64) -> Self {
+        debug_assert!(factor > 0.0, "Speed factor must be positive");
+
+        Speed { animation, factor }
+    }
+}
+
+impl<T, A: Animation<T>> Animation<T> for Speed<A> {
+    fn at(&self, t: TimeStamp) -> T {
+        self.animation.at(TimeStamp::new(t.value * self.factor))
+    }
+
+    fn duration(&self) -> Duration {
+        self.animation.duration() / self.factor
+    }
+}
+
+pub struct Clamp<A> {
+    animation: A,
+}
+
+impl<A> Clamp<A> {
+    pub fn new(animation: A) -> Self {
+        Clamp { animation }
+    }
+}
+
+impl<T, A: Animation<T>> Animation<T> for Clamp<A> {
+    fn at(&self, t: TimeStamp) -> T {
+        let t = t.value.clamp(0.0, self.animation.duration().value);
+
+        self.animation.at(TimeStamp::new(t))
+    }
+
+    fn duration(&self) -> Duration {
+        self.animation.duration()
+    }
+}
+
+pub struct Map<A, F, T> {
+    animation: A,
+    function: F,
+    input: PhantomData<fn(T)>,
+}
+
+impl<A, F, T> Map<A, F, T> {
+    pub fn new(animation: A, function: F) -> Self {
+        Map {
+            animation,
+            function,
+            input: PhantomData,
+        }
+    }
+}
+
+impl<T, U, A: Animation<T>, F: Fn(T) -> U> Animation<U> for Map<A, F, T> {
+    fn at(&self, t: TimeStamp) -> U {
+        (self.function)(self.animation.at(t))
+    }
+
+    fn duration(&self) -> Duration {
+        self.animation.duration()
+    }
+}
+
+pub struct Zip<A, B> {
+    first: A,
+    second: B,
+}
+
+impl<A, B> Zip<A, B> {
+    pub fn new(first: A, second: B) -> Self {
+        Zip { first, second }
+    }
+}
+
+impl<T, U, A: Animation<T>, B: Animation<U>> Animation<(T, U)> for Zip<A, B> {
+    fn at(&self, t: TimeStamp) -> (T, U) {
+        (self.first.at(t), self.second.at(t))
+    }
+
+    fn duration(&self) -> Duration {
+        self.first.duration().max(self.second.duration())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    #[cfg(test)]
+    use super::*;
+
+    #[cfg(test)]
+    use crate::{
+        animation::{Interpolation, Keyframe, KeyframeTrack, LinearAnimation},
+        math::approx,
+    };
+
+    #[cfg(test)]
+    fn ramp(start: f64, end: f64, duration: f64) -> LinearAnimation<f64> {
+        LinearAnimation {
+            start,
+            end,
+            duration: Duration::from_seconds(duration),
+        }
+    }
+
+    #[rstest]
+    #[case(0.0, 0.0)]
+    #[case(0.5, 0.5)]
+    #[case(1.0, 10.0)]
+    #[case(2.0, 15.0)]
+    #[case(3.0, 20.0)]
+    fn sequence(#[case] t: f64, #[case] expected: f64) {
+        let animation = ramp(0.0, 1.0, 1.0).then(ramp(10.0, 20.0, 2.0));
+
+        assert_eq!(3.0, animation.duration().in_seconds());
+        assert_eq!(approx(expected), animation.at(TimeStamp::new(t)));
+    }
+
+    #[rstest]
+    #[case(0.0, 0.0)]
+    #[case(0.5, 0.25)]
+    #[case(2.5, 0.25)]
+    #[case(4.0, 0.0)]
+    #[case(5.9, 0.95)]
+    #[case(6.0, 1.0)]
+    #[case(7.0, 1.0)]
+    fn loop_times(#[case] t: f64, #[case] expected: f64) {
+        let animation = ramp(0.0, 1.0, 2.0).looped(Repetition::Times(3));
+
+        assert_eq!(6.0, animation.duration().in_seconds());
+        assert_eq!(approx(expected), animation.at(TimeStamp::new(t)));
+    }
+
+    #[rstest]
+    fn loop_forever() {
+        let animation = ramp(0.0, 1.0, 2.0).looped(Repetition::Forever);
+
+        assert!(animation.duration().in_seconds().is_infinite());
+        assert_eq!(approx(0.5), animation.at(TimeStamp::new(1001.0)));
+    }
+
+    #[rstest]
+    fn loop_timeless_forever(#[values(0.0, 1.0, 1000.0)] t: f64) {
+        let track = KeyframeTrack::new(vec![Keyframe::new(TimeStamp::new(0.0), 3.0, Interpolation::Linear)]);
+        let animation = Loop::new(track, Repetition::Forever);
+
+        assert_eq!(3.0, animation.at(TimeStamp::new(t)));
+    }
+
+    #[rstest]
+    #[case(0.0, 0.0)]
+    #[case(1.0, 0.5)]
+    #[case(2.0, 1.0)]
+    #[case(3.0, 0.5)]
+    #[case(4.0, 0.0)]
+    fn ping_pong(#[case] t: f64, #[case] expected: f64) {
+        let animation = ramp(0.0, 1.0, 2.0).ping_pong();
+
+        assert_eq!(4.0, animation.duration().in_seconds());
+        assert_eq!(approx(expected), animation.at(TimeStamp::new(t)));
+    }
+
+    #[rstest]
+    #[case(0.0, 0.0)]
+    #[case(1.0, 0.0)]
+    #[case(2.0, 0.5)]
+    #[case(3.0, 1.0)]
+    fn delay(#[case] t: f64, #[case] expected: f64) {
+        let animation = ramp(0.0, 1.0, 2.0).delayed(Duration::from_seconds(1.0));
+
+        assert_eq!(3.0, animation.duration().in_seconds());
+        assert_eq!(approx(expected), animation.at(TimeStamp::new(t)));
+    }
+
+    #[rstest]
+    #[case(2.0, 1.0, 1.0)]
+    #[case(2.0, 0.5, 0.5)]
+    #[case(0.5, 2.0, 0.5)]
+    fn speed(#[case] factor: f64, #[case] t: f64, #[case] expected: f64) {
+        let animation = ramp(0.0, 1.0, 2.0).speed(factor);
+
+        assert_eq!(approx(2.0 / factor), animation.duration().in_seconds());
+        assert_eq!(approx(expected), animation.at(TimeStamp::new(t)));
+    }
+
+    #[rstest]
+    #[case(-1.0, 0.0)]
+    #[case(1.0, 0.5)]
+    #[case(5.0, 1.0)]
+    fn clamp(#[case] t: f64, #[case] expected: f64) {
+        let animation = ramp(0.0, 1.0, 2.0).clamped();
+
+        assert_eq!(approx(expected), animation.at(TimeStamp::new(t)));
+    }
+
+    #[rstest]
+    fn map() {
+        let animation = ramp(0.0, 1.0, 2.0).map(|x| x > 0.5);
+
+        assert!(!animation.at(TimeStamp::new(0.5)));
+        assert!(animation.at(TimeStamp::new(1.5)));
+    }
+
+    #[rstest]
+    fn zip() {
+        let animation = ramp(0.0, 1.0, 2.0).zip(ConstantAnimation { value: 'x', duration: Duration::from_seconds(3.0) });
+
+        assert_eq!(3.0, animation.duration().in_seconds());
+        assert_eq!((0.5, 'x'), animation.at(TimeStamp::new(1.0)));
+    }
+}
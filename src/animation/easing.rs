@@ -0,0 +1,169 @@
+use std::f64::consts::PI;
+
+// Remaps normalized time in [0, 1] onto progress; all easings map 0 to 0 and 1 to 1
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Easing {
+    Linear,
+    QuadIn,
+    QuadOut,
+    QuadInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    ElasticIn,
+    ElasticOut,
+    ElasticInOut,
+    BounceIn,
+    BounceOut,
+    BounceInOut,
+}
+
+impl Easing {
+    pub fn apply(&self, t: f64) -> f64 {
+        let t = t.clamp(0.0, 1.0);
+
+        match self {
+            Easing::Linear => t,
+            Easing::QuadIn => t * t,
+            Easing::QuadOut => 1.0 - (1.0 - t).powi(2),
+            Easing::QuadInOut => {
+                if t < 0.5 { 2.0 * t * t } else { 1.0 - (2.0 - 2.0 * t).powi(2) / 2.0 }
+            }
+            Easing::CubicIn => t.powi(3),
+            Easing::CubicOut => 1.0 - (1.0 - t).powi(3),
+            Easing::CubicInOut => {
+                if t < 0.5 { 4.0 * t.powi(3) } else { 1.0 - (2.0 - 2.0 * t).powi(3) / 2.0 }
+            }
+            Easing::ElasticIn => elastic_in(t),
+            Easing::ElasticOut => 1.0 - elastic_in(1.0 - t),
+            Easing::ElasticInOut => {
+                if t < 0.5 { elastic_in(2.0 * t) / 2.0 } else { 1.0 - elastic_in(2.0 - 2.0 * t) / 2.0 }
+            }
+            Easing::BounceIn => 1.0 - bounce_out(1.0 - t),
+            Easing::BounceOut => bounce_out(t),
+            Easing::BounceInOut => {
+                if t < 0.5 { (1.0 - bounce_out(1.0 - 2.0 * t)) / 2.0 } else { (1.0 + bounce_out(2.0 * t - 1.0)) / 2.0 }
+            }
+        }
+    }
+}
+
+fn elastic_in(t: f64) -> f64 {
+    if t <= 0.0 {
+        0.0
+    } else if t >= 1.0 {
+        1.0
+    } else {
+        let period = 2.0 * PI / 3.0;
+
+        -(2.0f64.powf(10.0 * t - 10.0)) * ((10.0 * t - 10.75) * period).sin()
+    }
+}
+
+// Four parabolic arcs of decreasing height
+fn bounce_out(t: f64) -> f64 {
+    let n = 7.5625;
+    let d = 2.75;
+
+    if t < 1.0 / d {
+        n * t * t
+    } else if t < 2.0 / d {
+        let t = t - 1.5 / d;
+        n * t * t + 0.75
+    } else if t < 2.5 / d {
+        let t = t - 2.25 / d;
+        n * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / d;
+        n * t * t + 0.984375
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    #[cfg(test)]
+    use super::*;
+
+    use crate::math::approx;
+
+    const ALL: [Easing; 13] = [
+        Easing::Linear,
+        Easing::QuadIn,
+        Easing::QuadOut,
+        Easing::QuadInOut,
+        Easing::CubicIn,
+        Easing::CubicOut,
+        Easing::CubicInOut,
+        Easing::ElasticIn,
+        Easing::ElasticOut,
+        Easing::ElasticInOut,
+        Easing::BounceIn,
+        Easing::BounceOut,
+        Easing::BounceInOut,
+    ];
+
+    #[rstest]
+    fn endpoints_are_fixed() {
+        for easing in ALL {
+            assert_eq!(approx(0.0), easing.apply(0.0), "{easing:?}");
+            assert_eq!(approx(1.0), easing.apply(1.0), "{easing:?}");
+        }
+    }
+
+    #[rstest]
+    fn in_out_variants_are_symmetric() {
+        for easing in [Easing::QuadInOut, Easing::CubicInOut, Easing::ElasticInOut, Easing::BounceInOut] {
+            assert_eq!(approx(0.5), easing.apply(0.5), "{easing:?}");
+
+            for t in [0.1, 0.2, 0.35] {
+                assert_eq!(approx(1.0 - easing.apply(1.0 - t)), easing.apply(t), "{easing:?}");
+            }
+        }
+    }
+
+    #[rstest]
+    #[case(Easing::QuadIn, 0.5, 0.25)]
+    #[case(Easing::QuadOut, 0.5, 0.75)]
+    #[case(Easing::CubicIn, 0.5, 0.125)]
+    #[case(Easing::CubicOut, 0.5, 0.875)]
+    #[case(Easing::QuadInOut, 0.25, 0.125)]
+    #[case(Easing::CubicInOut, 0.25, 0.0625)]
+    fn polynomial_values(#[case] easing: Easing, #[case] t: f64, #[case] expected: f64) {
+        assert_eq!(approx(expected), easing.apply(t));
+    }
+
+    #[rstest]
+    fn polynomial_easings_are_monotonic() {
+        for easing in [Easing::QuadIn, Easing::QuadOut, Easing::QuadInOut, Easing::CubicIn, Easing::CubicOut, Easing::CubicInOut] {
+            let values: Vec<f64> = (0..=100).map(|i| easing.apply(i as f64 / 100.0)).collect();
+
+            assert!(values.windows(2).all(|pair| pair[0] <= pair[1]), "{easing:?}");
+        }
+    }
+
+    #[rstest]
+    fn elastic_out_overshoots() {
+        let maximum = (0..=100).map(|i| Easing::ElasticOut.apply(i as f64 / 100.0)).fold(f64::MIN, f64::max);
+
+        assert!(maximum > 1.0);
+    }
+
+    #[rstest]
+    fn bounce_stays_in_range() {
+        for easing in [Easing::BounceIn, Easing::BounceOut, Easing::BounceInOut] {
+            for i in 0..=100 {
+                let value = easing.apply(i as f64 / 100.0);
+
+                assert!((0.0..=1.0 + 1e-12).contains(&value), "{easing:?}");
+            }
+        }
+    }
+
+    #[rstest]
+    fn bounce_out_touches_ground_between_bounces() {
+        assert_eq!(approx(1.0), Easing::BounceOut.apply(1.0 / 2.75));
+        assert_eq!(approx(0.75), Easing::BounceOut.apply(1.5 / 2.75));
+    }
+}
@@ -0,0 +1,322 @@
+use super::{easing::Easing, linear::Interpolate, Animation, Duration, TimeStamp};
+
+// Determines how the value evolves from a key to the next one.
+// Tangents and handles are expressed as values so that only Interpolate is required of T:
+// a Hermite tangent target Q stands for the derivative Q - P with respect to the segment's normalized time.
+#[derive(Debug, Clone)]
+pub enum Interpolation<T> {
+    Step,
+    Linear,
+    Hermite { start_tangent: T, end_tangent: T },
+    CatmullRom,
+    Bezier { start_handle: T, end_handle: T },
+}
+
+#[derive(Debug, Clone)]
+pub struct Keyframe<T> {
+    pub time: TimeStamp,
+    pub value: T,
+    pub interpolation: Interpolation<T>,
+    pub easing: Easing,
+}
+
+pub struct KeyframeTrack<T> {
+    keys: Vec<Keyframe<T>>,
+}
+
+impl<T> Keyframe<T> {
+    pub fn new(time: TimeStamp, value: T, interpolation: Interpolation<T>) -> Self {
+        Keyframe {
+            time,
+            value,
+            interpolation,
+            easing: Easing::Linear,
+        }
+    }
+
+    pub fn eased(self, easing: Easing) -> Self {
+        Keyframe { easing, ..self }
+    }
+}
+
+impl<T: Interpolate + Clone> KeyframeTrack<T> {
+    pub fn new(mut keys: Vec<Keyframe<T>>) -> Self {
+        debug_assert!(!keys.is_empty());
+
+        keys.sort_by(|a, b| a.time.partial_cmp(&b.time).unwrap());
+
+        KeyframeTrack { keys }
+    }
+
+    fn time_of(&self, index: usize) -> f64 {
+        self.keys[index].time.in_seconds()
+    }
+
+    // Catmull-Rom needs the keys surrounding the segment; missing ones are mirrored,
+    // as are keys sharing a time with the segment's ends (jumps), which would cause divisions by zero
+    fn neighbours(&self, index: usize) -> ((f64, T), (f64, T)) {
+        let t0 = self.time_of(index);
+        let t1 = self.time_of(index + 1);
+        let p0 = &self.keys[index].value;
+        let p1 = &self.keys[index + 1].value;
+
+        let previous = if index > 0 && self.time_of(index - 1) < t0 {
+            (self.time_of(index - 1), self.keys[index - 1].value.clone())
+        } else {
+            (2.0 * t0 - t1, T::interpolate(p1, p0, 2.0))
+        };
+        let next = if index + 2 < self.keys.len() && self.time_of(index + 2) > t1 {
+            (self.time_of(index + 2), self.keys[index + 2].value.clone())
+        } else {
+            (2.0 * t1 - t0, T::interpolate(p0, p1, 2.0))
+        };
+
+        (previous, next)
+    }
+
+    fn evaluate_segment(&self, index: usize, t: f64) -> T {
+        let key = &self.keys[index];
+        let next = &self.keys[index + 1];
+        let t0 = self.time_of(index);
+        let t1 = self.time_of(index + 1);
+        let u = key.easing.apply((t - t0) / (t1 - t0));
+        let p0 = &key.value;
+        let p1 = &next.value;
+
+        match &key.interpolation {
+            Interpolation::Step => p0.clone(),
+            Interpolation::Linear => T::interpolate(p0, p1, u),
+            Interpolation::Hermite { start_tangent, end_tangent } => {
+                let c1 = T::interpolate(p0, start_tangent, 1.0 / 3.0);
+                let c2 = T::interpolate(p1, end_tangent, -1.0 / 3.0);
+
+                de_casteljau(p0, &c1, &c2, p1, u)
+            }
+            Interpolation::Bezier { start_handle, end_handle } => de_casteljau(p0, start_handle, end_handle, p1, u),
+            Interpolation::CatmullRom => {
+                let ((tp, pp), (tn, pn)) = self.neighbours(index);
+                let t = t0 + u * (t1 - t0);
+
+                barry_goldman([&pp, p0, p1, &pn], [tp, t0, t1, tn], t)
+            }
+        }
+    }
+}
+
+fn de_casteljau<T: Interpolate>(p0: &T, p1: &T, p2: &T, p3: &T, u: f64) -> T {
+    let a = T::interpolate(p0, p1, u);
+    let b = T::interpolate(p1, p2, u);
+    let c = T::interpolate(p2, p3, u);
+    let d = T::interpolate(&a, &b, u);
+    let e = T::interpolate(&b, &c, u);
+
+    T::interpolate(&d, &e, u)
+}
+
+// Catmull-Rom spline through p[1] and p[2] evaluated as a pyramid of linear interpolations
+fn barry_goldman<T: Interpolate>(p: [&T; 4], k: [f64; 4], t: f64) -> T {
+    let ratio = |from: f64, to: f64| (t - from) / (to - from);
+
+    let a1 = T::interpolate(p[0], p[1], ratio(k[0], k[1]));
+    let a2 = T::interpolate(p[1], p[2], ratio(k[1], k[2]));
+    let a3 = T::interpolate(p[2], p[3], ratio(k[2], k[3]));
+    let b1 = T::interpolate(&a1, &a2, ratio(k[0], k[2]));
+    let b2 = T::interpolate(&a2, &a3, ratio(k[1], k[3]));
+
+    T::interpolate(&b1, &b2, ratio(k[1], k[2]))
+}
+
+impl<T: Interpolate + Clone> Animation<T> for KeyframeTrack<T> {
+    fn duration(&self) -> Duration {
+        self.keys[self.keys.len() - 1].time - TimeStamp::zero()
+    }
+
+    fn at(&self, t: TimeStamp) -> T {
+        let t = t.in_seconds();
+        let last = self.keys.len() - 1;
+
+        if t <= self.time_of(0) {
+            self.keys[0].value.clone()
+        } else if t >= self.time_of(last) {
+            self.keys[last].value.clone()
+        } else {
+            let index = self.keys.partition_point(|key| key.time.in_seconds() <= t) - 1;
+
+            self.evaluate_segment(index, t)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    #[cfg(test)]
+    use super::*;
+
+    use crate::math::approx;
+
+    fn key(time: f64, value: f64, interpolation: Interpolation<f64>) -> Keyframe<f64> {
+        Keyframe::new(TimeStamp::new(time), value, interpolation)
+    }
+
+    fn value_at(track: &KeyframeTrack<f64>, t: f64) -> f64 {
+        track.at(TimeStamp::new(t))
+    }
+
+    #[rstest]
+    fn clamps_outside_keys() {
+        let track = KeyframeTrack::new(vec![key(1.0, 5.0, Interpolation::Linear), key(2.0, 7.0, Interpolation::Linear)]);
+
+        assert_eq!(5.0, value_at(&track, 0.0));
+        assert_eq!(7.0, value_at(&track, 3.0));
+        assert_eq!(approx(2.0), track.duration().in_seconds());
+    }
+
+    #[rstest]
+    fn keys_are_sorted() {
+        let track = KeyframeTrack::new(vec![key(2.0, 7.0, Interpolation::Linear), key(1.0, 5.0, Interpolation::Linear)]);
+
+        assert_eq!(approx(6.0), value_at(&track, 1.5));
+    }
+
+    #[rstest]
+    fn step() {
+        let track = KeyframeTrack::new(vec![key(0.0, 1.0, Interpolation::Step), key(1.0, 2.0, Interpolation::Step), key(2.0, 3.0, Interpolation::Step)]);
+
+        assert_eq!(1.0, value_at(&track, 0.99));
+        assert_eq!(2.0, value_at(&track, 1.0));
+        assert_eq!(2.0, value_at(&track, 1.5));
+    }
+
+    #[rstest]
+    #[case(0.0, 0.0)]
+    #[case(0.5, 5.0)]
+    #[case(1.0, 10.0)]
+    #[case(2.0, 0.0)]
+    #[case(3.0, -10.0)]
+    fn linear(#[case] t: f64, #[case] expected: f64) {
+        let track = KeyframeTrack::new(vec![
+            key(0.0, 0.0, Interpolation::Linear),
+            key(1.0, 10.0, Interpolation::Linear),
+            key(3.0, -10.0, Interpolation::Linear),
+        ]);
+
+        assert_eq!(approx(expected), value_at(&track, t));
+    }
+
+    #[rstest]
+    fn per_segment_interpolation() {
+        let track = KeyframeTrack::new(vec![
+            key(0.0, 0.0, Interpolation::Step),
+            key(1.0, 10.0, Interpolation::Linear),
+            key(2.0, 20.0, Interpolation::Linear),
+        ]);
+
+        assert_eq!(approx(0.0), value_at(&track, 0.5));
+        assert_eq!(approx(15.0), value_at(&track, 1.5));
+    }
+
+    #[rstest]
+    fn hermite_with_matching_tangents_is_linear(#[values(0.1, 0.3, 0.5, 0.9)] t: f64) {
+        // Slope of 10 per segment: tangent targets lie one full segment further along the line
+        let track = KeyframeTrack::new(vec![
+            key(0.0, 0.0, Interpolation::Hermite { start_tangent: 10.0, end_tangent: 20.0 }),
+            key(1.0, 10.0, Interpolation::Linear),
+        ]);
+
+        assert_eq!(approx(10.0 * t), value_at(&track, t));
+    }
+
+    #[rstest]
+    fn hermite_with_flat_tangents_eases() {
+        let track = KeyframeTrack::new(vec![
+            key(0.0, 0.0, Interpolation::Hermite { start_tangent: 0.0, end_tangent: 1.0 }),
+            key(1.0, 1.0, Interpolation::Linear),
+        ]);
+
+        // Smoothstep: 3t^2 - 2t^3
+        assert_eq!(approx(0.5), value_at(&track, 0.5));
+        assert_eq!(approx(0.15625), value_at(&track, 0.25));
+    }
+
+    #[rstest]
+    fn bezier() {
+        let track = KeyframeTrack::new(vec![
+            key(0.0, 0.0, Interpolation::Bezier { start_handle: 1.0, end_handle: 1.0 }),
+            key(1.0, 0.0, Interpolation::Linear),
+        ]);
+
+        // 3(1-t)^2 t + 3(1-t) t^2 at t = 0.5
+        assert_eq!(approx(0.75), value_at(&track, 0.5));
+        assert_eq!(approx(0.0), value_at(&track, 0.0));
+    }
+
+    #[rstest]
+    fn catmull_rom_passes_through_keys() {
+        let track = KeyframeTrack::new(vec![
+            key(0.0, 0.0, Interpolation::CatmullRom),
+            key(1.0, 3.0, Interpolation::CatmullRom),
+            key(3.0, -1.0, Interpolation::CatmullRom),
+            key(4.0, 2.0, Interpolation::CatmullRom),
+        ]);
+
+        assert_eq!(approx(3.0), value_at(&track, 1.0));
+        assert_eq!(approx(-1.0), value_at(&track, 3.0));
+        assert_eq!(approx(value_at(&track, 1.0 - 1e-9)), value_at(&track, 1.0 + 1e-9));
+    }
+
+    #[rstest]
+    fn catmull_rom_reproduces_lines(#[values(0.25, 1.5, 2.75, 3.5)] t: f64) {
+        let track = KeyframeTrack::new(vec![
+            key(0.0, 1.0, Interpolation::CatmullRom),
+            key(1.0, 3.0, Interpolation::CatmullRom),
+            key(3.0, 7.0, Interpolation::CatmullRom),
+            key(4.0, 9.0, Interpolation::CatmullRom),
+        ]);
+
+        assert_eq!(approx(1.0 + 2.0 * t), value_at(&track, t));
+    }
+
+    #[rstest]
+    fn catmull_rom_is_smooth() {
+        let track = KeyframeTrack::new(vec![
+            key(0.0, 0.0, Interpolation::CatmullRom),
+            key(1.0, 2.0, Interpolation::CatmullRom),
+            key(2.0, 1.0, Interpolation::CatmullRom),
+        ]);
+        let h = 1e-6;
+        let left_slope = (value_at(&track, 1.0) - value_at(&track, 1.0 - h)) / h;
+        let right_slope = (value_at(&track, 1.0 + h) - value_at(&track, 1.0)) / h;
+
+        assert!((left_slope - right_slope).abs() < 0.001);
+    }
+
+    #[rstest]
+    #[case(2.0, 0.5, 1.0)]
+    #[case(2.0, 1.0, 5.0)]
+    #[case(2.0, 1.5, 6.0)]
+    #[case(5.0, 0.5, 2.5)]
+    #[case(5.0, 1.0, 5.0)]
+    #[case(5.0, 1.5, 6.0)]
+    fn catmull_rom_with_coinciding_keys(#[case] value_before_jump: f64, #[case] t: f64, #[case] expected: f64) {
+        let track = KeyframeTrack::new(vec![
+            key(0.0, 0.0, Interpolation::CatmullRom),
+            key(1.0, value_before_jump, Interpolation::CatmullRom),
+            key(1.0, 5.0, Interpolation::CatmullRom),
+            key(2.0, 7.0, Interpolation::CatmullRom),
+        ]);
+
+        assert_eq!(approx(expected), value_at(&track, t));
+    }
+
+    #[rstest]
+    fn easing_applies_to_segment() {
+        let track = KeyframeTrack::new(vec![
+            key(0.0, 0.0, Interpolation::Linear).eased(Easing::QuadIn),
+            key(2.0, 10.0, Interpolation::Linear),
+        ]);
+
+        assert_eq!(approx(2.5), value_at(&track, 1.0));
+    }
+}
@@ -1,3 +1,8 @@
+use crate::{
+    imaging::color::Color,
+    math::{transformation3d::{Decomposition, Transformation3D}, Angle, Point, Vector},
+};
+
 use super::{Animation, Duration, TimeStamp};
 
 pub trait Interpolate {
@@ -26,6 +31,45 @@ impl Interpolate for f64 {
     }
 }
 
+impl<const N: usize> Interpolate for Vector<N> {
+    fn interpolate(start: &Self, end: &Self, t: f64) -> Self {
+        *start + (end - start) * t
+    }
+}
+
+impl<const N: usize> Interpolate for Point<N> {
+    fn interpolate(start: &Self, end: &Self, t: f64) -> Self {
+        start + &((end - start) * t)
+    }
+}
+
+impl Interpolate for Color {
+    fn interpolate(start: &Self, end: &Self, t: f64) -> Self {
+        start * (1.0 - t) + end * t
+    }
+}
+
+impl Interpolate for Angle {
+    fn interpolate(start: &Self, end: &Self, t: f64) -> Self {
+        Angle::radians(f64::interpolate(&start.in_radians(), &end.in_radians(), t))
+    }
+}
+
+impl Interpolate for Transformation3D {
+    fn interpolate(start: &Self, end: &Self, t: f64) -> Self {
+        let start = start.decompose();
+        let end = end.decompose();
+
+        let decomposition = Decomposition {
+            translation: Vector::interpolate(&start.translation, &end.translation, t),
+            rotation: start.rotation.slerp(&end.rotation, t),
+            scale: Vector::interpolate(&start.scale, &end.scale, t),
+        };
+
+        Transformation3D::from_decomposition(&decomposition)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use rstest::rstest;
@@ -33,6 +77,9 @@ mod test {
     #[cfg(test)]
     use super::*;
 
+    #[cfg(test)]
+    use crate::math::{approx, pt, vc, Matrix};
+
     #[rstest]
     #[case(0.0, 1.0, 1.0, 0.0, 0.0)]
     #[case(0.0, 1.0, 1.0, 0.5, 0.5)]
@@ -51,4 +98,49 @@ mod test {
 
         assert_eq!(expected, actual);
     }
-}
\ No newline at end of file
+
+    #[rstest]
+    #[case(0.0, pt!(1, 2, 3))]
+    #[case(0.5, pt!(2, 1, 3))]
+    #[case(1.0, pt!(3, 0, 3))]
+    fn point_interpolation(#[case] t: f64, #[case] expected: Point<3>) {
+        let actual = Point::interpolate(&pt!(1, 2, 3), &pt!(3, 0, 3), t);
+
+        assert_eq!(approx(expected), actual);
+    }
+
+    #[rstest]
+    fn color_interpolation() {
+        let actual = Color::interpolate(&Color::black(), &Color::new(1.0, 0.5, 0.0), 0.5);
+
+        assert_eq!(approx(0.5), actual.r());
+        assert_eq!(approx(0.25), actual.g());
+        assert_eq!(approx(0.0), actual.b());
+    }
+
+    #[rstest]
+    fn angle_interpolation() {
+        let actual = Angle::interpolate(&Angle::degrees(10.0), &Angle::degrees(50.0), 0.25);
+
+        assert_eq!(approx(Angle::degrees(20.0)), actual);
+    }
+
+    #[rstest]
+    fn transformation_interpolation_rotates_rigidly(#[values(0.0, 0.25, 0.5, 1.0)] t: f64) {
+        let start = Transformation3D::translate(&vc!(0, 0, 0));
+        let end = Transformation3D::translate(&vc!(4, 0, 0)).compose(&Transformation3D::rotate_around_z(Angle::degrees(90.0)));
+        let actual = Transformation3D::interpolate(&start, &end, t);
+        let expected = Transformation3D::translate(&vc!(4.0 * t, 0, 0)).compose(&Transformation3D::rotate_around_z(Angle::degrees(90.0 * t)));
+
+        assert_eq!(approx(expected.matrix), actual.matrix);
+    }
+
+    #[rstest]
+    fn transformation_interpolation_scales_linearly() {
+        let start = Transformation3D::scale(1.0, 1.0, 1.0);
+        let end = Transformation3D::scale(3.0, 1.0, 5.0);
+        let actual = Transformation3D::interpolate(&start, &end, 0.5);
+
+        assert_eq!(approx(Matrix::scale(2.0, 1.0, 3.0)), actual.matrix);
+    }
+}
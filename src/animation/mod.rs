@@ -1,3 +1,6 @@
+mod combinators;
+mod easing;
+mod keyframe;
 mod linear;
 mod time;
 mod timeline;
@@ -7,8 +10,67 @@ pub trait Animation<T> {
     fn at(&self, t: TimeStamp) -> T;
 
     fn duration(&self) -> Duration;
+
+    fn then<A: Animation<T>>(self, next: A) -> Sequence<Self, A>
+    where
+        Self: Sized,
+    {
+        Sequence::new(self, next)
+    }
+
+    fn looped(self, repetition: Repetition) -> Loop<Self>
+    where
+        Self: Sized,
+    {
+        Loop::new(self, repetition)
+    }
+
+    fn ping_pong(self) -> PingPong<Self>
+    where
+        Self: Sized,
+    {
+        PingPong::new(self)
+    }
+
+    fn delayed(self, delay: Duration) -> Delay<Self>
+    where
+        Self: Sized,
+    {
+        Delay::new(self, delay)
+    }
+
+    fn speed(self, factor: f64) -> Speed<Self>
+    where
+        Self: Sized,
+    {
+        Speed::new(self, factor)
+    }
+
+    fn clamped(self) -> Clamp<Self>
+    where
+        Self: Sized,
+    {
+        Clamp::new(self)
+    }
+
+    fn map<U, F: Fn(T) -> U>(self, function: F) -> Map<Self, F, T>
+    where
+        Self: Sized,
+    {
+        Map::new(self, function)
+    }
+
+    fn zip<U, A: Animation<U>>(self, other: A) -> Zip<Self, A>
+    where
+        Self: Sized,
+    {
+        Zip::new(self, other)
+    }
 }
 
+pub use combinators::{Clamp, ConstantAnimation, Delay, Loop, Map, PingPong, Repetition, Sequence, Speed, Zip};
+pub use easing::Easing;
+pub use keyframe::{Interpolation, Keyframe, KeyframeTrack};
 pub use linear::LinearAnimation;
 pub use time::{TimeStamp, Duration};
 pub use timeline::TimeLine;
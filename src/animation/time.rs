@@ -18,6 +18,10 @@ impl TimeStamp {
     pub fn zero() -> Self {
         Self::new(0.0)
     }
+
+    pub fn in_seconds(&self) -> f64 {
+        self.value
+    }
 }
 
 impl Duration {
@@ -28,6 +32,10 @@ impl Duration {
     pub fn in_seconds(&self) -> f64 {
         self.value
     }
+
+    pub fn max(self, other: Duration) -> Duration {
+        Duration { value: self.value.max(other.value) }
+    }
 }
 
 impl Add<Duration> for TimeStamp {
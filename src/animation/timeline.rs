@@ -20,6 +20,10 @@ impl TimeLine {
         }
     }
 
+    pub fn frames_per_second(&self) -> u32 {
+        self.frames_per_second
+    }
+
     pub fn frame_count(&self) -> u32 {
         (self.duration.in_seconds() * self.frames_per_second as f64) as u32
     }
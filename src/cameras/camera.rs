@@ -0,0 +1,61 @@
+use crate::math::{pt, Matrix, Point, Ray, Vector};
+
+pub trait Camera {
+    fn enumerate_rays(&self, sample: &CameraSample) -> Box<dyn Iterator<Item = Ray> + '_>;
+}
+
+// Screen position in [0, 1]^2 together with a sample in [0, 1)^2 used to pick a point on the lens
+// and the relative moment in [0, 1) within the shutter interval
+#[derive(Copy, Clone, Debug)]
+pub struct CameraSample {
+    pub screen: Point<2>,
+    pub lens: Point<2>,
+    pub time: f64,
+}
+
+impl CameraSample {
+    pub fn new(screen: Point<2>, lens: Point<2>, time: f64) -> Self {
+        CameraSample { screen, lens, time }
+    }
+
+    // Sample through the center of the lens at the moment the shutter opens
+    pub fn at(screen: Point<2>) -> Self {
+        Self::new(screen, pt!(0.5, 0.5), 0.0)
+    }
+}
+
+// Camera space has the x-axis pointing right, the y-axis pointing up and looks along the negative z-axis
+pub fn create_coordinate_system(
+    eye: &Point<3>,
+    look_at: &Point<3>,
+    up: &Vector<3>,
+) -> (Point<3>, Vector<3>, Vector<3>, Vector<3>) {
+    let look_direction = (*look_at - *eye).normalized();
+    let right = look_direction.cross(up).normalized();
+    let up = right.cross(&look_direction);
+
+    debug_assert!(look_direction.is_unit());
+    debug_assert!(right.is_unit());
+    debug_assert!(up.is_unit());
+    debug_assert!(look_direction.is_orthogonal_to(&right));
+
+    let origin = *eye;
+    let z_axis = -look_direction;
+    let y_axis = up;
+    let x_axis = right;
+
+    debug_assert!(x_axis.is_unit());
+    debug_assert!(y_axis.is_unit());
+    debug_assert!(z_axis.is_unit());
+    debug_assert!(x_axis.is_orthogonal_to(&y_axis));
+    debug_assert!(x_axis.is_orthogonal_to(&z_axis));
+    debug_assert!(y_axis.is_orthogonal_to(&z_axis));
+
+    (origin, x_axis, y_axis, z_axis)
+}
+
+pub fn create_transformation_matrix(eye: &Point<3>, look_at: &Point<3>, up: &Vector<3>) -> Matrix<4, 4> {
+    let (origin, x_axis, y_axis, z_axis) = create_coordinate_system(eye, look_at, up);
+
+    Matrix::from_coordinate_system(&origin, &x_axis, &y_axis, &z_axis)
+}
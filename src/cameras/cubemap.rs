@@ -0,0 +1,134 @@
+use crate::math::{vc, Matrix, Point, Ray, Vector};
+
+use super::camera::{create_transformation_matrix, Camera, CameraSample};
+
+// Renders six 90 degree views into a 3x2 grid:
+//
+//     right | left  | up
+//     down  | front | back
+//
+// Images should have a 3:2 aspect ratio so that each face is square.
+pub struct CubeMapCamera {
+    transformation_matrix: Matrix<4, 4>,
+}
+
+pub struct CubeMapCameraParameters {
+    pub eye: Point<3>,
+    pub look_at: Point<3>,
+    pub up: Vector<3>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum CubeFace {
+    Right,
+    Left,
+    Up,
+    Down,
+    Front,
+    Back,
+}
+
+const COLUMNS: usize = 3;
+const ROWS: usize = 2;
+const LAYOUT: [CubeFace; COLUMNS * ROWS] = [
+    CubeFace::Right, CubeFace::Left, CubeFace::Up,
+    CubeFace::Down, CubeFace::Front, CubeFace::Back,
+];
+
+impl CubeFace {
+    // Forward, right and up vectors in camera space
+    fn basis(&self) -> (Vector<3>, Vector<3>, Vector<3>) {
+        match self {
+            CubeFace::Front => (vc!(0, 0, -1), vc!(1, 0, 0), vc!(0, 1, 0)),
+            CubeFace::Back => (vc!(0, 0, 1), vc!(-1, 0, 0), vc!(0, 1, 0)),
+            CubeFace::Right => (vc!(1, 0, 0), vc!(0, 0, 1), vc!(0, 1, 0)),
+            CubeFace::Left => (vc!(-1, 0, 0), vc!(0, 0, -1), vc!(0, 1, 0)),
+            CubeFace::Up => (vc!(0, 1, 0), vc!(1, 0, 0), vc!(0, 0, 1)),
+            CubeFace::Down => (vc!(0, -1, 0), vc!(1, 0, 0), vc!(0, 0, -1)),
+        }
+    }
+}
+
+impl CubeMapCamera {
+    pub fn new(parameters: &CubeMapCameraParameters) -> Self {
+        CubeMapCamera {
+            transformation_matrix: create_transformation_matrix(&parameters.eye, &parameters.look_at, &parameters.up),
+        }
+    }
+}
+
+fn direction(point: &Point<2>) -> Vector<3> {
+    let x = point.x() * COLUMNS as f64;
+    let y = point.y() * ROWS as f64;
+    let column = (x as usize).min(COLUMNS - 1);
+    let row = (y as usize).min(ROWS - 1);
+    let face = LAYOUT[row * COLUMNS + column];
+    let (forward, right, up) = face.basis();
+
+    // Position within the face in [-1, 1]
+    let s = 2.0 * (x - column as f64) - 1.0;
+    let t = 1.0 - 2.0 * (y - row as f64);
+
+    forward + right * s + up * t
+}
+
+impl Camera for CubeMapCamera {
+    fn enumerate_rays(&self, sample: &CameraSample) -> Box<dyn Iterator<Item = Ray> + '_> {
+        let ray = Ray::new(Point::zero(), direction(&sample.screen));
+
+        Box::new(std::iter::once(&self.transformation_matrix * &ray))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(test)]
+    use super::*;
+    use rstest::rstest;
+
+    #[cfg(test)]
+    use crate::math::{approx, pt};
+
+    #[rstest]
+    #[case(pt!(1.0 / 6.0, 0.25), vc!(1, 0, 0))]
+    #[case(pt!(0.5, 0.25), vc!(-1, 0, 0))]
+    #[case(pt!(5.0 / 6.0, 0.25), vc!(0, 1, 0))]
+    #[case(pt!(1.0 / 6.0, 0.75), vc!(0, -1, 0))]
+    #[case(pt!(0.5, 0.75), vc!(0, 0, -1))]
+    #[case(pt!(5.0 / 6.0, 0.75), vc!(0, 0, 1))]
+    fn face_centers(#[case] p: Point<2>, #[case] expected: Vector<3>) {
+        let camera = CubeMapCamera::new(&CubeMapCameraParameters {
+            eye: pt!(0, 0, 0),
+            look_at: pt!(0, 0, -1),
+            up: vc!(0, 1, 0),
+        });
+        let rays: Vec<Ray> = camera.enumerate_rays(&CameraSample::at(p)).collect();
+
+        assert_eq!(1, rays.len());
+        assert_eq!(approx(expected), rays[0].direction.normalized());
+    }
+
+    #[rstest]
+    fn front_face_covers_ninety_degrees() {
+        assert_eq!(approx(vc!(-1, 1, -1)), direction(&pt!(1.0 / 3.0, 0.5)));
+        assert_eq!(approx(vc!(0.6, -0.6, -1)), direction(&pt!(0.6, 0.9)));
+    }
+
+    #[rstest]
+    fn adjacent_faces_share_edges() {
+        // Right edge of the front face meets the left edge of the right face
+        let front_edge = CubeFace::Front.basis();
+        let right_edge = CubeFace::Right.basis();
+
+        assert_eq!(approx(front_edge.0 + front_edge.1), right_edge.0 - right_edge.1);
+    }
+
+    #[rstest]
+    fn basis_is_right_handed(
+        #[values(CubeFace::Right, CubeFace::Left, CubeFace::Up, CubeFace::Down, CubeFace::Front, CubeFace::Back)] face: CubeFace,
+    ) {
+        let (forward, right, up) = face.basis();
+
+        assert_eq!(approx(-forward), right.cross(&up));
+    }
+}
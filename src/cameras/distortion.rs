@@ -0,0 +1,91 @@
+use crate::math::Point;
+
+use crate::pt;
+
+const UNDISTORT_ITERATIONS: u32 = 20;
+
+// Brown-Conrady lens model with three radial and two tangential coefficients.
+// Operates on image coordinates normalized by the focal length, with x pointing right and y pointing down,
+// so that coefficients obtained from the usual calibration tools can be used as is.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct LensDistortion {
+    pub k1: f64,
+    pub k2: f64,
+    pub k3: f64,
+    pub p1: f64,
+    pub p2: f64,
+}
+
+impl LensDistortion {
+    pub fn radial(k1: f64, k2: f64, k3: f64) -> Self {
+        LensDistortion { k1, k2, k3, p1: 0.0, p2: 0.0 }
+    }
+
+    pub fn distort(&self, point: &Point<2>) -> Point<2> {
+        let x = point.x();
+        let y = point.y();
+        let r2 = x * x + y * y;
+        let radial = 1.0 + r2 * (self.k1 + r2 * (self.k2 + r2 * self.k3));
+        let dx = 2.0 * self.p1 * x * y + self.p2 * (r2 + 2.0 * x * x);
+        let dy = self.p1 * (r2 + 2.0 * y * y) + 2.0 * self.p2 * x * y;
+
+        pt!(x * radial + dx, y * radial + dy)
+    }
+
+    // The model has no closed form inverse; refine by fixed point iteration
+    pub fn undistort(&self, point: &Point<2>) -> Point<2> {
+        let mut result = *point;
+
+        for _ in 0..UNDISTORT_ITERATIONS {
+            let distorted = self.distort(&result);
+            result = pt!(result.x() + point.x() - distorted.x(), result.y() + point.y() - distorted.y());
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    #[cfg(test)]
+    use super::*;
+
+    use crate::math::approx;
+
+    #[rstest]
+    fn no_coefficients_is_identity() {
+        let distortion = LensDistortion::radial(0.0, 0.0, 0.0);
+
+        assert_eq!(approx(pt!(0.3, -0.2)), distortion.distort(&pt!(0.3, -0.2)));
+    }
+
+    #[rstest]
+    fn center_is_fixed() {
+        let distortion = LensDistortion { k1: 0.1, k2: 0.01, k3: 0.0, p1: 0.01, p2: 0.02 };
+
+        assert_eq!(approx(pt!(0, 0)), distortion.distort(&pt!(0, 0)));
+    }
+
+    #[rstest]
+    #[case(0.1, true)]
+    #[case(-0.1, false)]
+    fn radial_direction(#[case] k1: f64, #[case] outwards: bool) {
+        let distortion = LensDistortion::radial(k1, 0.0, 0.0);
+        let distorted = distortion.distort(&pt!(0.5, 0));
+
+        assert_eq!(outwards, distorted.x() > 0.5);
+        assert_eq!(approx(0.0), distorted.y());
+    }
+
+    #[rstest]
+    fn undistort_inverts_distort(
+        #[values(LensDistortion::radial(0.1, 0.0, 0.0), LensDistortion::radial(-0.2, 0.05, 0.0), LensDistortion { k1: 0.05, k2: 0.0, k3: 0.01, p1: 0.01, p2: -0.005 })] distortion: LensDistortion,
+        #[values(pt!(0, 0), pt!(0.4, 0.3), pt!(-0.6, 0.2))] point: Point<2>,
+    ) {
+        let undistorted = distortion.undistort(&point);
+
+        assert_eq!(approx(point), distortion.distort(&undistorted));
+    }
+}
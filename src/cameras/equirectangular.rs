@@ -0,0 +1,71 @@
+use std::f64::consts::PI;
+
+use crate::math::{vc, Matrix, Point, Ray, Vector};
+
+use super::camera::{create_transformation_matrix, Camera, CameraSample};
+
+// Maps the full sphere of directions onto the image: longitude along x, latitude along y.
+// The look direction ends up in the center of the image; images should have a 2:1 aspect ratio.
+pub struct EquirectangularCamera {
+    transformation_matrix: Matrix<4, 4>,
+}
+
+pub struct EquirectangularCameraParameters {
+    pub eye: Point<3>,
+    pub look_at: Point<3>,
+    pub up: Vector<3>,
+}
+
+impl EquirectangularCamera {
+    pub fn new(parameters: &EquirectangularCameraParameters) -> Self {
+        EquirectangularCamera {
+            transformation_matrix: create_transformation_matrix(&parameters.eye, &parameters.look_at, &parameters.up),
+        }
+    }
+}
+
+fn direction(point: &Point<2>) -> Vector<3> {
+    let longitude = (point.x() - 0.5) * 2.0 * PI;
+    let latitude = (0.5 - point.y()) * PI;
+
+    vc!(latitude.cos() * longitude.sin(), latitude.sin(), -latitude.cos() * longitude.cos())
+}
+
+impl Camera for EquirectangularCamera {
+    fn enumerate_rays(&self, sample: &CameraSample) -> Box<dyn Iterator<Item = Ray> + '_> {
+        let ray = Ray::new(Point::zero(), direction(&sample.screen));
+
+        Box::new(std::iter::once(&self.transformation_matrix * &ray))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(test)]
+    use super::*;
+    use rstest::rstest;
+
+    #[cfg(test)]
+    use crate::math::{approx, pt};
+
+    #[rstest]
+    #[case(pt!(0.5, 0.5), vc!(0, 0, -1))]
+    #[case(pt!(0.75, 0.5), vc!(1, 0, 0))]
+    #[case(pt!(0.25, 0.5), vc!(-1, 0, 0))]
+    #[case(pt!(0, 0.5), vc!(0, 0, 1))]
+    #[case(pt!(1, 0.5), vc!(0, 0, 1))]
+    #[case(pt!(0.3, 0), vc!(0, 1, 0))]
+    #[case(pt!(0.3, 1), vc!(0, -1, 0))]
+    fn directions(#[case] p: Point<2>, #[case] expected: Vector<3>) {
+        let camera = EquirectangularCamera::new(&EquirectangularCameraParameters {
+            eye: pt!(1, 2, 3),
+            look_at: pt!(1, 2, 2),
+            up: vc!(0, 1, 0),
+        });
+        let rays: Vec<Ray> = camera.enumerate_rays(&CameraSample::at(p)).collect();
+
+        assert_eq!(1, rays.len());
+        assert_eq!(approx(pt!(1, 2, 3)), rays[0].origin);
+        assert_eq!(approx(expected), rays[0].direction);
+    }
+}
@@ -0,0 +1,163 @@
+use crate::math::{vc, Angle, Matrix, Point, Ray, Vector};
+
+use super::camera::{create_transformation_matrix, Camera, CameraSample};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum FisheyeProjection {
+    // Distance from the image center is proportional to the angle with the optical axis
+    Equidistant,
+    // Preserves solid angle: distance from the image center is proportional to sin(theta / 2)
+    Equisolid,
+}
+
+pub struct FisheyeCamera {
+    transformation_matrix: Matrix<4, 4>,
+    aspect_ratio: f64,
+    field_of_view: Angle,
+    projection: FisheyeProjection,
+}
+
+pub struct FisheyeCameraParameters {
+    pub eye: Point<3>,
+    pub look_at: Point<3>,
+    pub up: Vector<3>,
+    pub aspect_ratio: f64,
+    // Full angle covered by the image circle, can exceed 180 degrees
+    pub field_of_view: Angle,
+    pub projection: FisheyeProjection,
+}
+
+impl FisheyeCamera {
+    pub fn new(parameters: &FisheyeCameraParameters) -> Self {
+        debug_assert!(0.0 < parameters.field_of_view.in_degrees() && parameters.field_of_view.in_degrees() <= 360.0);
+
+        FisheyeCamera {
+            transformation_matrix: create_transformation_matrix(&parameters.eye, &parameters.look_at, &parameters.up),
+            aspect_ratio: parameters.aspect_ratio,
+            field_of_view: parameters.field_of_view,
+            projection: parameters.projection,
+        }
+    }
+
+    // Angle with the optical axis for a point at the given (normalized) distance from the image center
+    fn theta(&self, radius: f64) -> f64 {
+        let max_theta = self.field_of_view.in_radians() / 2.0;
+
+        match self.projection {
+            FisheyeProjection::Equidistant => radius * max_theta,
+            FisheyeProjection::Equisolid => 2.0 * (radius * (max_theta / 2.0).sin()).asin(),
+        }
+    }
+
+    fn direction(&self, point: &Point<2>) -> Option<Vector<3>> {
+        // The image circle touches the top and bottom of the image
+        let x = (2.0 * point.x() - 1.0) * self.aspect_ratio;
+        let y = 1.0 - 2.0 * point.y();
+        let radius = x.hypot(y);
+
+        if radius > 1.0 {
+            None
+        } else {
+            let theta = self.theta(radius);
+            let phi = y.atan2(x);
+
+            Some(vc!(theta.sin() * phi.cos(), theta.sin() * phi.sin(), -theta.cos()))
+        }
+    }
+}
+
+impl Camera for FisheyeCamera {
+    fn enumerate_rays(&self, sample: &CameraSample) -> Box<dyn Iterator<Item = Ray> + '_> {
+        match self.direction(&sample.screen) {
+            None => Box::new(std::iter::empty()),
+            Some(direction) => {
+                let ray = Ray::new(Point::zero(), direction);
+
+                Box::new(std::iter::once(&self.transformation_matrix * &ray))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(test)]
+    use super::*;
+    use rstest::rstest;
+
+    #[cfg(test)]
+    use crate::math::{approx, pt};
+
+    fn create_camera(projection: FisheyeProjection, field_of_view: Angle) -> FisheyeCamera {
+        FisheyeCamera::new(&FisheyeCameraParameters {
+            eye: pt!(0, 0, 0),
+            look_at: pt!(0, 0, -1),
+            up: vc!(0, 1, 0),
+            aspect_ratio: 1.0,
+            field_of_view,
+            projection,
+        })
+    }
+
+    #[rstest]
+    fn center_looks_forward(#[values(FisheyeProjection::Equidistant, FisheyeProjection::Equisolid)] projection: FisheyeProjection) {
+        let camera = create_camera(projection, Angle::degrees(180.0));
+        let rays: Vec<Ray> = camera.enumerate_rays(&CameraSample::at(pt!(0.5, 0.5))).collect();
+
+        assert_eq!(1, rays.len());
+        assert_eq!(approx(vc!(0, 0, -1)), rays[0].direction);
+    }
+
+    #[rstest]
+    #[case(pt!(1, 0.5), vc!(1, 0, 0))]
+    #[case(pt!(0, 0.5), vc!(-1, 0, 0))]
+    #[case(pt!(0.5, 0), vc!(0, 1, 0))]
+    #[case(pt!(0.5, 1), vc!(0, -1, 0))]
+    fn edge_of_circle_at_half_field_of_view(
+        #[values(FisheyeProjection::Equidistant, FisheyeProjection::Equisolid)] projection: FisheyeProjection,
+        #[case] p: Point<2>,
+        #[case] expected: Vector<3>,
+    ) {
+        let camera = create_camera(projection, Angle::degrees(180.0));
+        let rays: Vec<Ray> = camera.enumerate_rays(&CameraSample::at(p)).collect();
+
+        assert_eq!(approx(expected), rays[0].direction.normalized());
+    }
+
+    #[rstest]
+    fn equidistant_is_linear_in_angle() {
+        let camera = create_camera(FisheyeProjection::Equidistant, Angle::degrees(180.0));
+        let rays: Vec<Ray> = camera.enumerate_rays(&CameraSample::at(pt!(0.75, 0.5))).collect();
+        let expected = vc!(1, 0, -1).normalized();
+
+        assert_eq!(approx(expected), rays[0].direction.normalized());
+    }
+
+    #[rstest]
+    fn equisolid_preserves_area() {
+        let camera = create_camera(FisheyeProjection::Equisolid, Angle::degrees(180.0));
+        let rays: Vec<Ray> = camera.enumerate_rays(&CameraSample::at(pt!(0.75, 0.5))).collect();
+        let cos_theta = -rays[0].direction.normalized().z();
+
+        // Half the image disk's area corresponds to half the hemisphere's solid angle
+        let disk_fraction = 0.5f64.powi(2);
+        let solid_angle_fraction = 1.0 - cos_theta;
+
+        assert!((disk_fraction - solid_angle_fraction).abs() < 0.000001);
+    }
+
+    #[rstest]
+    fn no_rays_outside_image_circle() {
+        let camera = create_camera(FisheyeProjection::Equidistant, Angle::degrees(180.0));
+
+        assert_eq!(0, camera.enumerate_rays(&CameraSample::at(pt!(0, 0))).count());
+    }
+
+    #[rstest]
+    fn wider_than_hemisphere() {
+        let camera = create_camera(FisheyeProjection::Equidistant, Angle::degrees(270.0));
+        let rays: Vec<Ray> = camera.enumerate_rays(&CameraSample::at(pt!(1, 0.5))).collect();
+
+        assert_eq!(approx(vc!(1, 0, 1).normalized()), rays[0].direction.normalized());
+    }
+}
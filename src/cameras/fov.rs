@@ -0,0 +1,48 @@
+use crate::math::Angle;
+
+#[derive(Copy, Clone, Debug)]
+pub enum FieldOfView {
+    Horizontal(Angle),
+    Vertical(Angle),
+}
+
+impl FieldOfView {
+    // Distance to a screen of height 1 and width aspect_ratio that yields this field of view
+    pub fn distance_to_screen(&self, aspect_ratio: f64) -> f64 {
+        match self {
+            FieldOfView::Horizontal(angle) => aspect_ratio / 2.0 / (*angle * 0.5).tan(),
+            FieldOfView::Vertical(angle) => 0.5 / (*angle * 0.5).tan(),
+        }
+    }
+}
+
+pub fn aspect_ratio(width: u32, height: u32) -> f64 {
+    debug_assert!(width > 0 && height > 0);
+
+    width as f64 / height as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    #[cfg(test)]
+    use super::*;
+
+    use crate::math::approx;
+
+    #[rstest]
+    #[case(FieldOfView::Vertical(Angle::degrees(90.0)), 1.0, 0.5)]
+    #[case(FieldOfView::Vertical(Angle::degrees(90.0)), 2.0, 0.5)]
+    #[case(FieldOfView::Horizontal(Angle::degrees(90.0)), 1.0, 0.5)]
+    #[case(FieldOfView::Horizontal(Angle::degrees(90.0)), 2.0, 1.0)]
+    #[case(FieldOfView::Vertical(Angle::degrees(60.0)), 1.0, 0.5 * 3.0f64.sqrt())]
+    fn distance_to_screen(#[case] field_of_view: FieldOfView, #[case] aspect_ratio: f64, #[case] expected: f64) {
+        assert_eq!(approx(expected), field_of_view.distance_to_screen(aspect_ratio));
+    }
+
+    #[rstest]
+    fn aspect_ratio_from_resolution() {
+        assert_eq!(approx(16.0 / 9.0), aspect_ratio(1920, 1080));
+    }
+}
@@ -1 +1,9 @@
+pub mod camera;
+pub mod cubemap;
+pub mod distortion;
+pub mod equirectangular;
+pub mod fisheye;
+pub mod fov;
+pub mod orthographic;
 pub mod perspective;
+pub mod stereo;
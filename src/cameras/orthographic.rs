@@ -0,0 +1,78 @@
+use crate::math::{pt, vc, Matrix, Point, Ray, Rectangle, Vector};
+
+use super::camera::{create_transformation_matrix, Camera, CameraSample};
+
+pub struct OrthographicCamera {
+    screen: Rectangle<3>,
+    transformation_matrix: Matrix<4, 4>,
+}
+
+pub struct OrthographicCameraParameters {
+    pub eye: Point<3>,
+    pub look_at: Point<3>,
+    pub up: Vector<3>,
+    pub view_height: f64,
+    pub aspect_ratio: f64,
+}
+
+fn create_canonical_screen(parameters: &OrthographicCameraParameters) -> Rectangle<3> {
+    let screen_height = parameters.view_height;
+    let screen_width = screen_height * parameters.aspect_ratio;
+    let origin = pt![-screen_width / 2.0, screen_height / 2.0, 0];
+    let x_axis = vc![screen_width, 0, 0];
+    let y_axis = vc![0, -screen_height, 0];
+
+    Rectangle::<3>::new(origin, x_axis, y_axis)
+}
+
+impl OrthographicCamera {
+    pub fn new(parameters: &OrthographicCameraParameters) -> Self {
+        debug_assert!(parameters.view_height > 0.0);
+
+        OrthographicCamera {
+            screen: create_canonical_screen(parameters),
+            transformation_matrix: create_transformation_matrix(&parameters.eye, &parameters.look_at, &parameters.up),
+        }
+    }
+}
+
+impl Camera for OrthographicCamera {
+    fn enumerate_rays(&self, sample: &CameraSample) -> Box<dyn Iterator<Item = Ray> + '_> {
+        let origin = self.screen.from_relative(&sample.screen);
+        let ray = Ray::new(origin, vc!(0, 0, -1));
+
+        Box::new(std::iter::once(&self.transformation_matrix * &ray))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(test)]
+    use super::*;
+    use rstest::rstest;
+
+    #[cfg(test)]
+    use crate::math::approx;
+
+    #[rstest]
+    #[case(pt!(0.5, 0.5), pt!(0, 0, 2))]
+    #[case(pt!(0, 0), pt!(-2, 1, 2))]
+    #[case(pt!(1, 0), pt!(2, 1, 2))]
+    #[case(pt!(0, 1), pt!(-2, -1, 2))]
+    #[case(pt!(1, 1), pt!(2, -1, 2))]
+    fn enumerate_rays(#[case] p: Point<2>, #[case] expected_origin: Point<3>) {
+        let parameters = OrthographicCameraParameters {
+            eye: pt!(0, 0, 2),
+            look_at: pt!(0, 0, 0),
+            up: vc!(0, 1, 0),
+            view_height: 2.0,
+            aspect_ratio: 2.0,
+        };
+        let camera = OrthographicCamera::new(&parameters);
+        let rays: Vec<Ray> = camera.enumerate_rays(&CameraSample::at(p)).collect();
+
+        assert_eq!(1, rays.len());
+        assert_eq!(approx(expected_origin), rays[0].origin);
+        assert_eq!(approx(vc!(0, 0, -1)), rays[0].direction);
+    }
+}
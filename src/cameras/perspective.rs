@@ -1,63 +1,66 @@
-use crate::math::{pt, vc, Matrix, Point, Ray, Rectangle, Vector};
+use crate::{
+    math::{pt, vc, Angle, Matrix, Point, Ray, Rectangle, Vector},
+    primitives::Primitive,
+    samplers::{UniformDiskSampler, UniformPolygonSampler, Warp},
+};
+
+use super::{
+    camera::{self, Camera, CameraSample},
+    distortion::LensDistortion,
+    fov::{self, FieldOfView},
+};
 
 pub struct PerspectiveCamera {
     screen: Rectangle<3>,
     transformation_matrix: Matrix<4, 4>,
+    lens: Box<dyn Warp<Point<2>>>,
+    aperture_radius: f64,
+    focal_distance: f64,
+    distance_to_screen: f64,
+    distortion: Option<LensDistortion>,
 }
 
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ApertureShape {
+    Circular,
+    // Regular polygon mimicking a diaphragm with the given number of blades
+    Polygonal { blades: u32, rotation: Angle },
+}
+
+#[derive(Clone)]
 pub struct PerspectiveCameraParameters {
     pub eye: Point<3>,
     pub look_at: Point<3>,
     pub up: Vector<3>,
     pub distance_to_screen: f64,
     pub aspect_ratio: f64,
+    // A radius of zero yields a pinhole camera
+    pub aperture_radius: f64,
+    // Distance along the view direction of the plane that is in perfect focus
+    pub focal_distance: f64,
+    pub aperture_shape: ApertureShape,
+    // Off-axis projection: moves the screen parallel to itself, expressed in units of screen height.
+    // Positive values move the framing right and up while keeping verticals parallel.
+    pub shift: Vector<2>,
+    pub distortion: Option<LensDistortion>,
 }
 
 pub struct Rays<'a> {
     parent: &'a PerspectiveCamera,
-    point: Point<2>,
+    sample: CameraSample,
     consumed: bool,
 }
 
-fn create_coordinate_system(
-    parameters: &PerspectiveCameraParameters,
-) -> (Point<3>, Vector<3>, Vector<3>, Vector<3>) {
-    let look_direction = (parameters.look_at - parameters.eye).normalized();
-    let right = look_direction.cross(&parameters.up).normalized();
-    let up = right.cross(&look_direction);
-
-    debug_assert!(look_direction.is_unit());
-    debug_assert!(right.is_unit());
-    debug_assert!(up.is_unit());
-    debug_assert!(look_direction.is_orthogonal_to(&right));
-
-    let origin = parameters.eye;
-    let z_axis = -look_direction;
-    let y_axis = up;
-    let x_axis = right;
-
-    debug_assert!(x_axis.is_unit());
-    debug_assert!(y_axis.is_unit());
-    debug_assert!(z_axis.is_unit());
-    debug_assert!(x_axis.is_orthogonal_to(&y_axis));
-    debug_assert!(x_axis.is_orthogonal_to(&z_axis));
-    debug_assert!(y_axis.is_orthogonal_to(&z_axis));
-
-    (origin, x_axis, y_axis, z_axis)
-}
-
 fn create_transformation_matrix(parameters: &PerspectiveCameraParameters) -> Matrix<4, 4> {
-    let (origin, x_axis, y_axis, z_axis) = create_coordinate_system(parameters);
-
-    Matrix::from_coordinate_system(&origin, &x_axis, &y_axis, &z_axis)
+    camera::create_transformation_matrix(&parameters.eye, &parameters.look_at, &parameters.up)
 }
 
 fn create_canonical_screen(parameters: &PerspectiveCameraParameters) -> Rectangle<3> {
     let screen_width = parameters.aspect_ratio;
     let screen_height = 1.0;
     let origin = pt![
-        -screen_width / 2.0,
-        screen_height / 2.0,
+        -screen_width / 2.0 + parameters.shift.x(),
+        screen_height / 2.0 + parameters.shift.y(),
         -parameters.distance_to_screen
     ];
     let x_axis = vc![screen_width, 0, 0];
@@ -66,23 +69,102 @@ fn create_canonical_screen(parameters: &PerspectiveCameraParameters) -> Rectangl
     Rectangle::<3>::new(origin, x_axis, y_axis)
 }
 
+fn create_lens(shape: &ApertureShape) -> Box<dyn Warp<Point<2>>> {
+    match shape {
+        ApertureShape::Circular => Box::new(UniformDiskSampler::new()),
+        ApertureShape::Polygonal { blades, rotation } => Box::new(UniformPolygonSampler::new(*blades, *rotation)),
+    }
+}
+
+impl PerspectiveCameraParameters {
+    pub fn new(eye: Point<3>, look_at: Point<3>, up: Vector<3>, field_of_view: FieldOfView, width: u32, height: u32) -> Self {
+        let aspect_ratio = fov::aspect_ratio(width, height);
+
+        PerspectiveCameraParameters {
+            eye,
+            look_at,
+            up,
+            distance_to_screen: field_of_view.distance_to_screen(aspect_ratio),
+            aspect_ratio,
+            aperture_radius: 0.0,
+            focal_distance: 1.0,
+            aperture_shape: ApertureShape::Circular,
+            shift: vc!(0, 0),
+            distortion: None,
+        }
+    }
+
+    // Focuses on whatever is visible through the center of the screen, if anything
+    pub fn autofocus(&mut self, root: &dyn Primitive) {
+        let ray = Ray::new(self.eye, (self.look_at - self.eye).normalized());
+
+        if let Some(hit) = root.find_first_positive_hit(&ray) {
+            self.focal_distance = hit.t;
+        }
+    }
+}
+
 impl PerspectiveCamera {
     pub fn new(parameters: &PerspectiveCameraParameters) -> Self {
+        debug_assert!(parameters.aperture_radius >= 0.0);
+        debug_assert!(parameters.aperture_radius == 0.0 || parameters.focal_distance > 0.0);
+
         let screen = create_canonical_screen(parameters);
         let transformation_matrix = create_transformation_matrix(parameters);
+        let lens = create_lens(&parameters.aperture_shape);
 
         PerspectiveCamera {
             screen,
             transformation_matrix,
+            lens,
+            aperture_radius: parameters.aperture_radius,
+            focal_distance: parameters.focal_distance,
+            distance_to_screen: parameters.distance_to_screen,
+            distortion: parameters.distortion,
+        }
+    }
+
+    // Point on the screen in camera space; distortion is centered on the optical axis
+    fn screen_point(&self, relative: &Point<2>) -> Point<3> {
+        let point = self.screen.from_relative(relative);
+
+        match &self.distortion {
+            None => point,
+            Some(distortion) => {
+                let d = self.distance_to_screen;
+                let undistorted = distortion.undistort(&pt!(point.x() / d, -point.y() / d));
+
+                pt!(undistorted.x() * d, -undistorted.y() * d, point.z())
+            }
+        }
+    }
+
+    // Camera space ray; at t = 1 it reaches the plane of the screen
+    fn create_ray(&self, sample: &CameraSample) -> Ray {
+        let to = self.screen_point(&sample.screen);
+
+        if self.aperture_radius == 0.0 {
+            let origin = pt!(0, 0, 0);
+
+            Ray::new(origin, to - origin)
+        } else {
+            let lens_point = self.lens.warp(&sample.lens);
+            let origin = pt!(lens_point.x() * self.aperture_radius, lens_point.y() * self.aperture_radius, 0);
+            let focus_scale = self.focal_distance / self.distance_to_screen;
+            let in_focus = pt!(to.x() * focus_scale, to.y() * focus_scale, to.z() * focus_scale);
+
+            Ray::new(origin, (in_focus - origin) * focus_scale.recip())
         }
     }
+}
 
-    pub fn enumerate_rays(&self, point: Point<2>) -> Rays {
-        Rays {
+impl Camera for PerspectiveCamera {
+    fn enumerate_rays(&self, sample: &CameraSample) -> Box<dyn Iterator<Item = Ray> + '_> {
+        Box::new(Rays {
             parent: self,
-            point,
+            sample: *sample,
             consumed: false,
-        }
+        })
     }
 }
 
@@ -95,10 +177,7 @@ impl<'a> Iterator for Rays<'a> {
         } else {
             self.consumed = true;
 
-            let origin = pt!(0, 0, 0);
-            let to = self.parent.screen.from_relative(&self.point);
-            let direction = to - origin;
-            let ray = Ray::new(origin, direction);
+            let ray = self.parent.create_ray(&self.sample);
             let transformed_ray = &self.parent.transformation_matrix * &ray;
 
             Some(transformed_ray)
@@ -113,7 +192,7 @@ mod tests {
     use rstest::rstest;
 
     #[cfg(test)]
-    use crate::math::{approx, pt, vc};
+    use crate::{math::{approx, pt, vc}, primitives::PlaneXY};
 
     #[rstest]
     fn coordinate_system_1() {
@@ -131,10 +210,15 @@ mod tests {
             eye,
             look_at,
             up,
+            aperture_radius: 0.0,
+            focal_distance: 1.0,
+            aperture_shape: ApertureShape::Circular,
+            shift: vc!(0, 0),
+            distortion: None,
         };
 
         let (actual_origin, actual_x_axis, actual_y_axis, actual_z_axis) =
-            create_coordinate_system(&parameters);
+            camera::create_coordinate_system(&parameters.eye, &parameters.look_at, &parameters.up);
 
         assert_eq!(approx(expected_origin), actual_origin);
         assert_eq!(approx(expected_x_axis), actual_x_axis);
@@ -158,10 +242,15 @@ mod tests {
             eye,
             look_at,
             up,
+            aperture_radius: 0.0,
+            focal_distance: 1.0,
+            aperture_shape: ApertureShape::Circular,
+            shift: vc!(0, 0),
+            distortion: None,
         };
 
         let (actual_origin, actual_x_axis, actual_y_axis, actual_z_axis) =
-            create_coordinate_system(&parameters);
+            camera::create_coordinate_system(&parameters.eye, &parameters.look_at, &parameters.up);
 
         assert_eq!(approx(expected_origin), actual_origin);
         assert_eq!(approx(expected_x_axis), actual_x_axis);
@@ -185,10 +274,15 @@ mod tests {
             eye,
             look_at,
             up,
+            aperture_radius: 0.0,
+            focal_distance: 1.0,
+            aperture_shape: ApertureShape::Circular,
+            shift: vc!(0, 0),
+            distortion: None,
         };
 
         let (actual_origin, actual_x_axis, actual_y_axis, actual_z_axis) =
-            create_coordinate_system(&parameters);
+            camera::create_coordinate_system(&parameters.eye, &parameters.look_at, &parameters.up);
 
         assert_eq!(approx(expected_origin), actual_origin);
         assert_eq!(approx(expected_x_axis), actual_x_axis);
@@ -209,9 +303,14 @@ mod tests {
             eye: pt!(0, 0, -1),
             look_at: pt!(0, 0, 0),
             up: vc!(0, 1, 0),
+            aperture_radius: 0.0,
+            focal_distance: 1.0,
+            aperture_shape: ApertureShape::Circular,
+            shift: vc!(0, 0),
+            distortion: None,
         };
         let camera = PerspectiveCamera::new(&parameters);
-        let rays: Vec<Ray> = camera.enumerate_rays(p).collect();
+        let rays: Vec<Ray> = camera.enumerate_rays(&CameraSample::at(p)).collect();
 
         assert_eq!(1, rays.len());
 
@@ -233,9 +332,14 @@ mod tests {
             eye: pt!(0, 0, 2),
             look_at: pt!(0, 0, 0),
             up: vc!(0, 1, 0),
+            aperture_radius: 0.0,
+            focal_distance: 1.0,
+            aperture_shape: ApertureShape::Circular,
+            shift: vc!(0, 0),
+            distortion: None,
         };
         let camera = PerspectiveCamera::new(&parameters);
-        let rays: Vec<Ray> = camera.enumerate_rays(p).collect();
+        let rays: Vec<Ray> = camera.enumerate_rays(&CameraSample::at(p)).collect();
 
         assert_eq!(1, rays.len());
 
@@ -243,4 +347,140 @@ mod tests {
         assert_eq!(approx(pt!(0, 0, 2)), ray.origin);
         assert_eq!(approx(expected), ray.at(1.0));
     }
+
+    fn create_thin_lens_parameters(aperture_shape: ApertureShape) -> PerspectiveCameraParameters {
+        PerspectiveCameraParameters {
+            aspect_ratio: 1.0,
+            distance_to_screen: 1.0,
+            eye: pt!(0, 0, 5),
+            look_at: pt!(0, 0, 0),
+            up: vc!(0, 1, 0),
+            aperture_radius: 0.5,
+            focal_distance: 5.0,
+            aperture_shape,
+            shift: vc!(0, 0),
+            distortion: None,
+        }
+    }
+
+    #[rstest]
+    #[case(pt!(0.5, 0.5), pt!(0, 0, 0))]
+    #[case(pt!(0, 0), pt!(-2.5, 2.5, 0))]
+    #[case(pt!(1, 1), pt!(2.5, -2.5, 0))]
+    fn lens_rays_converge_on_focal_plane(#[case] p: Point<2>, #[case] expected: Point<3>) {
+        let camera = PerspectiveCamera::new(&create_thin_lens_parameters(ApertureShape::Circular));
+
+        for lens in [pt!(0.5, 0.5), pt!(0, 0), pt!(0.9, 0.2), pt!(0.3, 0.7)] {
+            let rays: Vec<Ray> = camera.enumerate_rays(&CameraSample::new(p, lens, 0.0)).collect();
+
+            assert_eq!(1, rays.len());
+            assert_eq!(approx(expected), rays[0].at(5.0));
+        }
+    }
+
+    #[rstest]
+    fn lens_rays_start_on_aperture(
+        #[values(ApertureShape::Circular, ApertureShape::Polygonal { blades: 6, rotation: Angle::degrees(0.0) })] aperture_shape: ApertureShape,
+    ) {
+        let camera = PerspectiveCamera::new(&create_thin_lens_parameters(aperture_shape));
+        let lens_samples = [pt!(0, 0), pt!(1, 1), pt!(0.9, 0.2), pt!(0.3, 0.7)];
+        let origins: Vec<Point<3>> = lens_samples
+            .iter()
+            .map(|lens| camera.enumerate_rays(&CameraSample::new(pt!(0.2, 0.4), *lens, 0.0)).next().unwrap().origin)
+            .collect();
+
+        for origin in &origins {
+            let offset = *origin - pt!(0, 0, 5);
+
+            assert_eq!(approx(0.0), offset.z());
+            assert!(offset.norm() <= 0.5 + 0.000001);
+        }
+
+        assert!(origins.iter().any(|origin| (*origin - pt!(0, 0, 5)).norm() > 0.1));
+    }
+
+    #[rstest]
+    fn centered_lens_sample_matches_pinhole() {
+        let thin_lens = PerspectiveCamera::new(&create_thin_lens_parameters(ApertureShape::Circular));
+        let pinhole = PerspectiveCamera::new(&PerspectiveCameraParameters {
+            aperture_radius: 0.0,
+            ..create_thin_lens_parameters(ApertureShape::Circular)
+        });
+        let sample = CameraSample::at(pt!(0.3, 0.8));
+        let expected = pinhole.enumerate_rays(&sample).next().unwrap();
+        let actual = thin_lens.enumerate_rays(&sample).next().unwrap();
+
+        assert_eq!(approx(expected.origin), actual.origin);
+        assert_eq!(approx(expected.direction), actual.direction);
+    }
+
+    #[rstest]
+    fn autofocus_on_center() {
+        let mut parameters = create_thin_lens_parameters(ApertureShape::Circular);
+        parameters.eye = pt!(0, 0, 7);
+        parameters.autofocus(&PlaneXY::new());
+
+        assert_eq!(approx(7.0), parameters.focal_distance);
+    }
+
+    #[rstest]
+    fn autofocus_without_hit_keeps_focal_distance() {
+        let mut parameters = create_thin_lens_parameters(ApertureShape::Circular);
+        parameters.look_at = pt!(0, 0, 10);
+        parameters.autofocus(&PlaneXY::new());
+
+        assert_eq!(approx(5.0), parameters.focal_distance);
+    }
+
+    #[rstest]
+    #[case(FieldOfView::Vertical(Angle::degrees(90.0)), pt!(0.5, 0), vc!(0, 1, -1))]
+    #[case(FieldOfView::Horizontal(Angle::degrees(90.0)), pt!(1, 0.5), vc!(1, 0, -1))]
+    #[case(FieldOfView::Horizontal(Angle::degrees(90.0)), pt!(0, 0.5), vc!(-1, 0, -1))]
+    fn ray_directions_from_field_of_view(#[case] field_of_view: FieldOfView, #[case] p: Point<2>, #[case] expected: Vector<3>) {
+        let parameters = PerspectiveCameraParameters::new(pt!(0, 0, 0), pt!(0, 0, -1), vc!(0, 1, 0), field_of_view, 200, 100);
+        let camera = PerspectiveCamera::new(&parameters);
+        let ray = camera.enumerate_rays(&CameraSample::at(p)).next().unwrap();
+
+        assert_eq!(approx(2.0), parameters.aspect_ratio);
+        assert_eq!(approx(expected.normalized()), ray.direction.normalized());
+    }
+
+    #[rstest]
+    fn shift_keeps_view_direction() {
+        let mut parameters = PerspectiveCameraParameters::new(pt!(0, 0, 0), pt!(0, 0, -1), vc!(0, 1, 0), FieldOfView::Vertical(Angle::degrees(90.0)), 100, 100);
+        parameters.shift = vc!(0.25, 0.5);
+        let camera = PerspectiveCamera::new(&parameters);
+        let center = camera.enumerate_rays(&CameraSample::at(pt!(0.5, 0.5))).next().unwrap();
+        let bottom = camera.enumerate_rays(&CameraSample::at(pt!(0.5, 1))).next().unwrap();
+
+        assert_eq!(approx(pt!(0.25, 0.5, -0.5)), center.at(1.0));
+        assert_eq!(approx(pt!(0.25, 0, -0.5)), bottom.at(1.0));
+    }
+
+    #[rstest]
+    fn distortion_leaves_center_unchanged() {
+        let mut parameters = PerspectiveCameraParameters::new(pt!(0, 0, 0), pt!(0, 0, -1), vc!(0, 1, 0), FieldOfView::Vertical(Angle::degrees(60.0)), 100, 100);
+        parameters.distortion = Some(LensDistortion::radial(0.2, 0.0, 0.0));
+        let camera = PerspectiveCamera::new(&parameters);
+        let ray = camera.enumerate_rays(&CameraSample::at(pt!(0.5, 0.5))).next().unwrap();
+
+        assert_eq!(approx(vc!(0, 0, -1)), ray.direction.normalized());
+    }
+
+    #[rstest]
+    fn distorted_pixel_shows_undistorted_direction() {
+        let distortion = LensDistortion { k1: 0.2, k2: 0.0, k3: 0.0, p1: 0.01, p2: 0.0 };
+        let mut parameters = PerspectiveCameraParameters::new(pt!(0, 0, 0), pt!(0, 0, -1), vc!(0, 1, 0), FieldOfView::Vertical(Angle::degrees(90.0)), 100, 100);
+        parameters.distortion = Some(distortion);
+        let camera = PerspectiveCamera::new(&parameters);
+        let ray = camera.enumerate_rays(&CameraSample::at(pt!(0.8, 0.3))).next().unwrap();
+
+        // Project the ray back onto normalized image coordinates and apply the lens model
+        let direction = ray.direction;
+        let normalized = pt!(direction.x() / -direction.z(), -direction.y() / -direction.z());
+        let distorted = distortion.distort(&normalized);
+
+        // Pixel (0.8, 0.3) lies at (0.6, -0.4) in normalized coordinates for a 90 degree field of view
+        assert_eq!(approx(pt!(0.6, -0.4)), distorted);
+    }
 }
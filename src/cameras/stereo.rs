@@ -0,0 +1,136 @@
+use crate::math::vc;
+
+use super::perspective::{PerspectiveCamera, PerspectiveCameraParameters};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Eye {
+    Left,
+    Right,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ConvergenceMethod {
+    // Both eyes are rotated towards the convergence point; simple but introduces vertical parallax in the corners
+    ToeIn,
+    // Eyes look in parallel and their screens are shifted so that they overlap at the convergence distance
+    OffAxis,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct StereoRig {
+    pub interocular_distance: f64,
+    // Distance at which objects appear to lie on the display's surface
+    pub convergence_distance: f64,
+    pub method: ConvergenceMethod,
+}
+
+impl Eye {
+    fn side(&self) -> f64 {
+        match self {
+            Eye::Left => -1.0,
+            Eye::Right => 1.0,
+        }
+    }
+}
+
+impl StereoRig {
+    pub fn eye_parameters(&self, parameters: &PerspectiveCameraParameters, eye: Eye) -> PerspectiveCameraParameters {
+        debug_assert!(self.interocular_distance >= 0.0);
+        debug_assert!(self.convergence_distance > 0.0);
+
+        let forward = (parameters.look_at - parameters.eye).normalized();
+        let right = forward.cross(&parameters.up).normalized();
+        let half_distance = self.interocular_distance / 2.0;
+        let offset = right * (eye.side() * half_distance);
+        let mut result = parameters.clone();
+
+        result.eye = parameters.eye + offset;
+
+        match self.method {
+            ConvergenceMethod::ToeIn => {
+                result.look_at = parameters.eye + forward * self.convergence_distance;
+            }
+            ConvergenceMethod::OffAxis => {
+                result.look_at = parameters.look_at + offset;
+                result.shift = result.shift + vc!(-eye.side() * half_distance * parameters.distance_to_screen / self.convergence_distance, 0);
+            }
+        }
+
+        result
+    }
+
+    pub fn camera(&self, parameters: &PerspectiveCameraParameters, eye: Eye) -> PerspectiveCamera {
+        PerspectiveCamera::new(&self.eye_parameters(parameters, eye))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    #[cfg(test)]
+    use super::*;
+
+    use crate::{
+        cameras::{camera::{Camera, CameraSample}, fov::FieldOfView},
+        math::{approx, pt, vc, Angle, Point, Ray},
+    };
+
+    fn create_parameters() -> PerspectiveCameraParameters {
+        PerspectiveCameraParameters::new(pt!(0, 0, 10), pt!(0, 0, 0), vc!(0, 1, 0), FieldOfView::Vertical(Angle::degrees(60.0)), 100, 100)
+    }
+
+    fn create_rig(method: ConvergenceMethod) -> StereoRig {
+        StereoRig {
+            interocular_distance: 0.5,
+            convergence_distance: 4.0,
+            method,
+        }
+    }
+
+    fn center_ray(rig: &StereoRig, eye: Eye) -> Ray {
+        rig.camera(&create_parameters(), eye).enumerate_rays(&CameraSample::at(pt!(0.5, 0.5))).next().unwrap()
+    }
+
+    // Point along the ray at the given depth along the negative z-axis
+    fn at_depth(ray: &Ray, z: f64) -> Point<3> {
+        ray.at((z - ray.origin.z()) / ray.direction.z())
+    }
+
+    #[rstest]
+    fn eyes_are_separated(#[values(ConvergenceMethod::ToeIn, ConvergenceMethod::OffAxis)] method: ConvergenceMethod) {
+        let rig = create_rig(method);
+
+        assert_eq!(approx(pt!(-0.25, 0, 10)), rig.eye_parameters(&create_parameters(), Eye::Left).eye);
+        assert_eq!(approx(pt!(0.25, 0, 10)), rig.eye_parameters(&create_parameters(), Eye::Right).eye);
+    }
+
+    #[rstest]
+    fn center_rays_meet_at_convergence_distance(#[values(ConvergenceMethod::ToeIn, ConvergenceMethod::OffAxis)] method: ConvergenceMethod) {
+        let rig = create_rig(method);
+        let left = center_ray(&rig, Eye::Left);
+        let right = center_ray(&rig, Eye::Right);
+
+        assert_eq!(approx(pt!(0, 0, 6)), at_depth(&left, 6.0));
+        assert_eq!(approx(pt!(0, 0, 6)), at_depth(&right, 6.0));
+    }
+
+    #[rstest]
+    fn off_axis_keeps_view_directions_parallel() {
+        let rig = create_rig(ConvergenceMethod::OffAxis);
+        let left = rig.eye_parameters(&create_parameters(), Eye::Left);
+        let right = rig.eye_parameters(&create_parameters(), Eye::Right);
+
+        assert_eq!(approx((left.look_at - left.eye).normalized()), (right.look_at - right.eye).normalized());
+        assert_eq!(approx(vc!(0, 0, -1)), (left.look_at - left.eye).normalized());
+    }
+
+    #[rstest]
+    fn toe_in_rotates_eyes() {
+        let rig = create_rig(ConvergenceMethod::ToeIn);
+        let left = rig.eye_parameters(&create_parameters(), Eye::Left);
+
+        assert_eq!(approx(pt!(0, 0, 6)), left.look_at);
+        assert_eq!(approx(vc!(0, 0)), left.shift);
+    }
+}
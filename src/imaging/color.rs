@@ -58,6 +58,11 @@ impl Color {
     pub fn intensity(&self) -> f64 {
         self.rgb.iter().sum::<f64>() / 3.0
     }
+
+    // Rec. 709 weights
+    pub fn luminance(&self) -> f64 {
+        0.2126 * self.r() + 0.7152 * self.g() + 0.0722 * self.b()
+    }
 }
 
 impl std::ops::Add for Color {
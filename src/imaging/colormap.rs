@@ -0,0 +1,34 @@
+use super::color::Color;
+
+// Black through blue, cyan and yellow to red for t in [0, 1]
+pub fn heat_color(t: f64) -> Color {
+    let stops = [
+        Color::black(),
+        Color::blue(),
+        Color::new(0.0, 1.0, 1.0),
+        Color::new(1.0, 1.0, 0.0),
+        Color::red(),
+    ];
+    let scaled = t.clamp(0.0, 1.0) * (stops.len() - 1) as f64;
+    let index = (scaled.floor() as usize).min(stops.len() - 2);
+    let fraction = scaled - index as f64;
+
+    stops[index] * (1.0 - fraction) + stops[index + 1] * fraction
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    #[cfg(test)]
+    use super::*;
+
+    #[rstest]
+    #[case(0.0, Color::black())]
+    #[case(0.25, Color::blue())]
+    #[case(1.0, Color::red())]
+    #[case(2.0, Color::red())]
+    fn heat_colors(#[case] t: f64, #[case] expected: Color) {
+        assert_eq!(expected, heat_color(t));
+    }
+}
@@ -0,0 +1,313 @@
+use super::{
+    color::Color,
+    colormap::heat_color,
+    image::Image,
+    ops::Kernel,
+};
+use crate::math::Position;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ImageComparison {
+    pub mse: f64,
+    pub psnr: f64,
+    pub ssim: f64,
+    pub flip: f64,
+}
+
+// Per pixel error, stored row by row
+#[derive(Debug, Clone, PartialEq)]
+pub struct ErrorMap {
+    width: u32,
+    height: u32,
+    values: Vec<f64>,
+}
+
+impl ErrorMap {
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn get(&self, x: u32, y: u32) -> f64 {
+        self.values[(y * self.width + x) as usize]
+    }
+
+    pub fn mean(&self) -> f64 {
+        self.values.iter().sum::<f64>() / self.values.len() as f64
+    }
+
+    pub fn max(&self) -> f64 {
+        self.values.iter().copied().fold(0.0, f64::max)
+    }
+
+    // Errors are expected to lie in [0, 1]
+    pub fn to_false_color(&self) -> Image {
+        Image::from_fn(self.width, self.height, |x, y| heat_color(self.get(x, y)))
+    }
+}
+
+pub fn compare(reference: &Image, test: &Image) -> ImageComparison {
+    ImageComparison {
+        mse: mse(reference, test),
+        psnr: psnr(reference, test),
+        ssim: ssim(reference, test),
+        flip: flip(reference, test).mean(),
+    }
+}
+
+pub fn difference_image(reference: &Image, test: &Image) -> Image {
+    flip(reference, test).to_false_color()
+}
+
+// Averaged over all pixels and channels
+pub fn mse(reference: &Image, test: &Image) -> f64 {
+    check_sizes(reference, test);
+
+    let difference = reference.subtract(test);
+    let total: f64 = pixels(&difference)
+        .map(|c| c.r() * c.r() + c.g() * c.g() + c.b() * c.b())
+        .sum();
+
+    total / (3 * reference.width() * reference.height()) as f64
+}
+
+// Assumes a peak value of 1; identical images have infinite PSNR
+pub fn psnr(reference: &Image, test: &Image) -> f64 {
+    let mse = mse(reference, test);
+
+    if mse == 0.0 {
+        f64::INFINITY
+    } else {
+        -10.0 * mse.log10()
+    }
+}
+
+// Mean structural similarity of the luminance, using an 11x11 Gaussian window
+pub fn ssim(reference: &Image, test: &Image) -> f64 {
+    const SIGMA: f64 = 1.5;
+    const C1: f64 = 0.01 * 0.01;
+    const C2: f64 = 0.03 * 0.03;
+
+    check_sizes(reference, test);
+
+    let x = reference.map(|c| gray(c.luminance().clamp(0.0, 1.0)));
+    let y = test.map(|c| gray(c.luminance().clamp(0.0, 1.0)));
+    let mean_x = x.gaussian_blur(SIGMA);
+    let mean_y = y.gaussian_blur(SIGMA);
+    let mean_xx = x.multiply(&x).gaussian_blur(SIGMA);
+    let mean_yy = y.multiply(&y).gaussian_blur(SIGMA);
+    let mean_xy = x.multiply(&y).gaussian_blur(SIGMA);
+
+    let width = reference.width();
+    let height = reference.height();
+    let total: f64 = (0..height)
+        .flat_map(|j| (0..width).map(move |i| Position::<2>::cartesian(i as i32, j as i32)))
+        .map(|position| {
+            let mx = mean_x.get(position).r();
+            let my = mean_y.get(position).r();
+            let vx = mean_xx.get(position).r() - mx * mx;
+            let vy = mean_yy.get(position).r() - my * my;
+            let cxy = mean_xy.get(position).r() - mx * my;
+
+            ((2.0 * mx * my + C1) * (2.0 * cxy + C2)) / ((mx * mx + my * my + C1) * (vx + vy + C2))
+        })
+        .sum();
+
+    total / (width * height) as f64
+}
+
+// Simplified FLIP: a blurred HyAB color difference in L*a*b*, amplified where edges or points differ
+pub fn flip(reference: &Image, test: &Image) -> ErrorMap {
+    const COLOR_EXPONENT: f64 = 0.7;
+    const FEATURE_EXPONENT: f64 = 0.5;
+
+    check_sizes(reference, test);
+
+    let reference = reference.map(clamp);
+    let test = test.map(clamp);
+    let maximum_difference = hyab(&lab(&Color::green()), &lab(&Color::blue()));
+
+    let blurred_reference = reference.gaussian_blur(0.5);
+    let blurred_test = test.gaussian_blur(0.5);
+    let reference_features = Features::detect(&reference);
+    let test_features = Features::detect(&test);
+
+    let width = reference.width();
+    let height = reference.height();
+    let values = (0..height)
+        .flat_map(|y| (0..width).map(move |x| Position::<2>::cartesian(x as i32, y as i32)))
+        .map(|position| {
+            let color_difference = hyab(&lab(blurred_reference.get(position)), &lab(blurred_test.get(position)));
+            let color_error = (color_difference / maximum_difference).min(1.0).powf(COLOR_EXPONENT);
+            let feature_difference = reference_features.difference(&test_features, position);
+            let feature_error = (feature_difference / 2f64.sqrt()).min(1.0).powf(FEATURE_EXPONENT);
+
+            color_error.powf(1.0 - feature_error)
+        })
+        .collect();
+
+    ErrorMap { width, height, values }
+}
+
+// Edge and point strength of the luminance
+struct Features {
+    edges: Image,
+    points: Image,
+}
+
+impl Features {
+    fn detect(image: &Image) -> Self {
+        let sobel_x = Kernel::new(3, 3, vec![-1.0, 0.0, 1.0, -2.0, 0.0, 2.0, -1.0, 0.0, 1.0]).scaled(0.25);
+        let sobel_y = Kernel::new(3, 3, vec![-1.0, -2.0, -1.0, 0.0, 0.0, 0.0, 1.0, 2.0, 1.0]).scaled(0.25);
+        let laplacian = Kernel::new(3, 3, vec![0.0, 1.0, 0.0, 1.0, -4.0, 1.0, 0.0, 1.0, 0.0]).scaled(0.25);
+
+        let lightness = image.map(|c| gray(lab(c)[0] / 100.0));
+        let gx = lightness.convolve(&sobel_x);
+        let gy = lightness.convolve(&sobel_y);
+        let edges = gx.zip_with(&gy, |a, b| gray(a.r().hypot(b.r())));
+        let points = lightness.convolve(&laplacian).map(|c| gray(c.r().abs()));
+
+        Features { edges, points }
+    }
+
+    fn difference(&self, other: &Features, position: Position<2>) -> f64 {
+        let edge = (self.edges.get(position).r() - other.edges.get(position).r()).abs();
+        let point = (self.points.get(position).r() - other.points.get(position).r()).abs();
+
+        edge.max(point)
+    }
+}
+
+fn check_sizes(reference: &Image, test: &Image) {
+    assert_eq!((reference.width(), reference.height()), (test.width(), test.height()), "Image sizes differ");
+}
+
+fn pixels(image: &Image) -> impl Iterator<Item = &Color> {
+    (0..image.height())
+        .flat_map(move |y| (0..image.width()).map(move |x| Position::<2>::cartesian(x as i32, y as i32)))
+        .map(|position| image.get(position))
+}
+
+fn gray(value: f64) -> Color {
+    Color::new(value, value, value)
+}
+
+fn clamp(color: &Color) -> Color {
+    Color::new(color.r().clamp(0.0, 1.0), color.g().clamp(0.0, 1.0), color.b().clamp(0.0, 1.0))
+}
+
+// Linear sRGB to CIE L*a*b* with a D65 white point
+fn lab(color: &Color) -> [f64; 3] {
+    const WHITE: [f64; 3] = [0.95047, 1.0, 1.08883];
+
+    let (r, g, b) = (color.r(), color.g(), color.b());
+    let xyz = [
+        0.4124 * r + 0.3576 * g + 0.1805 * b,
+        0.2126 * r + 0.7152 * g + 0.0722 * b,
+        0.0193 * r + 0.1192 * g + 0.9505 * b,
+    ];
+    let f = |t: f64| {
+        if t > 216.0 / 24389.0 {
+            t.cbrt()
+        } else {
+            (24389.0 / 27.0 * t + 16.0) / 116.0
+        }
+    };
+    let [fx, fy, fz] = [f(xyz[0] / WHITE[0]), f(xyz[1] / WHITE[1]), f(xyz[2] / WHITE[2])];
+
+    [116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz)]
+}
+
+// Lightness and chroma differences are combined as absolute and Euclidean distance respectively
+fn hyab(a: &[f64; 3], b: &[f64; 3]) -> f64 {
+    (a[0] - b[0]).abs() + (a[1] - b[1]).hypot(a[2] - b[2])
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    #[cfg(test)]
+    use super::*;
+
+    #[cfg(test)]
+    use crate::math::approx;
+
+    #[cfg(test)]
+    fn checkerboard(size: u32) -> Image {
+        Image::from_fn(size, size, |x, y| Color::white() * ((x / 2 + y / 2) % 2) as f64)
+    }
+
+    #[cfg(test)]
+    fn constant(size: u32, value: f64) -> Image {
+        Image::from_fn(size, size, |_, _| Color::white() * value)
+    }
+
+    #[rstest]
+    fn identical_images() {
+        let image = checkerboard(16);
+        let comparison = compare(&image, &image);
+
+        assert_eq!(0.0, comparison.mse);
+        assert!(comparison.psnr.is_infinite());
+        assert_eq!(approx(1.0), comparison.ssim);
+        assert_eq!(0.0, comparison.flip);
+    }
+
+    #[rstest]
+    #[case(0.5, 0.25, 0.0625, 12.0412)]
+    #[case(0.0, 0.1, 0.01, 20.0)]
+    fn mse_and_psnr(#[case] a: f64, #[case] b: f64, #[case] expected_mse: f64, #[case] expected_psnr: f64) {
+        let a = constant(4, a);
+        let b = constant(4, b);
+
+        assert_eq!(approx(expected_mse), mse(&a, &b));
+        assert!((psnr(&a, &b) - expected_psnr).abs() < 1e-4);
+    }
+
+    #[rstest]
+    fn ssim_penalizes_structure_more_than_brightness() {
+        let reference = checkerboard(16);
+        let brighter = reference.map(|c| c * 0.9 + Color::white() * 0.1);
+        let blurred = reference.gaussian_blur(1.0);
+
+        assert!(ssim(&reference, &brighter) > ssim(&reference, &blurred));
+        assert!(ssim(&reference, &blurred) < 0.9);
+    }
+
+    #[rstest]
+    fn flip_grows_with_difference() {
+        let reference = constant(8, 0.2);
+        let errors: Vec<f64> = [0.2, 0.25, 0.4, 0.8]
+            .iter()
+            .map(|&value| flip(&reference, &constant(8, value)).mean())
+            .collect();
+
+        assert_eq!(0.0, errors[0]);
+        assert!(errors.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(errors[3] <= 1.0);
+    }
+
+    #[rstest]
+    fn flip_is_localized() {
+        let reference = constant(9, 0.5);
+        let mut test = reference.clone();
+        *test.get_mut(Position::<2>::cartesian(4, 4)) = Color::red();
+        let errors = flip(&reference, &test);
+
+        assert!(errors.get(4, 4) > 0.5);
+        assert_eq!(errors.get(4, 4), errors.max());
+        assert_eq!(0.0, errors.get(0, 0));
+    }
+
+    #[rstest]
+    fn difference_image_uses_false_color() {
+        let reference = constant(4, 0.0);
+        let image = difference_image(&reference, &reference);
+
+        assert_eq!(Color::black(), *image.get(Position::<2>::cartesian(3, 3)));
+    }
+}
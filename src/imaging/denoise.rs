@@ -0,0 +1,359 @@
+use super::{color::Color, image::Image};
+use crate::math::Position;
+
+// Auxiliary buffers rendered alongside the color image; normals are stored in the color channels
+pub struct Guides<'a> {
+    pub albedo: &'a Image,
+    pub normal: &'a Image,
+    pub depth: &'a Image,
+}
+
+// Larger values tolerate larger differences before a neighbor stops contributing
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct EdgeStopping {
+    pub color: f64,
+    pub normal: f64,
+    pub depth: f64,
+    pub albedo: f64,
+}
+
+impl Default for EdgeStopping {
+    fn default() -> Self {
+        EdgeStopping {
+            color: 4.0,
+            normal: 128.0,
+            depth: 0.1,
+            albedo: 0.1,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Denoiser {
+    CrossBilateral { radius: u32, sigma_spatial: f64, edges: EdgeStopping },
+    ATrous { iterations: u32, edges: EdgeStopping },
+    NonLocalMeans { search_radius: u32, patch_radius: u32, strength: f64, edges: EdgeStopping },
+}
+
+impl Denoiser {
+    // Filters lighting only: colors are divided by the albedo first and multiplied by it again afterwards,
+    // so that texture detail survives. Alpha is left untouched.
+    pub fn apply(&self, image: &Image, guides: &Guides) -> Image {
+        let buffers = Buffers::new(image, guides);
+        let lighting = match *self {
+            Denoiser::CrossBilateral { radius, sigma_spatial, edges } => buffers.cross_bilateral(radius, sigma_spatial, &edges),
+            Denoiser::ATrous { iterations, edges } => buffers.a_trous(iterations, &edges),
+            Denoiser::NonLocalMeans { search_radius, patch_radius, strength, edges } => {
+                buffers.non_local_means(search_radius, patch_radius, strength, &edges)
+            }
+        };
+
+        let result = Image::from_fn(image.width(), image.height(), |x, y| {
+            let index = buffers.index(x as i32, y as i32);
+            remodulate(&lighting[index], &buffers.albedo[index])
+        });
+
+        if image.has_alpha() {
+            let alpha = (0..image.height())
+                .flat_map(|y| (0..image.width()).map(move |x| Position::<2>::cartesian(x as i32, y as i32)))
+                .map(|position| image.alpha(position))
+                .collect();
+
+            result.with_alpha(alpha)
+        } else {
+            result
+        }
+    }
+}
+
+struct Buffers {
+    width: i32,
+    height: i32,
+    lighting: Vec<Color>,
+    albedo: Vec<Color>,
+    normal: Vec<Color>,
+    depth: Vec<f64>,
+}
+
+impl Buffers {
+    fn new(image: &Image, guides: &Guides) -> Self {
+        let positions: Vec<Position<2>> = (0..image.height())
+            .flat_map(|y| (0..image.width()).map(move |x| Position::<2>::cartesian(x as i32, y as i32)))
+            .collect();
+        let albedo: Vec<Color> = positions.iter().map(|&p| *guides.albedo.get(p)).collect();
+        let lighting = positions
+            .iter()
+            .zip(albedo.iter())
+            .map(|(&p, albedo)| demodulate(image.get(p), albedo))
+            .collect();
+
+        Buffers {
+            width: image.width() as i32,
+            height: image.height() as i32,
+            lighting,
+            albedo,
+            normal: positions.iter().map(|&p| *guides.normal.get(p)).collect(),
+            depth: positions.iter().map(|&p| guides.depth.get(p).r()).collect(),
+        }
+    }
+
+    fn index(&self, x: i32, y: i32) -> usize {
+        (y * self.width + x) as usize
+    }
+
+    fn contains(&self, x: i32, y: i32) -> bool {
+        0 <= x && x < self.width && 0 <= y && y < self.height
+    }
+
+    // Similarity of two pixels judged by the auxiliary buffers only
+    fn guide_weight(&self, p: usize, q: usize, edges: &EdgeStopping) -> f64 {
+        let normal_p = &self.normal[p];
+        let normal_q = &self.normal[q];
+        let cos_angle = normal_p.r() * normal_q.r() + normal_p.g() * normal_q.g() + normal_p.b() * normal_q.b();
+        let normal_weight = if normal_p.is_not_black() || normal_q.is_not_black() {
+            cos_angle.max(0.0).powf(edges.normal)
+        } else {
+            1.0
+        };
+
+        let depth_p = self.depth[p];
+        let depth_weight = (-(depth_p - self.depth[q]).abs() / (edges.depth * depth_p.max(1e-3))).exp();
+        let albedo_weight = (-squared_distance(&self.albedo[p], &self.albedo[q]) / (2.0 * edges.albedo * edges.albedo)).exp();
+
+        normal_weight * depth_weight * albedo_weight
+    }
+
+    fn color_weight(lighting: &[Color], p: usize, q: usize, edges: &EdgeStopping) -> f64 {
+        (-(lighting[p].luminance() - lighting[q].luminance()).abs() / edges.color).exp()
+    }
+
+    fn cross_bilateral(&self, radius: u32, sigma_spatial: f64, edges: &EdgeStopping) -> Vec<Color> {
+        let radius = radius as i32;
+
+        self.filter_each(|x, y, p| {
+            let mut total = Color::black();
+            let mut total_weight = 0.0;
+
+            for dy in -radius..=radius {
+                for dx in -radius..=radius {
+                    if self.contains(x + dx, y + dy) {
+                        let q = self.index(x + dx, y + dy);
+                        let spatial = (-((dx * dx + dy * dy) as f64) / (2.0 * sigma_spatial * sigma_spatial)).exp();
+                        let weight = spatial * self.guide_weight(p, q, edges) * Self::color_weight(&self.lighting, p, q, edges);
+
+                        total += self.lighting[q] * weight;
+                        total_weight += weight;
+                    }
+                }
+            }
+
+            total / total_weight
+        })
+    }
+
+    // Edge-avoiding wavelet filter with a B3 spline kernel whose taps spread out by a factor two every pass
+    fn a_trous(&self, iterations: u32, edges: &EdgeStopping) -> Vec<Color> {
+        const KERNEL: [f64; 5] = [1.0 / 16.0, 1.0 / 4.0, 3.0 / 8.0, 1.0 / 4.0, 1.0 / 16.0];
+
+        let mut current = self.lighting.clone();
+
+        for iteration in 0..iterations {
+            let step = 1 << iteration;
+            let input = current;
+
+            current = self.filter_each(|x, y, p| {
+                let mut total = Color::black();
+                let mut total_weight = 0.0;
+
+                for (j, ky) in KERNEL.iter().enumerate() {
+                    for (i, kx) in KERNEL.iter().enumerate() {
+                        let qx = x + (i as i32 - 2) * step;
+                        let qy = y + (j as i32 - 2) * step;
+
+                        if self.contains(qx, qy) {
+                            let q = self.index(qx, qy);
+                            let weight = kx * ky * self.guide_weight(p, q, edges) * Self::color_weight(&input, p, q, edges);
+
+                            total += input[q] * weight;
+                            total_weight += weight;
+                        }
+                    }
+                }
+
+                total / total_weight
+            });
+        }
+
+        current
+    }
+
+    // Neighbors are weighted by how much the patches around them resemble the patch around the pixel
+    fn non_local_means(&self, search_radius: u32, patch_radius: u32, strength: f64, edges: &EdgeStopping) -> Vec<Color> {
+        let search_radius = search_radius as i32;
+        let patch_radius = patch_radius as i32;
+
+        self.filter_each(|x, y, p| {
+            let mut total = Color::black();
+            let mut total_weight = 0.0;
+
+            for dy in -search_radius..=search_radius {
+                for dx in -search_radius..=search_radius {
+                    if self.contains(x + dx, y + dy) {
+                        let q = self.index(x + dx, y + dy);
+                        let distance = self.patch_distance(x, y, x + dx, y + dy, patch_radius);
+                        let weight = (-distance / (strength * strength)).exp() * self.guide_weight(p, q, edges);
+
+                        total += self.lighting[q] * weight;
+                        total_weight += weight;
+                    }
+                }
+            }
+
+            total / total_weight
+        })
+    }
+
+    // Mean squared difference over the parts of both patches that lie inside the image
+    fn patch_distance(&self, px: i32, py: i32, qx: i32, qy: i32, patch_radius: i32) -> f64 {
+        let mut total = 0.0;
+        let mut count = 0;
+
+        for dy in -patch_radius..=patch_radius {
+            for dx in -patch_radius..=patch_radius {
+                if self.contains(px + dx, py + dy) && self.contains(qx + dx, qy + dy) {
+                    let a = &self.lighting[self.index(px + dx, py + dy)];
+                    let b = &self.lighting[self.index(qx + dx, qy + dy)];
+
+                    total += squared_distance(a, b);
+                    count += 1;
+                }
+            }
+        }
+
+        total / count as f64
+    }
+
+    fn filter_each(&self, f: impl Fn(i32, i32, usize) -> Color) -> Vec<Color> {
+        (0..self.height)
+            .flat_map(|y| (0..self.width).map(move |x| (x, y)))
+            .map(|(x, y)| f(x, y, self.index(x, y)))
+            .collect()
+    }
+}
+
+// Channels without albedo (e.g. background) are passed through unchanged
+fn demodulate(color: &Color, albedo: &Color) -> Color {
+    let divide = |c: f64, a: f64| if a > 1e-3 { c / a } else { c };
+
+    Color::new(divide(color.r(), albedo.r()), divide(color.g(), albedo.g()), divide(color.b(), albedo.b()))
+}
+
+fn remodulate(lighting: &Color, albedo: &Color) -> Color {
+    let multiply = |c: f64, a: f64| if a > 1e-3 { c * a } else { c };
+
+    Color::new(multiply(lighting.r(), albedo.r()), multiply(lighting.g(), albedo.g()), multiply(lighting.b(), albedo.b()))
+}
+
+fn squared_distance(a: &Color, b: &Color) -> f64 {
+    let (dr, dg, db) = (a.r() - b.r(), a.g() - b.g(), a.b() - b.b());
+
+    dr * dr + dg * dg + db * db
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    #[cfg(test)]
+    use super::*;
+
+    #[cfg(test)]
+    use crate::{imaging::compare::mse, math::approx, util::Random};
+
+    #[cfg(test)]
+    const SIZE: u32 = 24;
+
+    #[cfg(test)]
+    fn denoisers() -> Vec<Denoiser> {
+        let edges = EdgeStopping::default();
+
+        vec![
+            Denoiser::CrossBilateral { radius: 3, sigma_spatial: 2.0, edges },
+            Denoiser::ATrous { iterations: 3, edges },
+            Denoiser::NonLocalMeans { search_radius: 3, patch_radius: 1, strength: 0.3, edges },
+        ]
+    }
+
+    // Left half faces the camera, right half faces sideways and is twice as bright
+    #[cfg(test)]
+    fn scene() -> (Image, Image, Image, Image) {
+        let is_left = |x: u32| x < SIZE / 2;
+        let clean = Image::from_fn(SIZE, SIZE, |x, _| if is_left(x) { Color::white() * 0.25 } else { Color::white() * 0.5 });
+        let albedo = Image::from_fn(SIZE, SIZE, |_, _| Color::white());
+        let normal = Image::from_fn(SIZE, SIZE, |x, _| if is_left(x) { Color::blue() } else { Color::red() });
+        let depth = Image::from_fn(SIZE, SIZE, |_, _| Color::white() * 5.0);
+
+        (clean, albedo, normal, depth)
+    }
+
+    #[cfg(test)]
+    fn add_noise(image: &Image) -> Image {
+        let mut random = Random::new(3);
+        let noise: Vec<f64> = (0..SIZE * SIZE).map(|_| (random.next_f64() - 0.5) * 0.2).collect();
+
+        Image::from_fn(SIZE, SIZE, |x, y| *image.get(Position::<2>::cartesian(x as i32, y as i32)) + Color::white() * noise[(y * SIZE + x) as usize])
+    }
+
+    #[rstest]
+    fn constant_images_are_unchanged(#[values(0, 1, 2)] index: usize) {
+        let (_, albedo, normal, depth) = scene();
+        let guides = Guides { albedo: &albedo, normal: &normal, depth: &depth };
+        let image = Image::from_fn(SIZE, SIZE, |_, _| Color::new(0.1, 0.2, 0.3));
+        let denoised = denoisers()[index].apply(&image, &guides);
+
+        assert_eq!(approx(0.0), mse(&image, &denoised));
+    }
+
+    #[rstest]
+    fn noise_is_reduced(#[values(0, 1, 2)] index: usize) {
+        let (clean, albedo, normal, depth) = scene();
+        let guides = Guides { albedo: &albedo, normal: &normal, depth: &depth };
+        let noisy = add_noise(&clean);
+        let denoised = denoisers()[index].apply(&noisy, &guides);
+
+        assert!(mse(&clean, &denoised) < mse(&clean, &noisy) / 4.0);
+    }
+
+    #[rstest]
+    fn edges_are_preserved(#[values(0, 1, 2)] index: usize) {
+        let (clean, albedo, normal, depth) = scene();
+        let guides = Guides { albedo: &albedo, normal: &normal, depth: &depth };
+        let denoised = denoisers()[index].apply(&clean, &guides);
+        let left = denoised.get(Position::<2>::cartesian(SIZE as i32 / 2 - 1, 5)).g();
+        let right = denoised.get(Position::<2>::cartesian(SIZE as i32 / 2, 5)).g();
+
+        assert!((left - 0.25).abs() < 0.01);
+        assert!((right - 0.5).abs() < 0.01);
+    }
+
+    #[rstest]
+    fn texture_is_preserved(#[values(0, 1, 2)] index: usize) {
+        let (_, _, normal, depth) = scene();
+        let albedo = Image::from_fn(SIZE, SIZE, |x, y| if (x + y) % 2 == 0 { Color::white() } else { Color::white() * 0.5 });
+        let guides = Guides { albedo: &albedo, normal: &normal, depth: &depth };
+        let image = albedo.scale(0.8);
+        let denoised = denoisers()[index].apply(&image, &guides);
+
+        assert_eq!(approx(0.0), mse(&image, &denoised));
+    }
+
+    #[rstest]
+    fn alpha_is_kept() {
+        let (_, albedo, normal, depth) = scene();
+        let guides = Guides { albedo: &albedo, normal: &normal, depth: &depth };
+        let image = Image::new(SIZE, SIZE).with_alpha(vec![0.5; (SIZE * SIZE) as usize]);
+        let denoised = denoisers()[1].apply(&image, &guides);
+
+        assert_eq!(0.5, denoised.alpha(Position::<2>::cartesian(3, 3)));
+    }
+}
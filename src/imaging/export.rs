@@ -2,36 +2,53 @@ use std::{io::{BufWriter, Write}, fs::File};
 
 use png::{Encoder, Writer};
 
-use super::image::Image;
+use super::{image::Image, tonemap::OutputTransform};
 
 
+// Receives the frames of an animation one by one
+pub trait FrameWriter {
+    fn write_frame(&mut self, image: Image);
+}
+
 pub struct PNGWriter<T: Write> {
     writer: Writer<T>,
+    output_transform: OutputTransform,
+    alpha: bool,
 }
 
 pub struct PNGWriterOptions {
     pub width: u32,
     pub height: u32,
     pub frame_count: u32,
+    pub frames_per_second: u32,
+    pub output_transform: OutputTransform,
+    pub alpha: bool,
 }
 
 impl<T: Write> PNGWriter<T> {
     pub fn new(writer: T, options: PNGWriterOptions) -> Self {
         let encoder = {
         let mut encoder = png::Encoder::new(writer, options.width, options.height);
-            encoder.set_color(png::ColorType::Rgb);
+            encoder.set_color(if options.alpha { png::ColorType::Rgba } else { png::ColorType::Rgb });
             encoder.set_depth(png::BitDepth::Eight);
             encoder.set_animated(options.frame_count, 0).expect("Failed to set PNG to animated");
+            encoder.set_frame_delay(1, options.frames_per_second as u16).expect("Failed to set frame delay");
             encoder
         };
 
         let writer = encoder.write_header().expect("Failed to write header");
 
-        PNGWriter { writer }
+        PNGWriter { writer, output_transform: options.output_transform, alpha: options.alpha }
     }
+}
 
-    pub fn write_frame(&mut self, image: Image) {
-        let data = image.convert_to_raw_rgb();
+impl<T: Write> FrameWriter for PNGWriter<T> {
+    fn write_frame(&mut self, image: Image) {
+        let data = if self.alpha {
+            image.convert_to_display_rgba(&self.output_transform)
+        } else {
+            image.convert_to_display_rgb(&self.output_transform)
+        };
         self.writer.write_image_data(&data).expect("Failed to write image data");
     }
 }
@@ -44,3 +61,41 @@ impl<'a> PNGWriter<BufWriter<File>> {
         Self::new(writer, options)
     }
 }
+
+// Writes every frame to its own file, numbered from 1: base_0001.png, base_0002.png, ...
+pub struct PNGSequenceWriter {
+    base: String,
+    output_transform: OutputTransform,
+    alpha: bool,
+    next_index: u32,
+}
+
+impl PNGSequenceWriter {
+    pub fn new(base: &str, output_transform: OutputTransform, alpha: bool) -> Self {
+        PNGSequenceWriter {
+            base: String::from(base),
+            output_transform,
+            alpha,
+            next_index: 1,
+        }
+    }
+
+    pub fn frame_path(&self, index: u32) -> String {
+        format!("{}_{:04}.png", self.base, index)
+    }
+}
+
+impl FrameWriter for PNGSequenceWriter {
+    fn write_frame(&mut self, image: Image) {
+        let file = File::create(self.frame_path(self.next_index)).expect("Failed to create file");
+        let writer = BufWriter::new(file);
+
+        if self.alpha {
+            image.write_png_with_alpha(writer, &self.output_transform).expect("Failed to write frame");
+        } else {
+            image.write_png(writer, &self.output_transform).expect("Failed to write frame");
+        }
+
+        self.next_index += 1;
+    }
+}
@@ -0,0 +1,325 @@
+use std::io::Write;
+
+use flate2::{write::ZlibEncoder, Compression};
+
+use super::{
+    color::Color,
+    image::{Image, WriteError},
+};
+use crate::math::Position;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ExrCompression {
+    None,
+    Zip,
+}
+
+impl ExrCompression {
+    fn code(&self) -> u8 {
+        match self {
+            ExrCompression::None => 0,
+            ExrCompression::Zip => 3,
+        }
+    }
+
+    fn lines_per_block(&self) -> u32 {
+        match self {
+            ExrCompression::None => 1,
+            ExrCompression::Zip => 16,
+        }
+    }
+}
+
+// Values are stored row by row, top to bottom
+pub struct ExrChannel {
+    pub name: String,
+    pub values: Vec<f32>,
+}
+
+pub fn write_exr(image: &Image, writer: impl Write, compression: ExrCompression) -> Result<(), WriteError> {
+    let width = image.width();
+    let height = image.height();
+    let colors: Vec<_> = (0..height)
+        .flat_map(|y| (0..width).map(move |x| Position::<2>::cartesian(x as i32, y as i32)))
+        .map(|position| image.get(position))
+        .collect();
+    let channel = |name: &str, component: fn(&Color) -> f64| ExrChannel {
+        name: String::from(name),
+        values: colors.iter().map(|color| component(color) as f32).collect(),
+    };
+    let channels = vec![channel("R", |c| c.r()), channel("G", |c| c.g()), channel("B", |c| c.b())];
+
+    write_exr_channels(width, height, channels, writer, compression)
+}
+
+// Single part scanline file with 32-bit float channels
+pub fn write_exr_channels(width: u32, height: u32, mut channels: Vec<ExrChannel>, mut writer: impl Write, compression: ExrCompression) -> Result<(), WriteError> {
+    debug_assert!(channels.iter().all(|channel| channel.values.len() == (width * height) as usize));
+
+    // The format requires channels sorted by name; pixel data follows the same order
+    channels.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let header = create_header(width, height, &channels, compression);
+    let blocks: Vec<Vec<u8>> = (0..height)
+        .step_by(compression.lines_per_block() as usize)
+        .map(|y| create_block(width, height, y, &channels, compression))
+        .collect();
+
+    let mut offset = (header.len() + 8 * blocks.len()) as u64;
+    let mut offsets = Vec::with_capacity(blocks.len() * 8);
+    for block in blocks.iter() {
+        offsets.extend_from_slice(&offset.to_le_bytes());
+        offset += block.len() as u64;
+    }
+
+    writer.write_all(&header).map_err(WriteError::IOError)?;
+    writer.write_all(&offsets).map_err(WriteError::IOError)?;
+    for block in blocks.iter() {
+        writer.write_all(block).map_err(WriteError::IOError)?;
+    }
+
+    Ok(())
+}
+
+fn create_header(width: u32, height: u32, channels: &[ExrChannel], compression: ExrCompression) -> Vec<u8> {
+    const FLOAT_PIXEL_TYPE: i32 = 2;
+
+    let mut header = vec![0x76, 0x2f, 0x31, 0x01, 2, 0, 0, 0];
+
+    let mut channel_list = Vec::new();
+    for channel in channels {
+        channel_list.extend_from_slice(channel.name.as_bytes());
+        channel_list.push(0);
+        channel_list.extend_from_slice(&FLOAT_PIXEL_TYPE.to_le_bytes());
+        channel_list.extend_from_slice(&[0, 0, 0, 0]);
+        channel_list.extend_from_slice(&1i32.to_le_bytes());
+        channel_list.extend_from_slice(&1i32.to_le_bytes());
+    }
+    channel_list.push(0);
+
+    let window: Vec<u8> = [0, 0, width as i32 - 1, height as i32 - 1]
+        .iter()
+        .flat_map(|v| v.to_le_bytes())
+        .collect();
+
+    add_attribute(&mut header, "channels", "chlist", &channel_list);
+    add_attribute(&mut header, "compression", "compression", &[compression.code()]);
+    add_attribute(&mut header, "dataWindow", "box2i", &window);
+    add_attribute(&mut header, "displayWindow", "box2i", &window);
+    add_attribute(&mut header, "lineOrder", "lineOrder", &[0]);
+    add_attribute(&mut header, "pixelAspectRatio", "float", &1f32.to_le_bytes());
+    add_attribute(&mut header, "screenWindowCenter", "v2f", &[0; 8]);
+    add_attribute(&mut header, "screenWindowWidth", "float", &1f32.to_le_bytes());
+    header.push(0);
+
+    header
+}
+
+fn add_attribute(header: &mut Vec<u8>, name: &str, kind: &str, value: &[u8]) {
+    header.extend_from_slice(name.as_bytes());
+    header.push(0);
+    header.extend_from_slice(kind.as_bytes());
+    header.push(0);
+    header.extend_from_slice(&(value.len() as i32).to_le_bytes());
+    header.extend_from_slice(value);
+}
+
+fn create_block(width: u32, height: u32, first_line: u32, channels: &[ExrChannel], compression: ExrCompression) -> Vec<u8> {
+    let last_line = (first_line + compression.lines_per_block()).min(height);
+    let mut data = Vec::new();
+
+    for y in first_line..last_line {
+        for channel in channels {
+            let start = (y * width) as usize;
+            for value in &channel.values[start..start + width as usize] {
+                data.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+    }
+
+    let data = match compression {
+        ExrCompression::None => data,
+        ExrCompression::Zip => {
+            let compressed = zip_compress(&data);
+
+            // Readers treat a block whose size equals the uncompressed size as stored
+            if compressed.len() < data.len() {
+                compressed
+            } else {
+                data
+            }
+        }
+    };
+
+    let mut block = Vec::with_capacity(data.len() + 8);
+    block.extend_from_slice(&(first_line as i32).to_le_bytes());
+    block.extend_from_slice(&(data.len() as i32).to_le_bytes());
+    block.extend_from_slice(&data);
+
+    block
+}
+
+// Bytes are split into even and odd halves and delta encoded before deflating
+fn zip_compress(data: &[u8]) -> Vec<u8> {
+    let mut reordered: Vec<u8> = data.iter().step_by(2).copied().collect();
+    reordered.extend(data.iter().skip(1).step_by(2));
+
+    let mut previous = reordered.first().copied().unwrap_or(0);
+    for value in reordered.iter_mut().skip(1) {
+        let current = *value;
+        *value = current.wrapping_sub(previous).wrapping_add(128);
+        previous = current;
+    }
+
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&reordered).expect("Writing to memory cannot fail");
+    encoder.finish().expect("Writing to memory cannot fail")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use flate2::read::ZlibDecoder;
+    use rstest::rstest;
+
+    #[cfg(test)]
+    use super::*;
+
+    #[cfg(test)]
+    fn zip_decompress(data: &[u8]) -> Vec<u8> {
+        let mut reordered = Vec::new();
+        ZlibDecoder::new(data).read_to_end(&mut reordered).unwrap();
+
+        for i in 1..reordered.len() {
+            reordered[i] = reordered[i - 1].wrapping_add(reordered[i]).wrapping_sub(128);
+        }
+
+        let half = reordered.len().div_ceil(2);
+        (0..reordered.len())
+            .map(|i| if i % 2 == 0 { reordered[i / 2] } else { reordered[half + i / 2] })
+            .collect()
+    }
+
+    // Returns all attributes together with the position right after the header
+    #[cfg(test)]
+    fn read_header(file: &[u8]) -> (Vec<(String, &[u8])>, usize) {
+        let mut attributes = Vec::new();
+        let mut i = 8;
+
+        while file[i] != 0 {
+            let name_end = i + file[i..].iter().position(|&b| b == 0).unwrap();
+            let kind_end = name_end + 1 + file[name_end + 1..].iter().position(|&b| b == 0).unwrap();
+            let size = i32::from_le_bytes(file[kind_end + 1..kind_end + 5].try_into().unwrap()) as usize;
+            let name = String::from_utf8(file[i..name_end].to_vec()).unwrap();
+
+            attributes.push((name, &file[kind_end + 5..kind_end + 5 + size]));
+            i = kind_end + 5 + size;
+        }
+
+        (attributes, i + 1)
+    }
+
+    #[cfg(test)]
+    fn find_attribute<'a>(file: &'a [u8], name: &str) -> &'a [u8] {
+        let (attributes, _) = read_header(file);
+
+        attributes.into_iter().find(|(n, _)| n == name).unwrap().1
+    }
+
+    // Returns the first line and decompressed pixel data of every block
+    #[cfg(test)]
+    fn read_blocks(file: &[u8], width: usize, height: usize, compression: ExrCompression) -> Vec<(usize, Vec<u8>)> {
+        let (_, header_end) = read_header(file);
+        let lines_per_block = compression.lines_per_block() as usize;
+        let block_count = height.div_ceil(lines_per_block);
+
+        (0..block_count)
+            .map(|i| {
+                let entry = header_end + 8 * i;
+                let offset = u64::from_le_bytes(file[entry..entry + 8].try_into().unwrap()) as usize;
+                let y = i32::from_le_bytes(file[offset..offset + 4].try_into().unwrap()) as usize;
+                let size = i32::from_le_bytes(file[offset + 4..offset + 8].try_into().unwrap()) as usize;
+                let data = &file[offset + 8..offset + 8 + size];
+                let raw_size = lines_per_block.min(height - y) * width * 3 * 4;
+
+                if size < raw_size {
+                    (y, zip_decompress(data))
+                } else {
+                    (y, data.to_vec())
+                }
+            })
+            .collect()
+    }
+
+    #[cfg(test)]
+    fn create_image() -> Image {
+        let mut image = Image::new(40, 20);
+        for y in 0..20 {
+            for x in 0..40 {
+                *image.get_mut(Position::<2>::cartesian(x, y)) = Color::new(x as f64 * 10.0, y as f64 / 4.0, 0.5);
+            }
+        }
+
+        image
+    }
+
+    #[rstest]
+    fn zip_round_trip() {
+        let data: Vec<u8> = (0..1001).map(|i| ((i * 7) % 13) as u8).collect();
+
+        assert_eq!(data, zip_decompress(&zip_compress(&data)));
+    }
+
+    #[rstest]
+    fn header_attributes(#[values(ExrCompression::None, ExrCompression::Zip)] compression: ExrCompression) {
+        let mut buffer = Vec::new();
+        write_exr(&create_image(), &mut buffer, compression).unwrap();
+
+        assert_eq!(&[0x76, 0x2f, 0x31, 0x01, 2, 0, 0, 0], &buffer[..8]);
+        assert_eq!(&[compression.code()], find_attribute(&buffer, "compression"));
+
+        let channels = find_attribute(&buffer, "channels");
+        assert_eq!(3 * 18 + 1, channels.len());
+        assert_eq!(b'B', channels[0]);
+        assert_eq!(b'G', channels[18]);
+        assert_eq!(b'R', channels[36]);
+
+        let window: Vec<i32> = find_attribute(&buffer, "dataWindow")
+            .chunks(4)
+            .map(|bytes| i32::from_le_bytes(bytes.try_into().unwrap()))
+            .collect();
+        assert_eq!(vec![0, 0, 39, 19], window);
+    }
+
+    #[rstest]
+    #[case(ExrCompression::None, 20)]
+    #[case(ExrCompression::Zip, 2)]
+    fn pixel_data(#[case] compression: ExrCompression, #[case] block_count: usize) {
+        let mut buffer = Vec::new();
+        write_exr(&create_image(), &mut buffer, compression).unwrap();
+
+        let blocks = read_blocks(&buffer, 40, 20, compression);
+        let lines_per_block = compression.lines_per_block() as usize;
+
+        assert_eq!(block_count, blocks.len());
+
+        for (index, (first_line, data)) in blocks.iter().enumerate() {
+            assert_eq!(index * lines_per_block, *first_line);
+
+            let floats: Vec<f32> = data.chunks(4).map(|bytes| f32::from_le_bytes(bytes.try_into().unwrap())).collect();
+            let line_count = lines_per_block.min(20 - first_line);
+            assert_eq!(line_count * 3 * 40, floats.len());
+
+            for (line, values) in floats.chunks(3 * 40).enumerate() {
+                let y = *first_line as f32 + line as f32;
+                let (b, rest) = values.split_at(40);
+                let (g, r) = rest.split_at(40);
+
+                assert!(b.iter().all(|&v| v == 0.5));
+                assert!(g.iter().all(|&v| v == y / 4.0));
+                assert_eq!(390.0, r[39]);
+            }
+        }
+    }
+}
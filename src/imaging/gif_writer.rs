@@ -0,0 +1,146 @@
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+};
+
+use gif::{Encoder, Frame, Repeat};
+
+use super::{
+    export::FrameWriter,
+    image::Image,
+    quantize::{quantize, Dithering, Quantizer},
+    tonemap::OutputTransform,
+};
+
+pub struct GIFWriterOptions {
+    pub width: u32,
+    pub height: u32,
+    pub frames_per_second: u32,
+    pub output_transform: OutputTransform,
+    pub quantizer: Quantizer,
+    pub dithering: Dithering,
+}
+
+// Every frame gets its own 256 color palette; alpha is ignored
+pub struct GIFWriter<T: Write> {
+    encoder: Encoder<T>,
+    options: GIFWriterOptions,
+    frame_index: u32,
+}
+
+impl<T: Write> GIFWriter<T> {
+    pub fn new(writer: T, options: GIFWriterOptions) -> Self {
+        let mut encoder = Encoder::new(writer, options.width as u16, options.height as u16, &[]).expect("Failed to write GIF header");
+        encoder.set_repeat(Repeat::Infinite).expect("Failed to set GIF to looping");
+
+        GIFWriter {
+            encoder,
+            options,
+            frame_index: 0,
+        }
+    }
+}
+
+impl GIFWriter<BufWriter<File>> {
+    pub fn to_file(path: &str, options: GIFWriterOptions) -> Self {
+        let file = File::create(path).expect("Failed to create file");
+
+        Self::new(BufWriter::new(file), options)
+    }
+}
+
+impl<T: Write> FrameWriter for GIFWriter<T> {
+    fn write_frame(&mut self, image: Image) {
+        let width = image.width();
+        let height = image.height();
+        let rgb = image.convert_to_display_rgb(&self.options.output_transform);
+        let palette = self.options.quantizer.palette(&rgb, 256);
+        let indices = quantize(&rgb, width, height, &palette, self.options.dithering);
+
+        let frame = Frame {
+            width: width as u16,
+            height: height as u16,
+            delay: frame_delay(self.frame_index, self.options.frames_per_second),
+            palette: Some(palette.to_bytes()),
+            buffer: indices.into(),
+            ..Frame::default()
+        };
+
+        self.encoder.write_frame(&frame).expect("Failed to write frame");
+        self.frame_index += 1;
+    }
+}
+
+// GIF delays are whole centiseconds; rounding the frame's end time rather than its length keeps the total in sync
+fn frame_delay(index: u32, frames_per_second: u32) -> u16 {
+    let end_time = |index: u64| (100.0 * index as f64 / frames_per_second as f64).round() as u64;
+
+    (end_time(index as u64 + 1) - end_time(index as u64)) as u16
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    #[cfg(test)]
+    use super::*;
+
+    #[cfg(test)]
+    use crate::imaging::color::Color;
+
+    #[rstest]
+    #[case(1, vec![100, 100, 100])]
+    #[case(25, vec![4, 4, 4])]
+    #[case(30, vec![3, 4, 3, 3, 4, 3])]
+    #[case(60, vec![2, 1, 2, 2, 1, 2])]
+    fn frame_delays(#[case] frames_per_second: u32, #[case] expected: Vec<u16>) {
+        let delays: Vec<u16> = (0..expected.len() as u32).map(|i| frame_delay(i, frames_per_second)).collect();
+
+        assert_eq!(expected, delays);
+    }
+
+    #[rstest]
+    #[case(1, 656)]
+    #[case(1, 100_000)]
+    #[case(30, 1_000_000)]
+    #[case(30, u32::MAX - 30)]
+    fn late_frame_delays(#[case] frames_per_second: u32, #[case] index: u32) {
+        let total: u32 = (index..index + 30).map(|i| frame_delay(i, frames_per_second) as u32).sum();
+
+        assert_eq!(3000 / frames_per_second, total);
+    }
+
+    #[rstest]
+    fn frames_are_decodable() {
+        let options = GIFWriterOptions {
+            width: 8,
+            height: 4,
+            frames_per_second: 10,
+            output_transform: OutputTransform::linear(),
+            quantizer: Quantizer::MedianCut,
+            dithering: Dithering::FloydSteinberg,
+        };
+        let mut buffer = Vec::new();
+
+        {
+            let mut writer = GIFWriter::new(&mut buffer, options);
+            for color in [Color::red(), Color::blue()] {
+                writer.write_frame(Image::from_fn(8, 4, |x, _| if x < 4 { color } else { Color::white() }));
+            }
+        }
+
+        let mut decode_options = gif::DecodeOptions::new();
+        decode_options.set_color_output(gif::ColorOutput::RGBA);
+        let mut decoder = decode_options.read_info(buffer.as_slice()).unwrap();
+        let mut frames = Vec::new();
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            frames.push((frame.delay, frame.buffer.to_vec()));
+        }
+
+        assert_eq!(2, frames.len());
+        assert!(frames.iter().all(|(delay, _)| *delay == 10));
+        assert_eq!(&[255, 0, 0, 255], &frames[0].1[..4]);
+        assert_eq!(&[0, 0, 255, 255], &frames[1].1[..4]);
+        assert_eq!(&[255, 255, 255, 255], &frames[1].1[4 * 7..4 * 8]);
+    }
+}
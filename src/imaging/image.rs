@@ -1,11 +1,20 @@
 use crate::imaging::color::Color;
+use crate::imaging::exr::{write_exr, ExrCompression};
+use crate::imaging::import::read_png;
+use crate::imaging::pfm::{read_pfm, write_pfm};
+use crate::imaging::ppm::{read_ppm, write_ppm};
+use crate::imaging::radiance::write_hdr;
+use crate::imaging::tonemap::OutputTransform;
 use crate::math::Position;
 use std::fs::File;
-use std::io::{BufWriter, Write};
+use std::io::{BufReader, BufWriter, Read, Write};
 use std::path::Path;
 
+// When alpha is present, colors are premultiplied by it
+#[derive(Debug, Clone, PartialEq)]
 pub struct Image {
     pixels: Vec<Color>,
+    alpha: Option<Vec<f64>>,
     width: u32,
     height: u32,
 }
@@ -14,6 +23,39 @@ pub struct Image {
 pub enum WriteError {
     IOError(std::io::Error),
     PNGError(png::EncodingError),
+    UnsupportedFormat(String),
+}
+
+#[derive(Debug)]
+pub enum ReadError {
+    IOError(std::io::Error),
+    PNGError(png::DecodingError),
+    FormatError(String),
+    UnsupportedFormat(String),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ImageFormat {
+    Png,
+    Ppm,
+    Pfm,
+    Hdr,
+    Exr(ExrCompression),
+}
+
+impl ImageFormat {
+    pub fn from_path(path: &Path) -> Option<ImageFormat> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+
+        match extension.as_str() {
+            "png" => Some(ImageFormat::Png),
+            "ppm" => Some(ImageFormat::Ppm),
+            "pfm" => Some(ImageFormat::Pfm),
+            "hdr" => Some(ImageFormat::Hdr),
+            "exr" => Some(ImageFormat::Exr(ExrCompression::Zip)),
+            _ => None,
+        }
+    }
 }
 
 impl Image {
@@ -24,11 +66,71 @@ impl Image {
 
         Image {
             pixels,
+            alpha: None,
+            width,
+            height,
+        }
+    }
+
+    pub fn from_fn(width: u32, height: u32, f: impl Fn(u32, u32) -> Color) -> Self {
+        let pixels = (0..height).flat_map(|y| (0..width).map(move |x| (x, y))).map(|(x, y)| f(x, y)).collect();
+
+        Image {
+            pixels,
+            alpha: None,
             width,
             height,
         }
     }
 
+    // Alpha values are given row by row
+    pub fn with_alpha(self, alpha: Vec<f64>) -> Self {
+        assert_eq!(self.pixels.len(), alpha.len(), "Alpha size does not match image");
+
+        Image { alpha: Some(alpha), ..self }
+    }
+
+    pub fn without_alpha(self) -> Self {
+        Image { alpha: None, ..self }
+    }
+
+    pub fn has_alpha(&self) -> bool {
+        self.alpha.is_some()
+    }
+
+    // Images without alpha are fully opaque
+    pub fn alpha(&self, position: Position<2>) -> f64 {
+        match &self.alpha {
+            None => 1.0,
+            Some(alpha) => alpha[self.index_of(position)],
+        }
+    }
+
+    pub fn read_from_file(path: &Path) -> std::result::Result<Image, ReadError> {
+        let format = ImageFormat::from_path(path).ok_or_else(|| ReadError::UnsupportedFormat(path.display().to_string()))?;
+        let file = File::open(path).map_err(ReadError::IOError)?;
+        let reader = BufReader::new(file);
+
+        Image::read_as(reader, format)
+    }
+
+    pub fn read_as(reader: impl Read, format: ImageFormat) -> std::result::Result<Image, ReadError> {
+        match format {
+            ImageFormat::Png => read_png(reader),
+            ImageFormat::Ppm => read_ppm(reader),
+            ImageFormat::Pfm => read_pfm(reader),
+            _ => Err(ReadError::UnsupportedFormat(format!("{:?}", format))),
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
     pub fn get(&self, position: Position<2>) -> &Color {
         let index = self.index_of(position);
         &self.pixels[index]
@@ -47,43 +149,85 @@ impl Image {
     }
 
     pub fn write_to_file(&self, path: &Path) -> std::result::Result<(), WriteError> {
+        let format = ImageFormat::from_path(path).ok_or_else(|| WriteError::UnsupportedFormat(path.display().to_string()))?;
         let file = File::create(path).map_err(WriteError::IOError)?;
         let writer = BufWriter::new(file);
 
-        self.write(writer)
+        self.write_as(writer, format)
+    }
+
+    pub fn write_as(&self, writer: impl Write, format: ImageFormat) -> std::result::Result<(), WriteError> {
+        match format {
+            ImageFormat::Png => self.write(writer),
+            ImageFormat::Ppm => write_ppm(self, writer),
+            ImageFormat::Pfm => write_pfm(self, writer),
+            ImageFormat::Hdr => write_hdr(self, writer),
+            ImageFormat::Exr(compression) => write_exr(self, writer, compression),
+        }
     }
 
     pub fn write(&self, writer: impl Write) -> std::result::Result<(), WriteError> {
+        self.write_png(writer, &OutputTransform::linear())
+    }
+
+    pub fn write_png(&self, writer: impl Write, output_transform: &OutputTransform) -> std::result::Result<(), WriteError> {
+        self.encode_png(writer, png::ColorType::Rgb, &self.convert_to_display_rgb(output_transform))
+    }
+
+    // Straight (non-premultiplied) RGBA, as PNG requires
+    pub fn write_png_with_alpha(&self, writer: impl Write, output_transform: &OutputTransform) -> std::result::Result<(), WriteError> {
+        self.encode_png(writer, png::ColorType::Rgba, &self.convert_to_display_rgba(output_transform))
+    }
+
+    fn encode_png(&self, writer: impl Write, color_type: png::ColorType, data: &[u8]) -> std::result::Result<(), WriteError> {
         let width = self.width;
         let height = self.height;
         let encoder = {
             let mut encoder = png::Encoder::new(writer, width, height);
 
-            encoder.set_color(png::ColorType::Rgb);
+            encoder.set_color(color_type);
             encoder.set_depth(png::BitDepth::Eight);
 
             encoder
         };
         let mut writer2 = encoder.write_header().map_err(WriteError::PNGError)?;
-        let data = self.convert_to_raw_rgb();
         writer2
-            .write_image_data(&data)
+            .write_image_data(data)
             .map_err(WriteError::PNGError)?;
 
         Ok(())
     }
 
     pub fn convert_to_raw_rgb(&self) -> Vec<u8> {
+        self.convert_to_display_rgb(&OutputTransform::linear())
+    }
+
+    // The image itself stays linear; only the bytes go through the output transform
+    pub fn convert_to_display_rgb(&self, output_transform: &OutputTransform) -> Vec<u8> {
         let bytes_per_pixel = 3;
         let total_byte_count = (bytes_per_pixel * self.width * self.height) as usize;
         let mut result = Vec::with_capacity(total_byte_count);
 
         for color in self.pixels.iter() {
-            for c in color.to_byte_array() {
+            for c in output_transform.apply(color).to_byte_array() {
                 result.push(c);
             }
         }
 
         result
     }
+
+    pub fn convert_to_display_rgba(&self, output_transform: &OutputTransform) -> Vec<u8> {
+        let mut result = Vec::with_capacity((4 * self.width * self.height) as usize);
+
+        for (index, color) in self.pixels.iter().enumerate() {
+            let alpha = self.alpha.as_ref().map_or(1.0, |alpha| alpha[index]).clamp(0.0, 1.0);
+            let straight = if alpha > 0.0 { color / alpha } else { Color::black() };
+
+            result.extend_from_slice(&output_transform.apply(&straight).to_byte_array());
+            result.push((alpha * 255.0).round() as u8);
+        }
+
+        result
+    }
 }
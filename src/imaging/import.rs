@@ -0,0 +1,158 @@
+use std::io::Read;
+
+use super::{
+    color::Color,
+    image::{Image, ReadError},
+};
+
+// Files with an alpha channel produce an image with premultiplied colors
+pub fn read_png(reader: impl Read) -> Result<Image, ReadError> {
+    let mut decoder = png::Decoder::new(reader);
+    decoder.set_transformations(png::Transformations::EXPAND);
+
+    let mut reader = decoder.read_info().map_err(ReadError::PNGError)?;
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buffer).map_err(ReadError::PNGError)?;
+
+    let channel_count = match info.color_type {
+        png::ColorType::Grayscale => 1,
+        png::ColorType::GrayscaleAlpha => 2,
+        png::ColorType::Rgb => 3,
+        png::ColorType::Rgba => 4,
+        png::ColorType::Indexed => return Err(ReadError::FormatError(String::from("Palette was not expanded"))),
+    };
+    let samples: Vec<f64> = match info.bit_depth {
+        png::BitDepth::Sixteen => buffer[..info.buffer_size()]
+            .chunks_exact(2)
+            .map(|pair| u16::from_be_bytes([pair[0], pair[1]]) as f64 / 65535.0)
+            .collect(),
+        png::BitDepth::Eight => buffer[..info.buffer_size()].iter().map(|&b| b as f64 / 255.0).collect(),
+        depth => return Err(ReadError::FormatError(format!("Unsupported bit depth {depth:?}"))),
+    };
+
+    let width = info.width;
+    let height = info.height;
+    let pixel = |x: u32, y: u32| &samples[((y * width + x) * channel_count) as usize..][..channel_count as usize];
+    let alpha = |x: u32, y: u32| match pixel(x, y) {
+        [_, a] | [_, _, _, a] => *a,
+        _ => 1.0,
+    };
+    let image = Image::from_fn(width, height, |x, y| {
+        let color = match pixel(x, y) {
+            [v] | [v, _] => Color::new(*v, *v, *v),
+            [r, g, b] | [r, g, b, _] => Color::new(*r, *g, *b),
+            _ => unreachable!(),
+        };
+
+        color * alpha(x, y)
+    });
+
+    if channel_count % 2 == 0 {
+        let alpha = (0..height).flat_map(|y| (0..width).map(move |x| (x, y))).map(|(x, y)| alpha(x, y)).collect();
+
+        Ok(image.with_alpha(alpha))
+    } else {
+        Ok(image)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    #[cfg(test)]
+    use super::*;
+
+    #[cfg(test)]
+    use crate::{
+        imaging::tonemap::OutputTransform,
+        math::{approx, Position},
+    };
+
+    #[cfg(test)]
+    fn encode(width: u32, height: u32, color_type: png::ColorType, bit_depth: png::BitDepth, data: &[u8]) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        {
+            let mut encoder = png::Encoder::new(&mut buffer, width, height);
+            encoder.set_color(color_type);
+            encoder.set_depth(bit_depth);
+            let mut writer = encoder.write_header().unwrap();
+            writer.write_image_data(data).unwrap();
+        }
+
+        buffer
+    }
+
+    #[rstest]
+    fn round_trip() {
+        let image = Image::from_fn(4, 3, |x, y| Color::new(x as f64 / 3.0, y as f64 / 2.0, 1.0));
+        let mut buffer = Vec::new();
+        image.write(&mut buffer).unwrap();
+        let actual = read_png(&buffer[..]).unwrap();
+
+        assert_eq!((4, 3), (actual.width(), actual.height()));
+        for y in 0..3 {
+            for x in 0..4 {
+                let expected = image.get(Position::<2>::cartesian(x, y));
+                let actual = actual.get(Position::<2>::cartesian(x, y));
+                assert!((expected.r() - actual.r()).abs() < 1.0 / 255.0);
+                assert!((expected.g() - actual.g()).abs() < 1.0 / 255.0);
+                assert!((expected.b() - actual.b()).abs() < 1.0 / 255.0);
+            }
+        }
+    }
+
+    #[rstest]
+    fn grayscale_alpha() {
+        let data = encode(2, 1, png::ColorType::GrayscaleAlpha, png::BitDepth::Eight, &[255, 0, 51, 255]);
+        let image = read_png(&data[..]).unwrap();
+
+        assert_eq!(approx(0.0), image.get(Position::<2>::cartesian(0, 0)).g());
+        assert_eq!(approx(0.2), image.get(Position::<2>::cartesian(1, 0)).r());
+        assert_eq!(0.0, image.alpha(Position::<2>::cartesian(0, 0)));
+        assert_eq!(1.0, image.alpha(Position::<2>::cartesian(1, 0)));
+    }
+
+    #[rstest]
+    fn sixteen_bit_rgba() {
+        let data = encode(1, 1, png::ColorType::Rgba, png::BitDepth::Sixteen, &[0xff, 0xff, 0, 0, 0, 0, 0xff, 0xff]);
+        let image = read_png(&data[..]).unwrap();
+        let color = image.get(Position::<2>::cartesian(0, 0));
+
+        assert_eq!(approx(1.0), color.r());
+        assert_eq!(approx(0.0), color.g());
+        assert_eq!(1.0, image.alpha(Position::<2>::cartesian(0, 0)));
+    }
+
+    #[rstest]
+    fn rgba_round_trip() {
+        let alpha = vec![0.0, 0.2, 0.6, 1.0];
+        let image = Image::from_fn(2, 2, |x, y| Color::new(0.5, 0.25, 1.0) * alpha[(y * 2 + x) as usize]).with_alpha(alpha.clone());
+        let mut buffer = Vec::new();
+        image.write_png_with_alpha(&mut buffer, &OutputTransform::linear()).unwrap();
+        let actual = read_png(&buffer[..]).unwrap();
+
+        for y in 0..2 {
+            for x in 0..2 {
+                let position = Position::<2>::cartesian(x, y);
+
+                assert!((image.alpha(position) - actual.alpha(position)).abs() < 1.0 / 255.0);
+                assert!((image.get(position).b() - actual.get(position).b()).abs() < 1.0 / 255.0);
+                assert!((image.get(position).r() - actual.get(position).r()).abs() < 1.0 / 255.0);
+            }
+        }
+    }
+
+    #[rstest]
+    fn opaque_files_have_no_alpha() {
+        let mut buffer = Vec::new();
+        Image::new(2, 2).write(&mut buffer).unwrap();
+
+        assert!(!read_png(&buffer[..]).unwrap().has_alpha());
+    }
+
+    #[rstest]
+    fn invalid_data() {
+        assert!(read_png(&b"not a png"[..]).is_err());
+    }
+}
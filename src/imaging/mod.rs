@@ -1,5 +1,19 @@
 pub mod color;
+pub mod colormap;
+pub mod compare;
+pub mod denoise;
+pub mod exr;
+pub mod gif_writer;
 pub mod image;
+pub mod import;
+pub mod ops;
+pub mod pfm;
+pub mod ppm;
+pub mod quantize;
+pub mod radiance;
+pub mod stereo;
+pub mod tonemap;
+pub mod video;
 mod export;
 
-pub use export::{PNGWriter, PNGWriterOptions};
+pub use export::{FrameWriter, PNGSequenceWriter, PNGWriter, PNGWriterOptions};
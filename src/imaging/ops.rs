@@ -0,0 +1,459 @@
+use super::{color::Color, image::Image};
+use crate::math::{lanczos, Position};
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ResizeFilter {
+    Bilinear,
+    Lanczos { lobes: u32 },
+}
+
+impl ResizeFilter {
+    fn radius(&self) -> f64 {
+        match *self {
+            ResizeFilter::Bilinear => 1.0,
+            ResizeFilter::Lanczos { lobes } => lobes as f64,
+        }
+    }
+
+    fn evaluate(&self, x: f64) -> f64 {
+        let x = x.abs();
+
+        match *self {
+            ResizeFilter::Bilinear => (1.0 - x).max(0.0),
+            ResizeFilter::Lanczos { lobes } => lanczos(x, lobes as f64),
+        }
+    }
+}
+
+// Weights are stored row by row; both dimensions must be odd so the kernel has a center
+#[derive(Debug, Clone, PartialEq)]
+pub struct Kernel {
+    width: u32,
+    height: u32,
+    weights: Vec<f64>,
+}
+
+impl Kernel {
+    pub fn new(width: u32, height: u32, weights: Vec<f64>) -> Self {
+        assert!(width % 2 == 1 && height % 2 == 1, "Kernel dimensions must be odd");
+        assert_eq!((width * height) as usize, weights.len(), "Kernel size does not match weight count");
+
+        Kernel { width, height, weights }
+    }
+
+    pub fn gaussian(sigma: f64) -> Self {
+        let weights = gaussian_weights(sigma);
+        let size = weights.len() as u32;
+        let weights = weights.iter().flat_map(|wy| weights.iter().map(move |wx| wx * wy)).collect();
+
+        Kernel::new(size, size, weights)
+    }
+
+    pub fn scaled(&self, factor: f64) -> Self {
+        let weights = self.weights.iter().map(|w| w * factor).collect();
+
+        Kernel::new(self.width, self.height, weights)
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn weight(&self, x: u32, y: u32) -> f64 {
+        self.weights[(y * self.width + x) as usize]
+    }
+}
+
+// Normalized 1D Gaussian spanning three standard deviations on either side
+fn gaussian_weights(sigma: f64) -> Vec<f64> {
+    assert!(sigma > 0.0, "Standard deviation must be positive");
+
+    let radius = (3.0 * sigma).ceil() as i32;
+    let weights: Vec<f64> = (-radius..=radius)
+        .map(|i| (-((i * i) as f64) / (2.0 * sigma * sigma)).exp())
+        .collect();
+    let total: f64 = weights.iter().sum();
+
+    weights.iter().map(|w| w / total).collect()
+}
+
+impl Image {
+    // Coordinates outside the image are clamped to the nearest edge pixel
+    fn clamped(&self, x: i32, y: i32) -> Color {
+        let x = x.clamp(0, self.width() as i32 - 1);
+        let y = y.clamp(0, self.height() as i32 - 1);
+
+        *self.get(Position::<2>::cartesian(x, y))
+    }
+
+    fn pixel(&self, x: u32, y: u32) -> Color {
+        *self.get(Position::<2>::cartesian(x as i32, y as i32))
+    }
+
+    fn alpha_at(&self, x: u32, y: u32) -> f64 {
+        self.alpha(Position::<2>::cartesian(x as i32, y as i32))
+    }
+
+    // Applies the operation to the colors and, if present, to the alpha channel by running it on a gray image
+    fn preserving_alpha(&self, operation: impl Fn(&Image) -> Image) -> Image {
+        let result = operation(self);
+
+        if !self.has_alpha() {
+            return result;
+        }
+
+        let alpha = operation(&Image::from_fn(self.width(), self.height(), |x, y| Color::white() * self.alpha_at(x, y)));
+        result.with_alpha(row_major(alpha.width(), alpha.height(), |x, y| alpha.pixel(x, y).r()))
+    }
+
+    pub fn crop(&self, left: u32, top: u32, width: u32, height: u32) -> Image {
+        assert!(left + width <= self.width() && top + height <= self.height(), "Crop region exceeds image bounds");
+
+        self.preserving_alpha(|image| Image::from_fn(width, height, |x, y| image.pixel(left + x, top + y)))
+    }
+
+    pub fn resize(&self, width: u32, height: u32, filter: ResizeFilter) -> Image {
+        let horizontal = resample_weights(self.width(), width, filter);
+        let vertical = resample_weights(self.height(), height, filter);
+
+        self.preserving_alpha(|image| {
+            let intermediate = Image::from_fn(width, image.height(), |x, y| {
+                horizontal[x as usize]
+                    .iter()
+                    .fold(Color::black(), |acc, &(i, w)| acc + image.clamped(i, y as i32) * w)
+            });
+
+            Image::from_fn(width, height, |x, y| {
+                vertical[y as usize]
+                    .iter()
+                    .fold(Color::black(), |acc, &(i, w)| acc + intermediate.clamped(x as i32, i) * w)
+            })
+        })
+    }
+
+    pub fn flip_horizontal(&self) -> Image {
+        let (width, height) = (self.width(), self.height());
+
+        self.preserving_alpha(|image| Image::from_fn(width, height, |x, y| image.pixel(width - 1 - x, y)))
+    }
+
+    pub fn flip_vertical(&self) -> Image {
+        let (width, height) = (self.width(), self.height());
+
+        self.preserving_alpha(|image| Image::from_fn(width, height, |x, y| image.pixel(x, height - 1 - y)))
+    }
+
+    pub fn rotate_clockwise(&self) -> Image {
+        let (width, height) = (self.width(), self.height());
+
+        self.preserving_alpha(|image| Image::from_fn(height, width, |x, y| image.pixel(y, height - 1 - x)))
+    }
+
+    pub fn rotate_counterclockwise(&self) -> Image {
+        let (width, height) = (self.width(), self.height());
+
+        self.preserving_alpha(|image| Image::from_fn(height, width, |x, y| image.pixel(width - 1 - y, x)))
+    }
+
+    pub fn rotate_180(&self) -> Image {
+        let (width, height) = (self.width(), self.height());
+
+        self.preserving_alpha(|image| Image::from_fn(width, height, |x, y| image.pixel(width - 1 - x, height - 1 - y)))
+    }
+
+    // True convolution, i.e. the kernel is mirrored; edges are extended
+    pub fn convolve(&self, kernel: &Kernel) -> Image {
+        let cx = (kernel.width() / 2) as i32;
+        let cy = (kernel.height() / 2) as i32;
+
+        self.preserving_alpha(|image| {
+            Image::from_fn(image.width(), image.height(), |x, y| {
+                let mut total = Color::black();
+
+                for ky in 0..kernel.height() {
+                    for kx in 0..kernel.width() {
+                        let sx = x as i32 + cx - kx as i32;
+                        let sy = y as i32 + cy - ky as i32;
+                        total += image.clamped(sx, sy) * kernel.weight(kx, ky);
+                    }
+                }
+
+                total
+            })
+        })
+    }
+
+    // Separable, so much cheaper than convolving with Kernel::gaussian
+    pub fn gaussian_blur(&self, sigma: f64) -> Image {
+        let weights = gaussian_weights(sigma);
+        let radius = (weights.len() / 2) as i32;
+        let blur = |image: &Image, dx: i32, dy: i32| {
+            Image::from_fn(image.width(), image.height(), |x, y| {
+                weights.iter().enumerate().fold(Color::black(), |acc, (i, &w)| {
+                    let offset = i as i32 - radius;
+                    acc + image.clamped(x as i32 + offset * dx, y as i32 + offset * dy) * w
+                })
+            })
+        };
+
+        self.preserving_alpha(|image| blur(&blur(image, 1, 0), 0, 1))
+    }
+
+    // Arithmetic only affects colors; the result keeps the alpha of self
+    pub fn map(&self, f: impl Fn(&Color) -> Color) -> Image {
+        let result = Image::from_fn(self.width(), self.height(), |x, y| f(&self.pixel(x, y)));
+
+        self.copy_alpha_to(result)
+    }
+
+    pub fn zip_with(&self, other: &Image, f: impl Fn(&Color, &Color) -> Color) -> Image {
+        assert_eq!((self.width(), self.height()), (other.width(), other.height()), "Image sizes differ");
+
+        let result = Image::from_fn(self.width(), self.height(), |x, y| f(&self.pixel(x, y), &other.pixel(x, y)));
+
+        self.copy_alpha_to(result)
+    }
+
+    fn copy_alpha_to(&self, image: Image) -> Image {
+        if self.has_alpha() {
+            image.with_alpha(row_major(self.width(), self.height(), |x, y| self.alpha_at(x, y)))
+        } else {
+            image
+        }
+    }
+
+    pub fn add(&self, other: &Image) -> Image {
+        self.zip_with(other, |a, b| a + b)
+    }
+
+    pub fn subtract(&self, other: &Image) -> Image {
+        self.zip_with(other, |a, b| Color::new(a.r() - b.r(), a.g() - b.g(), a.b() - b.b()))
+    }
+
+    pub fn multiply(&self, other: &Image) -> Image {
+        self.zip_with(other, |a, b| a * b)
+    }
+
+    pub fn scale(&self, factor: f64) -> Image {
+        self.map(|c| c * factor)
+    }
+
+    // Porter-Duff over for premultiplied images; the result is opaque unless the background has alpha
+    pub fn premultiplied_over(&self, background: &Image) -> Image {
+        assert_eq!((self.width(), self.height()), (background.width(), background.height()), "Image sizes differ");
+
+        let result = Image::from_fn(self.width(), self.height(), |x, y| {
+            self.pixel(x, y) + background.pixel(x, y) * (1.0 - self.alpha_at(x, y))
+        });
+
+        if background.has_alpha() {
+            let alpha = row_major(self.width(), self.height(), |x, y| {
+                let a = self.alpha_at(x, y);
+                a + background.alpha_at(x, y) * (1.0 - a)
+            });
+
+            result.with_alpha(alpha)
+        } else {
+            result
+        }
+    }
+}
+
+fn row_major(width: u32, height: u32, f: impl Fn(u32, u32) -> f64) -> Vec<f64> {
+    (0..height).flat_map(|y| (0..width).map(move |x| (x, y))).map(|(x, y)| f(x, y)).collect()
+}
+
+// For each destination index, the contributing source indices with normalized weights
+fn resample_weights(source_size: u32, destination_size: u32, filter: ResizeFilter) -> Vec<Vec<(i32, f64)>> {
+    let ratio = source_size as f64 / destination_size as f64;
+
+    // When shrinking, the filter is widened so it covers all source pixels
+    let scale = ratio.max(1.0);
+    let support = filter.radius() * scale;
+
+    (0..destination_size)
+        .map(|i| {
+            let center = (i as f64 + 0.5) * ratio - 0.5;
+            let first = (center - support).floor() as i32;
+            let last = (center + support).ceil() as i32;
+            let weights: Vec<(i32, f64)> = (first..=last)
+                .map(|j| (j, filter.evaluate((j as f64 - center) / scale)))
+                .filter(|&(_, w)| w != 0.0)
+                .collect();
+            let total: f64 = weights.iter().map(|(_, w)| w).sum();
+
+            weights.into_iter().map(|(j, w)| (j, w / total)).collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    #[cfg(test)]
+    use super::*;
+
+    #[cfg(test)]
+    use crate::math::approx;
+
+    // Red channel holds x, green holds y, which makes geometric operations easy to check
+    #[cfg(test)]
+    fn coordinates(width: u32, height: u32) -> Image {
+        Image::from_fn(width, height, |x, y| Color::new(x as f64, y as f64, 0.0))
+    }
+
+    #[cfg(test)]
+    fn coordinates_at(image: &Image, x: u32, y: u32) -> (f64, f64) {
+        let color = image.pixel(x, y);
+
+        (color.r(), color.g())
+    }
+
+    #[rstest]
+    fn crop() {
+        let actual = coordinates(5, 4).crop(1, 2, 3, 2);
+
+        assert_eq!((3, 2), (actual.width(), actual.height()));
+        assert_eq!((1.0, 2.0), coordinates_at(&actual, 0, 0));
+        assert_eq!((3.0, 3.0), coordinates_at(&actual, 2, 1));
+    }
+
+    #[rstest]
+    #[case(0, 0, (2.0, 0.0))]
+    #[case(2, 1, (0.0, 1.0))]
+    fn flip_horizontal(#[case] x: u32, #[case] y: u32, #[case] expected: (f64, f64)) {
+        assert_eq!(expected, coordinates_at(&coordinates(3, 2).flip_horizontal(), x, y));
+    }
+
+    #[rstest]
+    #[case(0, 0, (0.0, 1.0))]
+    #[case(2, 1, (2.0, 0.0))]
+    fn flip_vertical(#[case] x: u32, #[case] y: u32, #[case] expected: (f64, f64)) {
+        assert_eq!(expected, coordinates_at(&coordinates(3, 2).flip_vertical(), x, y));
+    }
+
+    #[rstest]
+    fn rotations() {
+        let image = coordinates(3, 2);
+        let clockwise = image.rotate_clockwise();
+        let counterclockwise = image.rotate_counterclockwise();
+
+        assert_eq!((2, 3), (clockwise.width(), clockwise.height()));
+        assert_eq!((0.0, 1.0), coordinates_at(&clockwise, 0, 0));
+        assert_eq!((2.0, 0.0), coordinates_at(&clockwise, 1, 2));
+        assert_eq!((2.0, 0.0), coordinates_at(&counterclockwise, 0, 0));
+        assert_eq!((2, 3), (counterclockwise.width(), counterclockwise.height()));
+        assert_eq!((2.0, 1.0), coordinates_at(&image.rotate_180(), 0, 0));
+        assert_eq!(image, clockwise.rotate_counterclockwise());
+        assert_eq!(image.rotate_180(), clockwise.rotate_clockwise());
+    }
+
+    #[rstest]
+    fn resize_preserves_constant_images(#[values(ResizeFilter::Bilinear, ResizeFilter::Lanczos { lobes: 3 })] filter: ResizeFilter, #[values((2, 3), (17, 9))] size: (u32, u32)) {
+        let image = Image::from_fn(7, 5, |_, _| Color::new(0.25, 0.5, 1.0));
+        let actual = image.resize(size.0, size.1, filter);
+
+        assert_eq!(size, (actual.width(), actual.height()));
+        for y in 0..size.1 {
+            for x in 0..size.0 {
+                assert_eq!(approx(0.5), actual.pixel(x, y).g());
+            }
+        }
+    }
+
+    #[rstest]
+    fn bilinear_upscale_interpolates() {
+        let image = Image::from_fn(2, 1, |x, _| Color::white() * x as f64);
+        let actual = image.resize(4, 1, ResizeFilter::Bilinear);
+        let values: Vec<f64> = (0..4).map(|x| actual.pixel(x, 0).r()).collect();
+
+        assert_eq!(vec![approx(0.0), approx(0.25), approx(0.75), approx(1.0)], values);
+    }
+
+    #[rstest]
+    fn downscale_averages() {
+        let image = Image::from_fn(8, 1, |x, _| Color::white() * (x % 2) as f64);
+        let actual = image.resize(4, 1, ResizeFilter::Bilinear);
+
+        assert_eq!(approx(0.5), actual.pixel(1, 0).r());
+        assert_eq!(approx(0.5), actual.pixel(2, 0).r());
+    }
+
+    #[rstest]
+    fn convolution_mirrors_kernel() {
+        let mut image = Image::new(3, 3);
+        *image.get_mut(Position::<2>::cartesian(1, 1)) = Color::white();
+        let kernel = Kernel::new(3, 1, vec![1.0, 2.0, 3.0]);
+        let actual = image.convolve(&kernel);
+
+        assert_eq!(approx(1.0), actual.pixel(0, 1).r());
+        assert_eq!(approx(2.0), actual.pixel(1, 1).r());
+        assert_eq!(approx(3.0), actual.pixel(2, 1).r());
+        assert_eq!(approx(0.0), actual.pixel(1, 0).r());
+    }
+
+    #[rstest]
+    fn gaussian_blur_matches_kernel() {
+        let image = Image::from_fn(9, 7, |x, y| Color::new(((x * 7 + y * 3) % 5) as f64, 0.0, 0.0));
+        let separable = image.gaussian_blur(1.2);
+        let direct = image.convolve(&Kernel::gaussian(1.2));
+
+        for y in 0..7 {
+            for x in 0..9 {
+                assert_eq!(approx(direct.pixel(x, y).r()), separable.pixel(x, y).r());
+            }
+        }
+    }
+
+    #[rstest]
+    fn gaussian_blur_preserves_constant_images() {
+        let image = Image::from_fn(4, 4, |_, _| Color::white() * 3.0);
+
+        assert_eq!(approx(3.0), image.gaussian_blur(2.0).pixel(0, 3).b());
+    }
+
+    #[rstest]
+    fn arithmetic() {
+        let a = Image::from_fn(2, 2, |x, y| Color::new(x as f64, y as f64, 1.0));
+        let b = Image::from_fn(2, 2, |_, _| Color::new(2.0, 3.0, 4.0));
+
+        assert_eq!(Color::new(3.0, 4.0, 5.0), a.add(&b).pixel(1, 1));
+        assert_eq!(Color::new(-1.0, -3.0, -3.0), a.subtract(&b).pixel(1, 0));
+        assert_eq!(Color::new(2.0, 3.0, 4.0), a.multiply(&b).pixel(1, 1));
+        assert_eq!(Color::new(0.0, 0.5, 0.5), a.scale(0.5).pixel(0, 1));
+    }
+
+    #[rstest]
+    #[case(0.0, 1.0, 0.5)]
+    #[case(0.5, 0.75, 0.5)]
+    #[case(1.0, 0.5, 0.5)]
+    fn premultiplied_over(#[case] alpha: f64, #[case] expected_color: f64, #[case] background_alpha: f64) {
+        let foreground = Image::from_fn(1, 1, |_, _| Color::white() * (0.5 * alpha)).with_alpha(vec![alpha]);
+        let background = Image::from_fn(1, 1, |_, _| Color::white());
+        let translucent = Image::from_fn(1, 1, |_, _| Color::white() * background_alpha).with_alpha(vec![background_alpha]);
+
+        let opaque = foreground.premultiplied_over(&background);
+        assert!(!opaque.has_alpha());
+        assert_eq!(approx(expected_color), opaque.pixel(0, 0).r());
+
+        let layered = foreground.premultiplied_over(&translucent);
+        assert_eq!(approx(alpha + background_alpha * (1.0 - alpha)), layered.alpha_at(0, 0));
+    }
+
+    #[rstest]
+    fn operations_preserve_alpha() {
+        let image = coordinates(3, 2).with_alpha(vec![0.0, 0.1, 0.2, 0.3, 0.4, 0.5]);
+
+        assert_eq!(0.2, image.flip_horizontal().alpha_at(0, 0));
+        assert_eq!(0.3, image.rotate_clockwise().alpha_at(0, 0));
+        assert_eq!(0.4, image.crop(1, 1, 2, 1).alpha_at(0, 0));
+        assert_eq!(0.5, image.scale(2.0).alpha_at(2, 1));
+        assert_eq!(approx(0.25), image.resize(1, 1, ResizeFilter::Bilinear).alpha_at(0, 0));
+        assert!(!coordinates(3, 2).flip_vertical().has_alpha());
+    }
+}
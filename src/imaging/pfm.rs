@@ -0,0 +1,140 @@
+use std::io::{Read, Write};
+
+use super::{
+    color::Color,
+    image::{Image, ReadError, WriteError},
+    ppm::{parse, read_header},
+};
+use crate::math::Position;
+
+// Little endian (negative scale) RGB floats, rows stored bottom to top
+pub fn write_pfm(image: &Image, mut writer: impl Write) -> Result<(), WriteError> {
+    let width = image.width();
+    let height = image.height();
+    let mut data = Vec::with_capacity((width * height * 12) as usize);
+
+    for y in (0..height).rev() {
+        for x in 0..width {
+            let color = image.get(Position::<2>::cartesian(x as i32, y as i32));
+
+            for component in [color.r(), color.g(), color.b()] {
+                data.extend_from_slice(&(component as f32).to_le_bytes());
+            }
+        }
+    }
+
+    write!(writer, "PF\n{width} {height}\n-1.0\n").map_err(WriteError::IOError)?;
+    writer.write_all(&data).map_err(WriteError::IOError)?;
+
+    Ok(())
+}
+
+// Accepts color (PF) and grayscale (Pf) files in either byte order
+pub fn read_pfm(mut reader: impl Read) -> Result<Image, ReadError> {
+    let mut data = Vec::new();
+    reader.read_to_end(&mut data).map_err(ReadError::IOError)?;
+
+    let (header, offset) = read_header(&data, 4)?;
+    let channels = match header[0].as_str() {
+        "PF" => 3,
+        "Pf" => 1,
+        magic => return Err(ReadError::FormatError(format!("Unsupported PFM type {magic}"))),
+    };
+    let width = parse::<u32>(&header[1])?;
+    let height = parse::<u32>(&header[2])?;
+    let little_endian = parse::<f64>(&header[3])? < 0.0;
+
+    let values: Vec<f64> = data[offset..]
+        .chunks_exact(4)
+        .map(|bytes| {
+            let bytes = [bytes[0], bytes[1], bytes[2], bytes[3]];
+            let value = if little_endian { f32::from_le_bytes(bytes) } else { f32::from_be_bytes(bytes) };
+
+            value as f64
+        })
+        .collect();
+
+    let value_count = (width as usize)
+        .checked_mul(height as usize)
+        .and_then(|pixel_count| pixel_count.checked_mul(channels))
+        .ok_or_else(|| ReadError::FormatError(format!("Invalid image size {width}x{height}")))?;
+
+    if values.len() < value_count {
+        return Err(ReadError::FormatError(String::from("Unexpected end of pixel data")));
+    }
+
+    Ok(Image::from_fn(width, height, |x, y| {
+        let index = ((height - 1 - y) as usize * width as usize + x as usize) * channels;
+
+        if channels == 3 {
+            Color::new(values[index], values[index + 1], values[index + 2])
+        } else {
+            Color::new(values[index], values[index], values[index])
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    #[cfg(test)]
+    use super::*;
+
+    #[rstest]
+    fn header_and_row_order() {
+        let mut image = Image::new(2, 2);
+        *image.get_mut(Position::<2>::cartesian(0, 0)) = Color::new(1.0, 2.0, 3.0);
+        *image.get_mut(Position::<2>::cartesian(1, 1)) = Color::new(100.0, 0.5, 0.0);
+
+        let mut buffer = Vec::new();
+        write_pfm(&image, &mut buffer).unwrap();
+
+        let header = b"PF\n2 2\n-1.0\n";
+        assert_eq!(header, &buffer[..header.len()]);
+
+        let floats: Vec<f32> = buffer[header.len()..]
+            .chunks(4)
+            .map(|bytes| f32::from_le_bytes(bytes.try_into().unwrap()))
+            .collect();
+
+        assert_eq!(12, floats.len());
+        // Bottom row first: (0, 1) then (1, 1)
+        assert_eq!(&[0.0, 0.0, 0.0, 100.0, 0.5, 0.0], &floats[0..6]);
+        assert_eq!(&[1.0, 2.0, 3.0, 0.0, 0.0, 0.0], &floats[6..12]);
+    }
+
+    #[rstest]
+    fn round_trip() {
+        let image = Image::from_fn(3, 2, |x, y| Color::new(x as f64 * 100.0, y as f64 - 0.5, 0.125));
+        let mut buffer = Vec::new();
+        write_pfm(&image, &mut buffer).unwrap();
+        let actual = read_pfm(&buffer[..]).unwrap();
+
+        for y in 0..2 {
+            for x in 0..3 {
+                assert_eq!(image.get(Position::<2>::cartesian(x, y)), actual.get(Position::<2>::cartesian(x, y)));
+            }
+        }
+    }
+
+    #[rstest]
+    fn big_endian_grayscale() {
+        let mut data = b"Pf\n1 2\n1.0\n".to_vec();
+        data.extend_from_slice(&0.25f32.to_be_bytes());
+        data.extend_from_slice(&4.0f32.to_be_bytes());
+        let image = read_pfm(&data[..]).unwrap();
+
+        assert_eq!(4.0, image.get(Position::<2>::cartesian(0, 0)).g());
+        assert_eq!(0.25, image.get(Position::<2>::cartesian(0, 1)).b());
+    }
+
+    #[rstest]
+    #[case(&b"PX\n1 1\n-1.0\n\0\0\0\0"[..])]
+    #[case(&b"Pf\n2 1\n-1.0\n\0\0\0\0"[..])]
+    #[case(&b"PF\n65536 65536\n-1.0\n\0\0\0\0"[..])]
+    #[case(&b"PF\n4294967295 4294967295\n-1.0\n\0\0\0\0"[..])]
+    fn invalid_files(#[case] data: &[u8]) {
+        assert!(read_pfm(data).is_err());
+    }
+}
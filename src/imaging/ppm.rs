@@ -0,0 +1,158 @@
+use std::io::{Read, Write};
+
+use super::{
+    color::Color,
+    image::{Image, ReadError, WriteError},
+};
+
+pub fn write_ppm(image: &Image, mut writer: impl Write) -> Result<(), WriteError> {
+    write!(writer, "P6\n{} {}\n255\n", image.width(), image.height()).map_err(WriteError::IOError)?;
+    writer.write_all(&image.convert_to_raw_rgb()).map_err(WriteError::IOError)?;
+
+    Ok(())
+}
+
+// Supports both plain (P3) and binary (P6) files with 8 or 16 bits per sample
+pub fn read_ppm(mut reader: impl Read) -> Result<Image, ReadError> {
+    let mut data = Vec::new();
+    reader.read_to_end(&mut data).map_err(ReadError::IOError)?;
+
+    let (header, offset) = read_header(&data, 4)?;
+    let width = parse::<u32>(&header[1])?;
+    let height = parse::<u32>(&header[2])?;
+    let max_value = parse::<u32>(&header[3])?;
+    // Header dimensions are untrusted, so their product must not overflow
+    let sample_count = (width as usize)
+        .checked_mul(height as usize)
+        .and_then(|pixel_count| pixel_count.checked_mul(3))
+        .ok_or_else(|| ReadError::FormatError(format!("Invalid image size {width}x{height}")))?;
+
+    if max_value == 0 || max_value > 65535 {
+        return Err(ReadError::FormatError(format!("Invalid maximum value {max_value}")));
+    }
+
+    let samples: Vec<u32> = match header[0].as_str() {
+        "P3" => {
+            let text = String::from_utf8_lossy(&data[offset..]);
+            text.split_whitespace()
+                .take(sample_count)
+                .map(parse::<u32>)
+                .collect::<Result<_, _>>()?
+        }
+        "P6" if max_value < 256 => data[offset..].iter().take(sample_count).map(|&b| b as u32).collect(),
+        "P6" => data[offset..]
+            .chunks_exact(2)
+            .take(sample_count)
+            .map(|pair| u16::from_be_bytes([pair[0], pair[1]]) as u32)
+            .collect(),
+        magic => return Err(ReadError::FormatError(format!("Unsupported PPM type {magic}"))),
+    };
+
+    if samples.len() < sample_count {
+        return Err(ReadError::FormatError(String::from("Unexpected end of pixel data")));
+    }
+
+    let scale = (max_value as f64).recip();
+    Ok(Image::from_fn(width, height, |x, y| {
+        let index = (y as usize * width as usize + x as usize) * 3;
+        Color::new(samples[index] as f64 * scale, samples[index + 1] as f64 * scale, samples[index + 2] as f64 * scale)
+    }))
+}
+
+// Reads whitespace separated tokens, skipping comments, and returns them together with the
+// position of the first data byte (a single whitespace character follows the last token)
+pub(super) fn read_header(data: &[u8], token_count: usize) -> Result<(Vec<String>, usize), ReadError> {
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while tokens.len() < token_count {
+        match data.get(i) {
+            None => return Err(ReadError::FormatError(String::from("Truncated header"))),
+            Some(b'#') => {
+                while i < data.len() && data[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            Some(c) if c.is_ascii_whitespace() => i += 1,
+            Some(_) => {
+                let start = i;
+                while i < data.len() && !data[i].is_ascii_whitespace() {
+                    i += 1;
+                }
+                tokens.push(String::from_utf8_lossy(&data[start..i]).into_owned());
+            }
+        }
+    }
+
+    Ok((tokens, i + 1))
+}
+
+pub(super) fn parse<T: std::str::FromStr>(token: &str) -> Result<T, ReadError> {
+    token
+        .parse()
+        .map_err(|_| ReadError::FormatError(format!("Invalid header value {token}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    #[cfg(test)]
+    use super::*;
+
+    #[cfg(test)]
+    use crate::math::{approx, Position};
+
+    #[rstest]
+    fn plain_with_comments() {
+        let data = b"P3\n# a comment\n2 1\n# another\n10\n10 5 0  0 0 10\n";
+        let image = read_ppm(&data[..]).unwrap();
+        let left = image.get(Position::<2>::cartesian(0, 0));
+        let right = image.get(Position::<2>::cartesian(1, 0));
+
+        assert_eq!((2, 1), (image.width(), image.height()));
+        assert_eq!(approx(1.0), left.r());
+        assert_eq!(approx(0.5), left.g());
+        assert_eq!(approx(1.0), right.b());
+    }
+
+    #[rstest]
+    fn binary_sixteen_bit() {
+        let mut data = b"P6 1 1 65535\n".to_vec();
+        data.extend_from_slice(&[0xff, 0xff, 0x80, 0x00, 0x00, 0x00]);
+        let image = read_ppm(&data[..]).unwrap();
+        let color = image.get(Position::<2>::cartesian(0, 0));
+
+        assert_eq!(approx(1.0), color.r());
+        assert!((color.g() - 0.5).abs() < 1e-4);
+        assert_eq!(approx(0.0), color.b());
+    }
+
+    #[rstest]
+    fn binary_round_trip() {
+        let image = Image::from_fn(3, 2, |x, y| Color::new(x as f64 / 4.0, y as f64 / 2.0, 0.75));
+        let mut buffer = Vec::new();
+        write_ppm(&image, &mut buffer).unwrap();
+        let actual = read_ppm(&buffer[..]).unwrap();
+
+        for y in 0..2 {
+            for x in 0..3 {
+                let expected = image.get(Position::<2>::cartesian(x, y));
+                let actual = actual.get(Position::<2>::cartesian(x, y));
+                assert!((expected.r() - actual.r()).abs() < 1.0 / 255.0);
+                assert!((expected.g() - actual.g()).abs() < 1.0 / 255.0);
+                assert!((expected.b() - actual.b()).abs() < 1.0 / 255.0);
+            }
+        }
+    }
+
+    #[rstest]
+    #[case(&b"P5\n1 1\n255\n\0"[..])]
+    #[case(&b"P6\n2 2\n255\n\0\0\0"[..])]
+    #[case(&b"P6\n2"[..])]
+    #[case(&b"P6\n65536 65536\n255\n\0\0\0"[..])]
+    #[case(&b"P6\n4294967295 4294967295\n255\n\0\0\0"[..])]
+    fn invalid_files(#[case] data: &[u8]) {
+        assert!(read_ppm(data).is_err());
+    }
+}
@@ -0,0 +1,357 @@
+use std::collections::HashMap;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Quantizer {
+    MedianCut,
+    Octree,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Dithering {
+    None,
+    FloydSteinberg,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Palette {
+    colors: Vec<[u8; 3]>,
+}
+
+impl Palette {
+    pub fn colors(&self) -> &[[u8; 3]] {
+        &self.colors
+    }
+
+    pub fn len(&self) -> usize {
+        self.colors.len()
+    }
+
+    // Flattened RGB triplets, as expected by image encoders
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.colors.iter().flatten().copied().collect()
+    }
+
+    pub fn nearest(&self, color: [u8; 3]) -> u8 {
+        let distance = |candidate: &[u8; 3]| {
+            (0..3)
+                .map(|i| {
+                    let difference = candidate[i] as i32 - color[i] as i32;
+                    difference * difference
+                })
+                .sum::<i32>()
+        };
+
+        (0..self.colors.len()).min_by_key(|&i| distance(&self.colors[i])).expect("Palette must not be empty") as u8
+    }
+}
+
+impl Quantizer {
+    // Pixels are RGB triplets; the palette holds at most max_colors entries (at most 256)
+    pub fn palette(&self, rgb: &[u8], max_colors: usize) -> Palette {
+        debug_assert!((1..=256).contains(&max_colors));
+
+        let histogram = histogram(rgb);
+
+        let colors = if histogram.len() <= max_colors {
+            histogram.into_iter().map(|(color, _)| color).collect()
+        } else {
+            match self {
+                Quantizer::MedianCut => median_cut(histogram, max_colors),
+                Quantizer::Octree => octree(&histogram, max_colors),
+            }
+        };
+
+        Palette { colors }
+    }
+}
+
+// Maps every RGB triplet to a palette index
+pub fn quantize(rgb: &[u8], width: u32, height: u32, palette: &Palette, dithering: Dithering) -> Vec<u8> {
+    let mut cache = HashMap::new();
+    let mut lookup = |color: [u8; 3]| *cache.entry(color).or_insert_with(|| palette.nearest(color));
+
+    match dithering {
+        Dithering::None => rgb.chunks_exact(3).map(|pixel| lookup([pixel[0], pixel[1], pixel[2]])).collect(),
+        Dithering::FloydSteinberg => {
+            let width = width as usize;
+            let height = height as usize;
+            let mut values: Vec<[f64; 3]> = rgb.chunks_exact(3).map(|p| [p[0] as f64, p[1] as f64, p[2] as f64]).collect();
+            let mut result = Vec::with_capacity(width * height);
+
+            for y in 0..height {
+                for x in 0..width {
+                    let value = values[y * width + x];
+                    let target = value.map(|c| c.round().clamp(0.0, 255.0) as u8);
+                    let index = lookup(target);
+                    let chosen = palette.colors[index as usize];
+                    let error = [0, 1, 2].map(|i| value[i] - chosen[i] as f64);
+                    let mut diffuse = |dx: isize, dy: usize, weight: f64| {
+                        let nx = x as isize + dx;
+
+                        if 0 <= nx && (nx as usize) < width && y + dy < height {
+                            let neighbor = &mut values[(y + dy) * width + nx as usize];
+
+                            for i in 0..3 {
+                                neighbor[i] += error[i] * weight;
+                            }
+                        }
+                    };
+
+                    diffuse(1, 0, 7.0 / 16.0);
+                    diffuse(-1, 1, 3.0 / 16.0);
+                    diffuse(0, 1, 5.0 / 16.0);
+                    diffuse(1, 1, 1.0 / 16.0);
+                    result.push(index);
+                }
+            }
+
+            result
+        }
+    }
+}
+
+// Distinct colors with their pixel counts, sorted to keep results deterministic
+fn histogram(rgb: &[u8]) -> Vec<([u8; 3], u64)> {
+    let mut counts = HashMap::new();
+
+    for pixel in rgb.chunks_exact(3) {
+        *counts.entry([pixel[0], pixel[1], pixel[2]]).or_insert(0u64) += 1;
+    }
+
+    let mut histogram: Vec<_> = counts.into_iter().collect();
+    histogram.sort_unstable();
+    histogram
+}
+
+fn weighted_average(colors: &[([u8; 3], u64)]) -> [u8; 3] {
+    let total: u64 = colors.iter().map(|(_, count)| count).sum();
+
+    [0, 1, 2].map(|i| {
+        let sum: u64 = colors.iter().map(|(color, count)| color[i] as u64 * count).sum();
+        ((sum as f64 / total as f64).round()) as u8
+    })
+}
+
+// Repeatedly splits the box with the largest squared error at the weighted median of its widest channel
+fn median_cut(histogram: Vec<([u8; 3], u64)>, max_colors: usize) -> Vec<[u8; 3]> {
+    let range = |colors: &[([u8; 3], u64)], channel: usize| {
+        let min = colors.iter().map(|(color, _)| color[channel]).min().unwrap_or(0);
+        let max = colors.iter().map(|(color, _)| color[channel]).max().unwrap_or(0);
+        max - min
+    };
+    let widest_channel = |colors: &[([u8; 3], u64)]| (0..3).max_by_key(|&channel| range(colors, channel)).unwrap();
+    let squared_error = |colors: &[([u8; 3], u64)]| {
+        let average = weighted_average(colors);
+
+        colors
+            .iter()
+            .map(|(color, count)| (0..3).map(|i| (color[i] as f64 - average[i] as f64).powi(2)).sum::<f64>() * *count as f64)
+            .sum::<f64>()
+    };
+
+    let mut boxes = vec![histogram];
+
+    while boxes.len() < max_colors {
+        let candidate = (0..boxes.len())
+            .filter(|&i| boxes[i].len() > 1)
+            .map(|i| (i, squared_error(&boxes[i])))
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(i, _)| i);
+
+        let Some(index) = candidate else { break };
+        let mut colors = boxes.swap_remove(index);
+        let channel = widest_channel(&colors);
+        colors.sort_unstable_by_key(|(color, _)| color[channel]);
+
+        let total: u64 = colors.iter().map(|(_, count)| count).sum();
+        let mut accumulated = 0;
+        let mut split = colors.len() - 1;
+        for (i, (_, count)) in colors.iter().enumerate() {
+            accumulated += count;
+            if 2 * accumulated >= total {
+                split = i + 1;
+                break;
+            }
+        }
+        let split = split.clamp(1, colors.len() - 1);
+
+        let upper = colors.split_off(split);
+        boxes.push(colors);
+        boxes.push(upper);
+    }
+
+    boxes.iter().map(|colors| weighted_average(colors)).collect()
+}
+
+struct OctreeNode {
+    children: [Option<usize>; 8],
+    count: u64,
+    sum: [u64; 3],
+    is_leaf: bool,
+}
+
+// Colors are inserted into a tree with one level per bit; the least populated nodes are merged bottom up
+fn octree(histogram: &[([u8; 3], u64)], max_colors: usize) -> Vec<[u8; 3]> {
+    const DEPTH: usize = 8;
+
+    let new_node = |is_leaf: bool| OctreeNode {
+        children: [None; 8],
+        count: 0,
+        sum: [0; 3],
+        is_leaf,
+    };
+    let mut nodes = vec![new_node(false)];
+    let mut interior_nodes_per_level: Vec<Vec<usize>> = vec![vec![0]];
+    interior_nodes_per_level.resize(DEPTH, Vec::new());
+    let mut leaf_count = 0;
+
+    for &(color, count) in histogram {
+        let mut current = 0;
+
+        for level in 0..=DEPTH {
+            let node = &mut nodes[current];
+            node.count += count;
+            for (sum, &channel) in node.sum.iter_mut().zip(color.iter()) {
+                *sum += channel as u64 * count;
+            }
+
+            if level == DEPTH {
+                break;
+            }
+
+            let bit = 7 - level;
+            let child_index = (0..3).fold(0, |index, i| (index << 1) | ((color[i] >> bit) & 1) as usize);
+
+            current = match nodes[current].children[child_index] {
+                Some(child) => child,
+                None => {
+                    let child = nodes.len();
+                    let is_leaf = level + 1 == DEPTH;
+                    nodes.push(new_node(is_leaf));
+                    nodes[current].children[child_index] = Some(child);
+
+                    if is_leaf {
+                        leaf_count += 1;
+                    } else {
+                        interior_nodes_per_level[level + 1].push(child);
+                    }
+
+                    child
+                }
+            };
+        }
+    }
+
+    for level in (0..DEPTH).rev() {
+        let mut candidates = std::mem::take(&mut interior_nodes_per_level[level]);
+        candidates.sort_unstable_by_key(|&node| std::cmp::Reverse(nodes[node].count));
+
+        while leaf_count > max_colors {
+            let Some(node) = candidates.pop() else { break };
+            let child_count = nodes[node].children.iter().flatten().count();
+
+            nodes[node].children = [None; 8];
+            nodes[node].is_leaf = true;
+            leaf_count -= child_count - 1;
+        }
+    }
+
+    let mut palette = Vec::with_capacity(leaf_count);
+    let mut stack = vec![0];
+    while let Some(index) = stack.pop() {
+        let node = &nodes[index];
+
+        if node.is_leaf {
+            palette.push(node.sum.map(|sum| (sum as f64 / node.count as f64).round() as u8));
+        } else {
+            stack.extend(node.children.iter().flatten());
+        }
+    }
+
+    palette
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    #[cfg(test)]
+    use super::*;
+
+    #[cfg(test)]
+    use crate::util::Random;
+
+    #[cfg(test)]
+    fn gradient(width: u32, height: u32) -> Vec<u8> {
+        (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .flat_map(|(x, y)| [(x * 255 / (width - 1)) as u8, (y * 255 / (height - 1)) as u8, 128])
+            .collect()
+    }
+
+    #[rstest]
+    fn few_colors_are_kept_exactly(#[values(Quantizer::MedianCut, Quantizer::Octree)] quantizer: Quantizer) {
+        let rgb = [255, 0, 0, 0, 255, 0, 255, 0, 0, 10, 20, 30];
+        let palette = quantizer.palette(&rgb, 256);
+        let indices = quantize(&rgb, 4, 1, &palette, Dithering::None);
+
+        assert_eq!(3, palette.len());
+        for (index, pixel) in indices.iter().zip(rgb.chunks(3)) {
+            assert_eq!(pixel, palette.colors()[*index as usize]);
+        }
+    }
+
+    #[rstest]
+    fn palette_size_is_limited(
+        #[values(Quantizer::MedianCut, Quantizer::Octree)] quantizer: Quantizer,
+        #[values(2, 16, 256)] max_colors: usize,
+    ) {
+        let mut random = Random::new(5);
+        let rgb: Vec<u8> = (0..3 * 4000).map(|_| (random.next_f64() * 256.0) as u8).collect();
+        let palette = quantizer.palette(&rgb, max_colors);
+
+        assert!(palette.len() <= max_colors);
+        assert!(palette.len() >= max_colors / 2);
+    }
+
+    #[rstest]
+    fn clusters_are_found(#[values(Quantizer::MedianCut, Quantizer::Octree)] quantizer: Quantizer) {
+        let rgb: Vec<u8> = (0..100).flat_map(|i| if i % 2 == 0 { [200, 10 + i % 3, 10] } else { [10, 10, 200 + i % 5] }).collect();
+        let palette = quantizer.palette(&rgb, 2);
+        let indices = quantize(&rgb, 100, 1, &palette, Dithering::None);
+
+        assert_eq!(2, palette.len());
+        assert!(indices.iter().step_by(2).all(|&i| i == indices[0]));
+        assert!(indices.iter().skip(1).step_by(2).all(|&i| i == indices[1]));
+        assert_ne!(indices[0], indices[1]);
+    }
+
+    // A flat gray between two palette entries comes out as a mix of both with the right average
+    #[rstest]
+    fn dithering_preserves_average() {
+        let palette = Palette { colors: vec![[0, 0, 0], [255, 255, 255]] };
+        let rgb = vec![64; 3 * 32 * 32];
+        let plain = quantize(&rgb, 32, 32, &palette, Dithering::None);
+        let dithered = quantize(&rgb, 32, 32, &palette, Dithering::FloydSteinberg);
+        let average = |indices: &[u8]| indices.iter().map(|&i| palette.colors()[i as usize][0] as f64).sum::<f64>() / indices.len() as f64;
+
+        assert_eq!(0.0, average(&plain));
+        assert!((average(&dithered) - 64.0).abs() < 2.0);
+    }
+
+    #[rstest]
+    fn gradients_are_approximated(#[values(Quantizer::MedianCut, Quantizer::Octree)] quantizer: Quantizer) {
+        let rgb = gradient(64, 64);
+        let palette = quantizer.palette(&rgb, 64);
+        let indices = quantize(&rgb, 64, 64, &palette, Dithering::None);
+        let colors = palette.colors();
+        let max_error = indices
+            .iter()
+            .zip(rgb.chunks(3))
+            .flat_map(|(&index, pixel)| (0..3).map(move |i| (colors[index as usize][i] as i32 - pixel[i] as i32).abs()))
+            .max()
+            .unwrap();
+
+        assert!(max_error <= 32);
+    }
+}
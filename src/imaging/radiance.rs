@@ -0,0 +1,175 @@
+use std::io::Write;
+
+use super::{
+    color::Color,
+    image::{Image, WriteError},
+};
+use crate::math::Position;
+
+pub fn write_hdr(image: &Image, mut writer: impl Write) -> Result<(), WriteError> {
+    let width = image.width();
+    let height = image.height();
+
+    write!(writer, "#?RADIANCE\nFORMAT=32-bit_rle_rgbe\n\n-Y {height} +X {width}\n").map_err(WriteError::IOError)?;
+
+    for y in 0..height {
+        let scanline: Vec<[u8; 4]> = (0..width)
+            .map(|x| to_rgbe(image.get(Position::<2>::cartesian(x as i32, y as i32))))
+            .collect();
+
+        writer.write_all(&encode_scanline(&scanline)).map_err(WriteError::IOError)?;
+    }
+
+    Ok(())
+}
+
+// Shared exponent encoding: mantissas are scaled so that the largest component fits in a byte
+pub(super) fn to_rgbe(color: &Color) -> [u8; 4] {
+    let r = color.r().max(0.0);
+    let g = color.g().max(0.0);
+    let b = color.b().max(0.0);
+    let max = r.max(g).max(b);
+
+    if max < 1e-32 {
+        [0, 0, 0, 0]
+    } else {
+        let exponent = max.log2().floor() as i32 + 1;
+        let scale = 256.0 / 2f64.powi(exponent);
+        let mantissa = |c: f64| (c * scale).min(255.0) as u8;
+
+        [mantissa(r), mantissa(g), mantissa(b), (exponent + 128) as u8]
+    }
+}
+
+// New style RLE is only allowed for widths in [8, 32767]; other scanlines are stored flat
+fn encode_scanline(scanline: &[[u8; 4]]) -> Vec<u8> {
+    let width = scanline.len();
+
+    if !(8..=0x7fff).contains(&width) {
+        return scanline.iter().flatten().copied().collect();
+    }
+
+    let mut result = vec![2, 2, (width >> 8) as u8, (width & 0xff) as u8];
+
+    for component in 0..4 {
+        let values: Vec<u8> = scanline.iter().map(|rgbe| rgbe[component]).collect();
+        encode_runs(&values, &mut result);
+    }
+
+    result
+}
+
+fn encode_runs(values: &[u8], result: &mut Vec<u8>) {
+    const MIN_RUN: usize = 4;
+    const MAX_COUNT: usize = 127;
+
+    let mut literal_start = 0;
+    let mut i = 0;
+
+    while i < values.len() {
+        let run_length = values[i..].iter().take(MAX_COUNT).take_while(|&&v| v == values[i]).count();
+
+        if run_length >= MIN_RUN {
+            write_literals(&values[literal_start..i], result);
+            result.push(128 + run_length as u8);
+            result.push(values[i]);
+            i += run_length;
+            literal_start = i;
+        } else {
+            i += 1;
+        }
+    }
+
+    write_literals(&values[literal_start..], result);
+}
+
+fn write_literals(values: &[u8], result: &mut Vec<u8>) {
+    for chunk in values.chunks(128) {
+        result.push(chunk.len() as u8);
+        result.extend_from_slice(chunk);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    #[cfg(test)]
+    use super::*;
+
+    #[cfg(test)]
+    fn decode_runs(data: &[u8], count: usize) -> (Vec<u8>, usize) {
+        let mut result = Vec::new();
+        let mut i = 0;
+
+        while result.len() < count {
+            let code = data[i] as usize;
+
+            if code > 128 {
+                result.extend(std::iter::repeat_n(data[i + 1], code - 128));
+                i += 2;
+            } else {
+                result.extend_from_slice(&data[i + 1..i + 1 + code]);
+                i += 1 + code;
+            }
+        }
+
+        (result, i)
+    }
+
+    #[rstest]
+    #[case(Color::black(), [0, 0, 0, 0])]
+    #[case(Color::new(1.0, 0.5, 0.0), [128, 64, 0, 129])]
+    #[case(Color::new(0.5, 0.5, 0.5), [128, 128, 128, 128])]
+    #[case(Color::new(1000.0, 0.0, 0.0), [250, 0, 0, 138])]
+    fn rgbe_conversion(#[case] color: Color, #[case] expected: [u8; 4]) {
+        assert_eq!(expected, to_rgbe(&color));
+    }
+
+    #[rstest]
+    fn run_length_round_trip(#[values(0, 1, 5, 130, 300)] run: usize) {
+        let mut values: Vec<u8> = (0..10).collect();
+        values.extend(std::iter::repeat_n(42, run));
+        values.extend((0..200).map(|i| (i % 7) as u8));
+
+        let mut encoded = Vec::new();
+        encode_runs(&values, &mut encoded);
+        let (decoded, consumed) = decode_runs(&encoded, values.len());
+
+        assert_eq!(values, decoded);
+        assert_eq!(encoded.len(), consumed);
+    }
+
+    #[rstest]
+    fn header_and_scanlines() {
+        let mut image = Image::new(10, 2);
+        *image.get_mut(Position::<2>::cartesian(3, 1)) = Color::new(4.0, 2.0, 1.0);
+
+        let mut buffer = Vec::new();
+        write_hdr(&image, &mut buffer).unwrap();
+
+        let header = b"#?RADIANCE\nFORMAT=32-bit_rle_rgbe\n\n-Y 2 +X 10\n";
+        assert_eq!(header, &buffer[..header.len()]);
+
+        let mut data = &buffer[header.len()..];
+        let mut pixels = Vec::new();
+
+        for _ in 0..2 {
+            assert_eq!(&[2, 2, 0, 10], &data[..4]);
+            data = &data[4..];
+
+            let mut components = Vec::new();
+            for _ in 0..4 {
+                let (decoded, consumed) = decode_runs(data, 10);
+                components.push(decoded);
+                data = &data[consumed..];
+            }
+
+            pixels.extend((0..10).map(|x| [components[0][x], components[1][x], components[2][x], components[3][x]]));
+        }
+
+        assert!(data.is_empty());
+        assert_eq!([128, 64, 32, 131], pixels[13]);
+        assert_eq!([0, 0, 0, 0], pixels[12]);
+    }
+}
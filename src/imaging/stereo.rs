@@ -0,0 +1,158 @@
+use crate::math::Position;
+
+use super::{color::Color, image::Image};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum StereoLayout {
+    // Left eye on the left half, right eye on the right half
+    SideBySide,
+    // Left eye on the top half, right eye on the bottom half
+    OverUnder,
+    // Red channel from the left eye, green and blue channels from the right eye
+    Anaglyph,
+}
+
+impl StereoLayout {
+    pub fn composite_size(&self, width: u32, height: u32) -> (u32, u32) {
+        match self {
+            StereoLayout::SideBySide => (2 * width, height),
+            StereoLayout::OverUnder => (width, 2 * height),
+            StereoLayout::Anaglyph => (width, height),
+        }
+    }
+
+    // Alpha is kept if either eye has it; for anaglyphs the coverage of both eyes is merged
+    pub fn compose(&self, left: &Image, right: &Image) -> Image {
+        debug_assert!(left.width() == right.width() && left.height() == right.height());
+
+        let width = left.width();
+        let height = left.height();
+        let (composite_width, composite_height) = self.composite_size(width, height);
+        let mut result = Image::new(composite_width, composite_height);
+        let mut alpha = vec![0.0; (composite_width * composite_height) as usize];
+        let mut place = |x: u32, y: u32, color: Color, coverage: f64| {
+            *result.get_mut(Position::<2>::cartesian(x as i32, y as i32)) = color;
+            alpha[(y * composite_width + x) as usize] = coverage;
+        };
+
+        for y in 0..height {
+            for x in 0..width {
+                let position = Position::<2>::cartesian(x as i32, y as i32);
+                let left_color = *left.get(position);
+                let right_color = *right.get(position);
+                let left_alpha = left.alpha(position);
+                let right_alpha = right.alpha(position);
+
+                match self {
+                    StereoLayout::SideBySide => {
+                        place(x, y, left_color, left_alpha);
+                        place(x + width, y, right_color, right_alpha);
+                    }
+                    StereoLayout::OverUnder => {
+                        place(x, y, left_color, left_alpha);
+                        place(x, y + height, right_color, right_alpha);
+                    }
+                    StereoLayout::Anaglyph => {
+                        // Both eyes mostly cover the same surfaces, so the union is approximated by the larger coverage
+                        place(x, y, Color::new(left_color.r(), right_color.g(), right_color.b()), left_alpha.max(right_alpha));
+                    }
+                }
+            }
+        }
+
+        if left.has_alpha() || right.has_alpha() {
+            result.with_alpha(alpha)
+        } else {
+            result
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    #[cfg(test)]
+    use super::*;
+
+    fn filled(width: u32, height: u32, color: Color) -> Image {
+        let mut image = Image::new(width, height);
+
+        for y in 0..height {
+            for x in 0..width {
+                *image.get_mut(Position::<2>::cartesian(x as i32, y as i32)) = color;
+            }
+        }
+
+        image
+    }
+
+    fn at(image: &Image, x: i32, y: i32) -> Color {
+        *image.get(Position::<2>::cartesian(x, y))
+    }
+
+    #[rstest]
+    fn side_by_side() {
+        let left = filled(3, 2, Color::red());
+        let right = filled(3, 2, Color::blue());
+        let result = StereoLayout::SideBySide.compose(&left, &right);
+
+        assert_eq!((6, 2), (result.width(), result.height()));
+        assert_eq!(Color::red(), at(&result, 2, 1));
+        assert_eq!(Color::blue(), at(&result, 3, 0));
+        assert_eq!(Color::blue(), at(&result, 5, 1));
+    }
+
+    #[rstest]
+    fn over_under() {
+        let left = filled(3, 2, Color::red());
+        let right = filled(3, 2, Color::blue());
+        let result = StereoLayout::OverUnder.compose(&left, &right);
+
+        assert_eq!((3, 4), (result.width(), result.height()));
+        assert_eq!(Color::red(), at(&result, 2, 1));
+        assert_eq!(Color::blue(), at(&result, 0, 2));
+        assert_eq!(Color::blue(), at(&result, 2, 3));
+    }
+
+    #[rstest]
+    fn anaglyph() {
+        let left = filled(2, 2, Color::new(0.1, 0.2, 0.3));
+        let right = filled(2, 2, Color::new(0.4, 0.5, 0.6));
+        let result = StereoLayout::Anaglyph.compose(&left, &right);
+
+        assert_eq!((2, 2), (result.width(), result.height()));
+        assert_eq!(Color::new(0.1, 0.5, 0.6), at(&result, 1, 1));
+    }
+
+    #[rstest]
+    #[case(StereoLayout::SideBySide, (2, 0), 0.25)]
+    #[case(StereoLayout::OverUnder, (0, 1), 0.25)]
+    #[case(StereoLayout::Anaglyph, (0, 0), 0.75)]
+    fn alpha_is_kept(#[case] layout: StereoLayout, #[case] right_position: (i32, i32), #[case] expected_right: f64) {
+        let left = filled(2, 1, Color::red()).with_alpha(vec![0.75, 0.5]);
+        let right = filled(2, 1, Color::blue()).with_alpha(vec![0.25, 0.0]);
+        let result = layout.compose(&left, &right);
+
+        assert!(result.has_alpha());
+        assert_eq!(0.5, result.alpha(Position::<2>::cartesian(1, 0)));
+        assert_eq!(expected_right, result.alpha(Position::<2>::cartesian(right_position.0, right_position.1)));
+    }
+
+    #[rstest]
+    fn opaque_eyes_give_opaque_composite(#[values(StereoLayout::SideBySide, StereoLayout::OverUnder, StereoLayout::Anaglyph)] layout: StereoLayout) {
+        let result = layout.compose(&filled(2, 2, Color::red()), &filled(2, 2, Color::blue()));
+
+        assert!(!result.has_alpha());
+    }
+
+    #[rstest]
+    fn missing_alpha_counts_as_opaque() {
+        let left = filled(1, 1, Color::red());
+        let right = filled(1, 1, Color::blue()).with_alpha(vec![0.5]);
+        let result = StereoLayout::SideBySide.compose(&left, &right);
+
+        assert_eq!(1.0, result.alpha(Position::<2>::cartesian(0, 0)));
+        assert_eq!(0.5, result.alpha(Position::<2>::cartesian(1, 0)));
+    }
+}
@@ -0,0 +1,182 @@
+use super::color::Color;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ToneMapper {
+    Clamp,
+    Reinhard,
+    ExtendedReinhard { white_point: f64 },
+    AcesFilmic,
+    Hable { white_point: f64 },
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum TransferFunction {
+    Linear,
+    Srgb,
+    Rec709,
+}
+
+// Turns linear radiance into display values; exposure is expressed in stops
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct OutputTransform {
+    pub exposure: f64,
+    pub tone_mapper: ToneMapper,
+    pub transfer_function: TransferFunction,
+}
+
+impl OutputTransform {
+    pub fn linear() -> Self {
+        OutputTransform {
+            exposure: 0.0,
+            tone_mapper: ToneMapper::Clamp,
+            transfer_function: TransferFunction::Linear,
+        }
+    }
+
+    pub fn apply(&self, color: &Color) -> Color {
+        let exposed = *color * 2f64.powf(self.exposure);
+        let mapped = self.tone_mapper.apply(&exposed);
+        let encode = |c: f64| self.transfer_function.encode(c.clamp(0.0, 1.0));
+
+        Color::new(encode(mapped.r()), encode(mapped.g()), encode(mapped.b()))
+    }
+}
+
+impl ToneMapper {
+    pub fn apply(&self, color: &Color) -> Color {
+        match *self {
+            ToneMapper::Clamp => *color,
+            ToneMapper::Reinhard => scale_luminance(color, |l| l / (1.0 + l)),
+            ToneMapper::ExtendedReinhard { white_point } => {
+                scale_luminance(color, |l| l * (1.0 + l / (white_point * white_point)) / (1.0 + l))
+            }
+            ToneMapper::AcesFilmic => map_channels(color, aces_filmic),
+            ToneMapper::Hable { white_point } => {
+                let white_scale = hable(white_point).recip();
+                map_channels(color, |c| hable(c) * white_scale)
+            }
+        }
+    }
+}
+
+impl TransferFunction {
+    pub fn encode(&self, c: f64) -> f64 {
+        match self {
+            TransferFunction::Linear => c,
+            TransferFunction::Srgb => {
+                if c <= 0.0031308 {
+                    12.92 * c
+                } else {
+                    1.055 * c.powf(1.0 / 2.4) - 0.055
+                }
+            }
+            TransferFunction::Rec709 => {
+                if c < 0.018 {
+                    4.5 * c
+                } else {
+                    1.099 * c.powf(0.45) - 0.099
+                }
+            }
+        }
+    }
+}
+
+// Operators working on luminance keep the hue intact
+fn scale_luminance(color: &Color, operator: impl Fn(f64) -> f64) -> Color {
+    let l = color.luminance();
+
+    if l <= 0.0 {
+        Color::black()
+    } else {
+        *color * (operator(l) / l)
+    }
+}
+
+fn map_channels(color: &Color, operator: impl Fn(f64) -> f64) -> Color {
+    Color::new(operator(color.r().max(0.0)), operator(color.g().max(0.0)), operator(color.b().max(0.0)))
+}
+
+// Krzysztof Narkowicz's fit of the ACES reference rendering transform
+fn aces_filmic(x: f64) -> f64 {
+    (x * (2.51 * x + 0.03)) / (x * (2.43 * x + 0.59) + 0.14)
+}
+
+// John Hable's Uncharted 2 curve
+fn hable(x: f64) -> f64 {
+    const A: f64 = 0.15;
+    const B: f64 = 0.50;
+    const C: f64 = 0.10;
+    const D: f64 = 0.20;
+    const E: f64 = 0.02;
+    const F: f64 = 0.30;
+
+    (x * (A * x + C * B) + D * E) / (x * (A * x + B) + D * F) - E / F
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    #[cfg(test)]
+    use super::*;
+
+    #[cfg(test)]
+    use crate::math::approx;
+
+    #[rstest]
+    #[case(TransferFunction::Srgb, 0.0, 0.0)]
+    #[case(TransferFunction::Srgb, 0.002, 0.02584)]
+    #[case(TransferFunction::Srgb, 0.18, 0.46135)]
+    #[case(TransferFunction::Srgb, 1.0, 1.0)]
+    #[case(TransferFunction::Rec709, 0.01, 0.045)]
+    #[case(TransferFunction::Rec709, 0.18, 0.40901)]
+    #[case(TransferFunction::Rec709, 1.0, 1.0)]
+    fn transfer_functions(#[case] transfer_function: TransferFunction, #[case] linear: f64, #[case] expected: f64) {
+        assert!((transfer_function.encode(linear) - expected).abs() < 1e-4);
+    }
+
+    // Inputs stay below the white points; ACES and extended Reinhard overshoot 1 beyond those
+    #[rstest]
+    fn tone_mappers_are_monotonic_and_bounded(
+        #[values(ToneMapper::Reinhard, ToneMapper::ExtendedReinhard { white_point: 4.0 }, ToneMapper::AcesFilmic, ToneMapper::Hable { white_point: 11.2 })]
+        tone_mapper: ToneMapper,
+    ) {
+        let values: Vec<f64> = (0..100)
+            .map(|i| tone_mapper.apply(&(Color::white() * (i as f64 * 0.04))).g())
+            .collect();
+
+        assert_eq!(approx(0.0), values[0]);
+        assert!(values.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert!(values.iter().all(|&v| v <= 1.0 + 1e-6));
+    }
+
+    #[rstest]
+    #[case(ToneMapper::Reinhard, 1.0, 0.5)]
+    #[case(ToneMapper::ExtendedReinhard { white_point: 4.0 }, 4.0, 1.0)]
+    #[case(ToneMapper::Hable { white_point: 11.2 }, 11.2, 1.0)]
+    #[case(ToneMapper::AcesFilmic, 1.0, 0.80377)]
+    fn tone_mapper_values(#[case] tone_mapper: ToneMapper, #[case] input: f64, #[case] expected: f64) {
+        let actual = tone_mapper.apply(&(Color::white() * input));
+
+        assert!((actual.r() - expected).abs() < 1e-4);
+    }
+
+    #[rstest]
+    fn reinhard_preserves_hue() {
+        let actual = ToneMapper::Reinhard.apply(&Color::new(4.0, 2.0, 1.0));
+
+        assert_eq!(approx(2.0), actual.r() / actual.g());
+        assert_eq!(approx(2.0), actual.g() / actual.b());
+    }
+
+    #[rstest]
+    #[case(0.0, 0.25, 0.25)]
+    #[case(1.0, 0.25, 0.5)]
+    #[case(-2.0, 2.0, 0.5)]
+    #[case(0.0, 3.0, 1.0)]
+    fn exposure_before_clamp(#[case] exposure: f64, #[case] input: f64, #[case] expected: f64) {
+        let transform = OutputTransform { exposure, ..OutputTransform::linear() };
+
+        assert_eq!(approx(expected), transform.apply(&(Color::white() * input)).b());
+    }
+}
@@ -0,0 +1,230 @@
+use std::io::{BufWriter, Stdout, Write};
+
+use super::{export::FrameWriter, image::Image, tonemap::OutputTransform};
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ChromaSubsampling {
+    Yuv420,
+    Yuv444,
+}
+
+impl ChromaSubsampling {
+    fn tag(&self) -> &'static str {
+        match self {
+            ChromaSubsampling::Yuv420 => "420jpeg",
+            ChromaSubsampling::Yuv444 => "444",
+        }
+    }
+}
+
+pub struct Y4MWriterOptions {
+    pub width: u32,
+    pub height: u32,
+    pub frames_per_second: u32,
+    pub output_transform: OutputTransform,
+    pub chroma: ChromaSubsampling,
+}
+
+// YUV4MPEG2 stream with 8-bit BT.601 limited range samples, readable by e.g. ffmpeg -i -
+pub struct Y4MWriter<T: Write> {
+    writer: T,
+    output_transform: OutputTransform,
+    chroma: ChromaSubsampling,
+}
+
+impl<T: Write> Y4MWriter<T> {
+    pub fn new(mut writer: T, options: Y4MWriterOptions) -> Self {
+        let header = format!(
+            "YUV4MPEG2 W{} H{} F{}:1 Ip A1:1 C{} XCOLORRANGE=LIMITED\n",
+            options.width,
+            options.height,
+            options.frames_per_second,
+            options.chroma.tag()
+        );
+        writer.write_all(header.as_bytes()).expect("Failed to write Y4M header");
+
+        Y4MWriter {
+            writer,
+            output_transform: options.output_transform,
+            chroma: options.chroma,
+        }
+    }
+}
+
+impl Y4MWriter<BufWriter<Stdout>> {
+    pub fn to_stdout(options: Y4MWriterOptions) -> Self {
+        Self::new(BufWriter::new(std::io::stdout()), options)
+    }
+}
+
+impl<T: Write> FrameWriter for Y4MWriter<T> {
+    fn write_frame(&mut self, image: Image) {
+        let rgb = image.convert_to_display_rgb(&self.output_transform);
+        let (y, u, v) = rgb_to_yuv(&rgb, image.width(), image.height(), self.chroma);
+
+        self.writer.write_all(b"FRAME\n").expect("Failed to write frame");
+        for plane in [y, u, v] {
+            self.writer.write_all(&plane).expect("Failed to write frame");
+        }
+        self.writer.flush().expect("Failed to write frame");
+    }
+}
+
+// Headerless rgb24 frames; the reader needs to be told the size and rate, e.g. ffmpeg -f rawvideo -pix_fmt rgb24 -s WxH -r FPS -i -
+pub struct RawRGBWriter<T: Write> {
+    writer: T,
+    output_transform: OutputTransform,
+}
+
+impl<T: Write> RawRGBWriter<T> {
+    pub fn new(writer: T, output_transform: OutputTransform) -> Self {
+        RawRGBWriter { writer, output_transform }
+    }
+}
+
+impl RawRGBWriter<BufWriter<Stdout>> {
+    pub fn to_stdout(output_transform: OutputTransform) -> Self {
+        Self::new(BufWriter::new(std::io::stdout()), output_transform)
+    }
+}
+
+impl<T: Write> FrameWriter for RawRGBWriter<T> {
+    fn write_frame(&mut self, image: Image) {
+        let data = image.convert_to_display_rgb(&self.output_transform);
+
+        self.writer.write_all(&data).expect("Failed to write frame");
+        self.writer.flush().expect("Failed to write frame");
+    }
+}
+
+// Returns the Y, U and V planes; with 4:2:0, chroma is averaged over 2x2 blocks, rounding odd sizes up
+fn rgb_to_yuv(rgb: &[u8], width: u32, height: u32, chroma: ChromaSubsampling) -> (Vec<u8>, Vec<u8>, Vec<u8>) {
+    let pixel = |x: u32, y: u32| {
+        let index = (3 * (y * width + x)) as usize;
+        [rgb[index], rgb[index + 1], rgb[index + 2]].map(|c| c as f64 / 255.0)
+    };
+    let luma = |[r, g, b]: [f64; 3]| 0.299 * r + 0.587 * g + 0.114 * b;
+    let encode = |value: f64| value.round().clamp(0.0, 255.0) as u8;
+
+    let y_plane = (0..height)
+        .flat_map(|y| (0..width).map(move |x| (x, y)))
+        .map(|(x, y)| encode(16.0 + 219.0 * luma(pixel(x, y))))
+        .collect();
+
+    let (block_size, chroma_width, chroma_height) = match chroma {
+        ChromaSubsampling::Yuv420 => (2, width.div_ceil(2), height.div_ceil(2)),
+        ChromaSubsampling::Yuv444 => (1, width, height),
+    };
+    let mut u_plane = Vec::with_capacity((chroma_width * chroma_height) as usize);
+    let mut v_plane = Vec::with_capacity((chroma_width * chroma_height) as usize);
+
+    for cy in 0..chroma_height {
+        for cx in 0..chroma_width {
+            let (mut u, mut v, mut count) = (0.0, 0.0, 0.0);
+
+            for y in (cy * block_size)..((cy + 1) * block_size).min(height) {
+                for x in (cx * block_size)..((cx + 1) * block_size).min(width) {
+                    let color = pixel(x, y);
+                    let l = luma(color);
+
+                    u += (color[2] - l) / 1.772;
+                    v += (color[0] - l) / 1.402;
+                    count += 1.0;
+                }
+            }
+
+            u_plane.push(encode(128.0 + 224.0 * u / count));
+            v_plane.push(encode(128.0 + 224.0 * v / count));
+        }
+    }
+
+    (y_plane, u_plane, v_plane)
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    #[cfg(test)]
+    use super::*;
+
+    #[cfg(test)]
+    use crate::imaging::color::Color;
+
+    #[rstest]
+    #[case(Color::black(), [16, 128, 128])]
+    #[case(Color::white(), [235, 128, 128])]
+    #[case(Color::red(), [81, 90, 240])]
+    #[case(Color::green(), [145, 54, 34])]
+    #[case(Color::blue(), [41, 240, 110])]
+    fn conversion(#[case] color: Color, #[case] expected: [u8; 3], #[values(ChromaSubsampling::Yuv420, ChromaSubsampling::Yuv444)] chroma: ChromaSubsampling) {
+        let rgb = Image::from_fn(3, 3, |_, _| color).convert_to_display_rgb(&OutputTransform::linear());
+        let (y, u, v) = rgb_to_yuv(&rgb, 3, 3, chroma);
+
+        assert!(y.iter().all(|&value| value == expected[0]));
+        assert!(u.iter().all(|&value| value == expected[1]));
+        assert!(v.iter().all(|&value| value == expected[2]));
+    }
+
+    #[rstest]
+    #[case(ChromaSubsampling::Yuv420, 4, 3, 2, 2)]
+    #[case(ChromaSubsampling::Yuv420, 5, 5, 3, 3)]
+    #[case(ChromaSubsampling::Yuv444, 5, 3, 5, 3)]
+    fn plane_sizes(#[case] chroma: ChromaSubsampling, #[case] width: u32, #[case] height: u32, #[case] chroma_width: usize, #[case] chroma_height: usize) {
+        let rgb = vec![0; (3 * width * height) as usize];
+        let (y, u, v) = rgb_to_yuv(&rgb, width, height, chroma);
+
+        assert_eq!((width * height) as usize, y.len());
+        assert_eq!(chroma_width * chroma_height, u.len());
+        assert_eq!(chroma_width * chroma_height, v.len());
+    }
+
+    #[rstest]
+    fn chroma_is_averaged() {
+        let rgb = Image::from_fn(2, 2, |x, _| if x == 0 { Color::red() } else { Color::white() }).convert_to_display_rgb(&OutputTransform::linear());
+        let (y, u, v) = rgb_to_yuv(&rgb, 2, 2, ChromaSubsampling::Yuv420);
+
+        assert_eq!(vec![81, 235, 81, 235], y);
+        assert_eq!(vec![109], u);
+        assert_eq!(vec![184], v);
+    }
+
+    #[rstest]
+    fn y4m_stream() {
+        let options = Y4MWriterOptions {
+            width: 4,
+            height: 2,
+            frames_per_second: 24,
+            output_transform: OutputTransform::linear(),
+            chroma: ChromaSubsampling::Yuv420,
+        };
+        let mut buffer = Vec::new();
+
+        {
+            let mut writer = Y4MWriter::new(&mut buffer, options);
+            writer.write_frame(Image::new(4, 2));
+            writer.write_frame(Image::new(4, 2));
+        }
+
+        let header = b"YUV4MPEG2 W4 H2 F24:1 Ip A1:1 C420jpeg XCOLORRANGE=LIMITED\n";
+        let frame_size = 6 + 8 + 2 + 2;
+
+        assert_eq!(header.len() + 2 * frame_size, buffer.len());
+        assert_eq!(&header[..], &buffer[..header.len()]);
+        assert_eq!(b"FRAME\n", &buffer[header.len()..header.len() + 6]);
+        assert_eq!(b"FRAME\n", &buffer[header.len() + frame_size..header.len() + frame_size + 6]);
+    }
+
+    #[rstest]
+    fn raw_stream() {
+        let mut buffer = Vec::new();
+
+        {
+            let mut writer = RawRGBWriter::new(&mut buffer, OutputTransform::linear());
+            writer.write_frame(Image::from_fn(2, 1, |_, _| Color::red()));
+            writer.write_frame(Image::from_fn(2, 1, |_, _| Color::blue()));
+        }
+
+        assert_eq!(vec![255, 0, 0, 255, 0, 0, 0, 0, 255, 0, 0, 255], buffer);
+    }
+}
@@ -0,0 +1,83 @@
+use std::rc::Rc;
+
+use crate::{
+    animation::{Animation, Duration, TimeStamp},
+    imaging::color::Color,
+    math::Point,
+};
+
+use super::{light::LightSource, point::PointLight, sphere::SphereLight};
+
+pub struct AnimatedPointLight {
+    color: Box<dyn Animation<Color>>,
+    position: Box<dyn Animation<Point<3>>>,
+}
+
+impl AnimatedPointLight {
+    pub fn new(color: Box<dyn Animation<Color>>, position: Box<dyn Animation<Point<3>>>) -> Self {
+        AnimatedPointLight { color, position }
+    }
+}
+
+impl Animation<Rc<dyn LightSource>> for AnimatedPointLight {
+    fn at(&self, t: TimeStamp) -> Rc<dyn LightSource> {
+        Rc::new(PointLight::new(self.color.at(t), self.position.at(t)))
+    }
+
+    fn duration(&self) -> Duration {
+        self.color.duration().max(self.position.duration())
+    }
+}
+
+pub struct AnimatedSphereLight {
+    color: Box<dyn Animation<Color>>,
+    center: Box<dyn Animation<Point<3>>>,
+    radius: f64,
+}
+
+impl AnimatedSphereLight {
+    pub fn new(color: Box<dyn Animation<Color>>, center: Box<dyn Animation<Point<3>>>, radius: f64) -> Self {
+        AnimatedSphereLight { color, center, radius }
+    }
+}
+
+impl Animation<Rc<dyn LightSource>> for AnimatedSphereLight {
+    fn at(&self, t: TimeStamp) -> Rc<dyn LightSource> {
+        Rc::new(SphereLight::new(self.color.at(t), self.center.at(t), self.radius))
+    }
+
+    fn duration(&self) -> Duration {
+        self.color.duration().max(self.center.duration())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    #[cfg(test)]
+    use super::*;
+
+    #[cfg(test)]
+    use crate::{
+        animation::{ConstantAnimation, LinearAnimation},
+        math::{approx, pt},
+    };
+
+    #[rstest]
+    #[case(0.0, 1.0)]
+    #[case(1.0, 2.0)]
+    #[case(2.0, 3.0)]
+    fn point_light_position_follows_animation(#[case] t: f64, #[case] expected_distance: f64) {
+        let position = LinearAnimation {
+            start: pt!(0, 0, 1),
+            end: pt!(0, 0, 3),
+            duration: Duration::from_seconds(2.0),
+        };
+        let light = AnimatedPointLight::new(Box::new(ConstantAnimation::timeless(Color::white())), Box::new(position));
+        let sample = light.at(TimeStamp::new(t)).sample_towards(&pt!(0, 0, 0), &pt!(0.5, 0.5)).unwrap();
+
+        assert_eq!(2.0, light.duration().in_seconds());
+        assert_eq!(approx(expected_distance), sample.distance);
+    }
+}
@@ -1,10 +1,16 @@
 use crate::{
     imaging::color::Color,
-    math::{Matrix, Point, Ray},
+    math::{Matrix, Point, Ray, Vector},
 };
 
 pub trait LightSource {
     fn lightrays_to(&self, point: Point<3>) -> Box<dyn Iterator<Item = LightRay>>;
+
+    fn sample_towards(&self, point: &Point<3>, sample: &Point<2>) -> Option<LightSample>;
+
+    fn find_hit(&self, _ray: &Ray) -> Option<LightHit> {
+        None
+    }
 }
 
 pub struct LightRay {
@@ -12,6 +18,20 @@ pub struct LightRay {
     pub ray: Ray,
 }
 
+pub struct LightSample {
+    pub color: Color,
+    pub direction: Vector<3>,
+    pub distance: f64,
+    pub pdf: f64,
+    pub is_delta: bool,
+}
+
+pub struct LightHit {
+    pub t: f64,
+    pub color: Color,
+    pub pdf: f64,
+}
+
 impl LightRay {
     pub fn new(color: Color, ray: Ray) -> Self {
         LightRay { color, ray }
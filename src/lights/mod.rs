@@ -1,2 +1,4 @@
+pub mod animated;
 pub mod light;
 pub mod point;
+pub mod sphere;
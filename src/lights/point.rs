@@ -3,7 +3,7 @@ use crate::{
     math::{Point, Ray},
 };
 
-use super::light::{LightRay, LightSource};
+use super::light::{LightRay, LightSample, LightSource};
 
 pub struct PointLight {
     color: Color,
@@ -30,6 +30,23 @@ impl LightSource for PointLight {
 
         Box::new(iterator)
     }
+
+    fn sample_towards(&self, point: &Point<3>, _sample: &Point<2>) -> Option<LightSample> {
+        let delta = self.position - *point;
+        let distance = delta.norm();
+
+        if distance > 0.0 {
+            Some(LightSample {
+                color: self.color / (distance * distance),
+                direction: delta * distance.recip(),
+                distance,
+                pdf: 1.0,
+                is_delta: true,
+            })
+        } else {
+            None
+        }
+    }
 }
 
 impl Iterator for PointLightIterator {
@@ -39,3 +56,27 @@ impl Iterator for PointLightIterator {
         self.lightray.take()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    #[cfg(test)]
+    use super::*;
+
+    use crate::math::{approx, pt, vc, Vector};
+
+    #[rstest]
+    #[case(pt!(0, 0, 0), pt!(0, 0, 1), vc!(0, 0, 1), 1.0)]
+    #[case(pt!(0, 0, 0), pt!(0, 2, 0), vc!(0, 1, 0), 2.0)]
+    #[case(pt!(1, 1, 1), pt!(1, 1, -3), vc!(0, 0, -1), 4.0)]
+    fn sample_towards(#[case] point: Point<3>, #[case] position: Point<3>, #[case] expected_direction: Vector<3>, #[case] expected_distance: f64) {
+        let light = PointLight::new(Color::white(), position);
+        let sample = light.sample_towards(&point, &pt!(0.5, 0.5)).unwrap();
+
+        assert_eq!(approx(expected_direction), sample.direction);
+        assert_eq!(approx(expected_distance), sample.distance);
+        assert_eq!(approx(1.0 / expected_distance.powi(2)), sample.color.r());
+        assert!(sample.is_delta);
+    }
+}
@@ -0,0 +1,151 @@
+use crate::{
+    imaging::color::Color,
+    math::{Point, QuadraticEquation, Ray, Vector},
+    samplers::{UniformConeSampler, Warp},
+};
+
+use super::light::{LightHit, LightRay, LightSample, LightSource};
+
+pub struct SphereLight {
+    color: Color,
+    center: Point<3>,
+    radius: f64,
+}
+
+impl SphereLight {
+    pub fn new(color: Color, center: Point<3>, radius: f64) -> Self {
+        debug_assert!(radius > 0.0);
+
+        SphereLight {
+            color,
+            center,
+            radius,
+        }
+    }
+
+    fn cos_max_angle_from(&self, point: &Point<3>) -> Option<f64> {
+        let distance_sqr = (self.center - *point).norm_sqr();
+        let radius_sqr = self.radius * self.radius;
+
+        if distance_sqr <= radius_sqr {
+            None
+        } else {
+            Some((1.0 - radius_sqr / distance_sqr).max(0.0).sqrt())
+        }
+    }
+
+    fn find_t(&self, ray: &Ray) -> Option<f64> {
+        let delta = ray.origin - self.center;
+        let a = ray.direction.dot(&ray.direction);
+        let b = 2.0 * delta.dot(&ray.direction);
+        let c = delta.dot(&delta) - self.radius * self.radius;
+
+        match QuadraticEquation::new(a, b, c).solve() {
+            Some([t1, _]) if t1 > 0.0 => Some(t1),
+            Some([_, t2]) if t2 > 0.0 => Some(t2),
+            _ => None,
+        }
+    }
+}
+
+impl LightSource for SphereLight {
+    fn lightrays_to(&self, point: Point<3>) -> Box<dyn Iterator<Item = LightRay>> {
+        let ray = Ray::through(self.center, point);
+        let lightray = LightRay::new(self.color, ray);
+
+        Box::new(std::iter::once(lightray))
+    }
+
+    fn sample_towards(&self, point: &Point<3>, sample: &Point<2>) -> Option<LightSample> {
+        let cos_max_angle = self.cos_max_angle_from(point)?;
+        let w = (self.center - *point).normalized();
+        let (u, v) = w.orthonormal_basis();
+
+        let cone = UniformConeSampler::new(cos_max_angle);
+        let local = cone.warp(sample);
+        let direction: Vector<3> = u * local.x() + v * local.y() + w * local.z();
+
+        let distance = {
+            let ray = Ray::new(*point, direction);
+            self.find_t(&ray).unwrap_or_else(|| (self.center - *point).dot(&direction))
+        };
+
+        Some(LightSample {
+            color: self.color,
+            direction,
+            distance,
+            pdf: cone.pdf(&Vector::z_axis()),
+            is_delta: false,
+        })
+    }
+
+    fn find_hit(&self, ray: &Ray) -> Option<LightHit> {
+        let t = self.find_t(ray)?;
+        let cos_max_angle = self.cos_max_angle_from(&ray.origin)?;
+
+        Some(LightHit {
+            t,
+            color: self.color,
+            pdf: UniformConeSampler::new(cos_max_angle).pdf(&Vector::z_axis()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    #[cfg(test)]
+    use super::*;
+
+    use std::f64::consts::PI;
+
+    use crate::math::{approx, pt, vc};
+
+    #[rstest]
+    fn samples_hit_light(#[values(0.0, 0.25, 0.5, 0.99)] x: f64, #[values(0.0, 0.3, 0.7)] y: f64) {
+        let light = SphereLight::new(Color::white(), pt!(0, 0, 5), 1.0);
+        let point = pt!(0, 0, 0);
+        let sample = light.sample_towards(&point, &pt!(x, y)).unwrap();
+        let ray = Ray::new(point, sample.direction);
+        let hit = light.find_hit(&ray).unwrap();
+
+        assert!(sample.direction.is_unit());
+        assert_eq!(approx(sample.distance), hit.t);
+        assert_eq!(approx(sample.pdf), hit.pdf);
+    }
+
+    #[rstest]
+    fn center_sample_points_at_center() {
+        let light = SphereLight::new(Color::white(), pt!(3, 0, 0), 1.0);
+        let sample = light.sample_towards(&pt!(0, 0, 0), &pt!(0, 0)).unwrap();
+
+        assert_eq!(approx(vc!(1, 0, 0)), sample.direction);
+        assert_eq!(approx(2.0), sample.distance);
+    }
+
+    #[rstest]
+    fn pdf_is_inverse_of_subtended_solid_angle() {
+        let light = SphereLight::new(Color::white(), pt!(0, 2, 0), 1.0);
+        let sample = light.sample_towards(&pt!(0, 0, 0), &pt!(0.5, 0.5)).unwrap();
+        let cos_max_angle = 3.0f64.sqrt() / 2.0;
+        let solid_angle = 2.0 * PI * (1.0 - cos_max_angle);
+
+        assert_eq!(approx(1.0 / solid_angle), sample.pdf);
+    }
+
+    #[rstest]
+    fn no_sample_from_inside() {
+        let light = SphereLight::new(Color::white(), pt!(0, 0, 0), 1.0);
+
+        assert!(light.sample_towards(&pt!(0.5, 0, 0), &pt!(0.5, 0.5)).is_none());
+    }
+
+    #[rstest]
+    fn miss() {
+        let light = SphereLight::new(Color::white(), pt!(0, 0, 5), 1.0);
+        let ray = Ray::new(pt!(0, 0, 0), vc!(1, 0, 0));
+
+        assert!(light.find_hit(&ray).is_none());
+    }
+}
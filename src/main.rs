@@ -7,56 +7,133 @@ mod materials;
 mod math;
 mod primitives;
 mod regex;
+mod rendering;
 mod samplers;
 mod scripting;
 mod tracing;
 mod util;
 
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
 use std::rc::Rc;
 
-use animation::{Animation, LinearAnimation, Duration, TimeStamp, TimeLine};
-use cameras::perspective::{PerspectiveCamera, PerspectiveCameraParameters};
-use imaging::{PNGWriter, PNGWriterOptions};
+use animation::{Animation, ConstantAnimation, Duration, Easing, Interpolation, Keyframe, KeyframeTrack, LinearAnimation, TimeStamp, TimeLine};
+use cameras::camera::Camera;
+use cameras::cubemap::{CubeMapCamera, CubeMapCameraParameters};
+use cameras::equirectangular::{EquirectangularCamera, EquirectangularCameraParameters};
+use cameras::fov::{self, FieldOfView};
+use cameras::fisheye::{FisheyeCamera, FisheyeCameraParameters, FisheyeProjection};
+use cameras::orthographic::{OrthographicCamera, OrthographicCameraParameters};
+use cameras::perspective::{ApertureShape, PerspectiveCamera, PerspectiveCameraParameters};
+use cameras::stereo::{ConvergenceMethod, Eye, StereoRig};
+use imaging::{FrameWriter, PNGSequenceWriter, PNGWriter, PNGWriterOptions};
 use imaging::color::Color;
-use imaging::image::Image;
-use lights::{light::LightSource, point::PointLight};
-use materials::{UniformMaterial};
-use math::{transformation3d::Transformation3D, Position, Rasterizer, Rectangle};
-use primitives::{Primitive, Transformer, Union, PlaneXY, Sphere, Decorator};
-use samplers::{Sampler2D, StratifiedSampler2D};
-use tracing::raytracer::RayTracer;
+use imaging::denoise::{Denoiser, EdgeStopping};
+use imaging::exr::ExrCompression;
+use imaging::gif_writer::{GIFWriter, GIFWriterOptions};
+use imaging::quantize::{Dithering, Quantizer};
+use imaging::stereo::StereoLayout;
+use imaging::tonemap::{OutputTransform, ToneMapper, TransferFunction};
+use imaging::video::{ChromaSubsampling, RawRGBWriter, Y4MWriter, Y4MWriterOptions};
+use lights::{animated::AnimatedPointLight, light::LightSource};
+use materials::AnimatedUniformMaterial;
+use math::Angle;
+use math::transformation3d::Transformation3D;
+use primitives::{AnimatedDecorator, AnimatedTagger, AnimatedTransformer, AnimatedUnion, IdTag, Primitive, Transformer, PlaneXY, Sphere};
+use rendering::{AdaptiveSampling, ReconstructionFilter, RenderSettings, RenderedFrame, Renderer, SamplingMode, Shutter, StereoRenderer};
+use samplers::{HaltonSampler2D, JitteredSampler2D, MultiJitteredSampler2D, RandomSampler2D, Sampler2D, SobolSampler2D, StratifiedSampler2D};
+use tracing::integrator::Integrator;
+use tracing::pathtracer::PathTracer;
 use tracing::scene::Scene;
+use tracing::whitted::WhittedIntegrator;
 
 use crate::materials::{MaterialProperties, Material};
 
 
-struct TestScene { }
+struct TestScene {
+    camera_kind: String,
+    width: u32,
+    height: u32,
+    eye: Option<Eye>,
+    root: Box<dyn Animation<Rc<dyn Primitive>>>,
+    light_sources: Vec<Box<dyn Animation<Rc<dyn LightSource>>>>,
+}
 
 impl TestScene {
-    fn new() -> Self {
-        TestScene { }
+    fn new(camera_kind: &str, width: u32, height: u32, eye: Option<Eye>) -> Self {
+        TestScene {
+            camera_kind: String::from(camera_kind),
+            width,
+            height,
+            eye,
+            root: Self::create_root(),
+            light_sources: Self::create_light_sources(),
+        }
     }
 
-    fn create_camera(t: TimeStamp) -> PerspectiveCamera {
-        let eye_x = LinearAnimation {
-            start: -2.0,
-            end: 2.0,
-            duration: Duration::from_seconds(1.0)
-        }.at(t);
-
-        let camera_parameters = PerspectiveCameraParameters {
-            aspect_ratio: 1.0,
-            distance_to_screen: 1.0,
-            eye: pt!(eye_x, 5, 10),
-            look_at: pt!(0, 0, 0),
-            up: vc!(0, 1, 0),
-        };
+    fn create_perspective_camera(&self, parameters: &PerspectiveCameraParameters) -> Box<dyn Camera> {
+        match self.eye {
+            None => Box::new(PerspectiveCamera::new(parameters)),
+            Some(eye) => {
+                let rig = StereoRig {
+                    interocular_distance: 0.3,
+                    convergence_distance: 10.0,
+                    method: ConvergenceMethod::OffAxis,
+                };
+
+                Box::new(rig.camera(parameters, eye))
+            }
+        }
+    }
+
+    fn create_camera(&self, t: TimeStamp) -> Box<dyn Camera> {
+        let eye = KeyframeTrack::new(vec![
+            Keyframe::new(TimeStamp::zero(), pt!(-2, 5, 10), Interpolation::Linear).eased(Easing::CubicInOut),
+            Keyframe::new(TimeStamp::new(1.0), pt!(2, 5, 10), Interpolation::Linear),
+        ]).at(t);
+        let look_at = pt!(0, 0, 0);
+        let up = vc!(0, 1, 0);
+        let aspect_ratio = fov::aspect_ratio(self.width, self.height);
+        let field_of_view = FieldOfView::Vertical(Angle::degrees(53.13));
+
+        if self.eye.is_some() && !matches!(self.camera_kind.as_str(), "perspective" | "thinlens") {
+            panic!("Stereo rendering requires a perspective camera");
+        }
 
-        PerspectiveCamera::new(&camera_parameters)
+        match self.camera_kind.as_str() {
+            "perspective" => self.create_perspective_camera(&PerspectiveCameraParameters::new(eye, look_at, up, field_of_view, self.width, self.height)),
+            "thinlens" => {
+                let mut parameters = PerspectiveCameraParameters::new(eye, pt!(-2, 0, 3), up, field_of_view, self.width, self.height);
+                parameters.aperture_radius = 0.3;
+                parameters.aperture_shape = ApertureShape::Polygonal { blades: 6, rotation: Angle::degrees(0.0) };
+                parameters.autofocus(self.root.at(t).as_ref());
+
+                self.create_perspective_camera(&parameters)
+            }
+            "orthographic" => Box::new(OrthographicCamera::new(&OrthographicCameraParameters {
+                eye,
+                look_at,
+                up,
+                view_height: 10.0,
+                aspect_ratio,
+            })),
+            "fisheye" => Box::new(FisheyeCamera::new(&FisheyeCameraParameters {
+                eye,
+                look_at,
+                up,
+                aspect_ratio,
+                field_of_view: Angle::degrees(180.0),
+                projection: FisheyeProjection::Equisolid,
+            })),
+            "equirectangular" => Box::new(EquirectangularCamera::new(&EquirectangularCameraParameters { eye, look_at, up })),
+            "cubemap" => Box::new(CubeMapCamera::new(&CubeMapCameraParameters { eye, look_at, up })),
+            _ => panic!("Unknown camera {}", self.camera_kind),
+        }
     }
 
-    fn create_root() -> Rc<dyn Primitive> {
-        fn create_material(color: Color) -> Rc<dyn Material> {
+    fn create_root() -> Box<dyn Animation<Rc<dyn Primitive>>> {
+        fn create_material(color: Color) -> Box<dyn Animation<Rc<dyn Material>>> {
             let material_properties = MaterialProperties {
                 diffuse: color,
                 specular_color: Color::black(),
@@ -65,44 +142,51 @@ impl TestScene {
                 reflection: Color::white() * 0.1,
             };
 
-            Rc::new(UniformMaterial::new(material_properties))
+            Box::new(AnimatedUniformMaterial::new(Box::new(ConstantAnimation::timeless(material_properties))))
+        }
+
+        fn still(primitive: Rc<dyn Primitive>) -> Box<dyn Animation<Rc<dyn Primitive>>> {
+            Box::new(ConstantAnimation::timeless(primitive))
         }
 
         let plane = Rc::new(PlaneXY::new());
-        let sphere = Rc::new(Sphere::new());
+        let sphere: Rc<dyn Primitive> = Rc::new(Sphere::new());
 
-        let background = plane;
-        let left_sphere = Rc::new(Transformer::new(
+        let background = still(plane);
+        let left_sphere = still(Rc::new(Transformer::new(
             Transformation3D::translate(&vc!(-2, 0, 3)),
             sphere.clone(),
-        ));
-        let right_sphere = Rc::new(Transformer::new(
-            Transformation3D::translate(&vc!(1, 0, 5)),
-            sphere,
-        ));
+        )));
+        let right_sphere = {
+            let bounce = LinearAnimation {
+                start: Transformation3D::translate(&vc!(1, 0, 5)),
+                end: Transformation3D::translate(&vc!(1, 1, 5)),
+                duration: Duration::from_seconds(0.5),
+            };
+
+            Box::new(AnimatedTransformer::new(Box::new(bounce.ping_pong()), still(sphere)))
+        };
 
         let red_material = create_material(Color::red());
-        let green_material = create_material(Color::green());
         let blue_material = create_material(Color::blue());
         let white_material = create_material(Color::white());
 
-        let background = Rc::new(Decorator::new(white_material.clone(), background));
-        let left_sphere = Rc::new(Decorator::new(red_material, left_sphere));
-        let right_sphere = Rc::new(Decorator::new(blue_material, right_sphere));
+        let background = Box::new(AnimatedTagger::new(IdTag::Object(3), Box::new(AnimatedDecorator::new(white_material, background))));
+        let left_sphere = Box::new(AnimatedTagger::new(IdTag::Object(1), Box::new(AnimatedDecorator::new(red_material, left_sphere))));
+        let right_sphere = Box::new(AnimatedTagger::new(IdTag::Object(2), Box::new(AnimatedDecorator::new(blue_material, right_sphere))));
 
-        let union = Union::new(vec![
+        Box::new(AnimatedUnion::new(vec![
             left_sphere,
             right_sphere,
             background,
-        ]);
-
-        Rc::new(union)
+        ]))
     }
 
-    fn create_light_sources() -> Vec<Rc<dyn LightSource>> {
-        let light = Rc::new(PointLight::new(Color::white(), pt!(0, 5, 1)));
+    fn create_light_sources() -> Vec<Box<dyn Animation<Rc<dyn LightSource>>>> {
+        let color = Box::new(ConstantAnimation::timeless(Color::white()));
+        let position = Box::new(ConstantAnimation::timeless(pt!(0, 5, 1)));
 
-        vec![light]
+        vec![Box::new(AnimatedPointLight::new(color, position))]
     }
 }
 
@@ -112,9 +196,9 @@ impl Animation<Scene> for TestScene {
     }
 
     fn at(&self, t: TimeStamp) -> Scene {
-        let camera = Self::create_camera(t);
-        let root = Self::create_root();
-        let light_sources = Self::create_light_sources();
+        let camera = self.create_camera(t);
+        let root = self.root.at(t);
+        let light_sources = self.light_sources.iter().map(|light| light.at(t)).collect();
 
         Scene {
             camera,
@@ -124,91 +208,187 @@ impl Animation<Scene> for TestScene {
     }
 }
 
-struct Renderer {
-    width: u32,
-    height: u32,
-    scene: Box<dyn Animation<Scene>>,
+fn create_integrator(name: &str) -> Rc<dyn Integrator> {
+    match name {
+        "whitted" => Rc::new(WhittedIntegrator::new()),
+        "path" => Rc::new(PathTracer::new(16, 3)),
+        _ => panic!("Unknown integrator {name}"),
+    }
 }
 
-impl Renderer {
-    fn new(width: u32, height: u32, scene: Box<dyn Animation<Scene>>) -> Self {
-        Renderer {
-            width,
-            height,
-            scene,
-        }
+fn create_sampler(name: &str, seed: u64) -> Rc<dyn Sampler2D> {
+    match name {
+        "stratified" => Rc::new(StratifiedSampler2D::new()),
+        "random" => Rc::new(RandomSampler2D::new(seed)),
+        "jittered" => Rc::new(JitteredSampler2D::new(seed)),
+        "multijittered" => Rc::new(MultiJitteredSampler2D::new(seed, 4, 4)),
+        "halton" => Rc::new(HaltonSampler2D::new(seed)),
+        "sobol" => Rc::new(SobolSampler2D::new(seed)),
+        _ => panic!("Unknown sampler {name}"),
     }
+}
 
-    fn create_rasterizer(&self) -> Rasterizer<2> {
-        let rectangle = Rectangle::new(pt!(0, 0), vc!(1, 0), vc!(0, 1));
+fn camera_aspect_ratio(name: &str) -> f64 {
+    match name {
+        "equirectangular" => 2.0,
+        "cubemap" => 1.5,
+        _ => 1.0,
+    }
+}
 
-        Rasterizer::new(rectangle, self.width, self.height)
+fn create_stereo_layout(name: &str) -> Option<StereoLayout> {
+    match name {
+        "mono" => None,
+        "sidebyside" => Some(StereoLayout::SideBySide),
+        "overunder" => Some(StereoLayout::OverUnder),
+        "anaglyph" => Some(StereoLayout::Anaglyph),
+        _ => panic!("Unknown stereo layout {name}"),
     }
+}
 
-    fn create_sampler(&self) -> impl Sampler2D {
-        StratifiedSampler2D::new()
+fn create_denoiser(name: &str) -> Option<Denoiser> {
+    let edges = EdgeStopping::default();
+
+    match name {
+        "none" => None,
+        "bilateral" => Some(Denoiser::CrossBilateral { radius: 5, sigma_spatial: 3.0, edges }),
+        "atrous" => Some(Denoiser::ATrous { iterations: 5, edges }),
+        "nlm" => Some(Denoiser::NonLocalMeans { search_radius: 7, patch_radius: 2, strength: 0.2, edges }),
+        _ => panic!("Unknown denoiser {name}"),
     }
+}
 
-    fn render_frame(&self, t: TimeStamp) -> Image {
-        let width = self.width;
-        let height = self.height;
-        let mut image = Image::new(width, height);
-
-        let rasterizer = self.create_rasterizer();
-        let sampler = self.create_sampler();
-        let scene = self.scene.at(t);
-        let ray_tracer = Rc::new(RayTracer::new(scene));
-        let sample_count = 1;
-
-        for y in 0..height {
-            for x in 0..width {
-                let position = Position::<2>::cartesian(x as i32, y as i32);
-                let pixel = rasterizer.at(position);
-                let mut accumulated_color = Color::black();
-                let mut samples = sampler.sample(pixel);
-
-                for _ in 0..sample_count {
-                    let sample = samples.current();
-                    let camera_rays = ray_tracer.scene.camera.enumerate_rays(sample);
-
-                    for ray in camera_rays {
-                        let trace_result = ray_tracer.trace(&ray);
-                        accumulated_color += &trace_result.color;
-                    }
-
-                    samples.refine();
-                }
+fn create_frame_writer(name: &str, width: u32, height: u32, timeline: &TimeLine, output_transform: OutputTransform) -> Box<dyn FrameWriter> {
+    let frames_per_second = timeline.frames_per_second();
+
+    match name {
+        "apng" => {
+            let options = PNGWriterOptions {
+                width,
+                height,
+                frame_count: timeline.frame_count(),
+                frames_per_second,
+                output_transform,
+                alpha: false,
+            };
 
-                accumulated_color /= sample_count as f64;
-                *image.get_mut(position) = accumulated_color;
-            }
+            Box::new(PNGWriter::to_file("movie.png", options))
         }
+        "gif" => {
+            let options = GIFWriterOptions {
+                width,
+                height,
+                frames_per_second,
+                output_transform,
+                quantizer: Quantizer::MedianCut,
+                dithering: Dithering::FloydSteinberg,
+            };
+
+            Box::new(GIFWriter::to_file("movie.gif", options))
+        }
+        "sequence" => Box::new(PNGSequenceWriter::new("frame", output_transform, false)),
+        "y4m" | "y4m444" => {
+            let chroma = if name == "y4m" { ChromaSubsampling::Yuv420 } else { ChromaSubsampling::Yuv444 };
+            let options = Y4MWriterOptions {
+                width,
+                height,
+                frames_per_second,
+                output_transform,
+                chroma,
+            };
+
+            Box::new(Y4MWriter::to_stdout(options))
+        }
+        "raw" => Box::new(RawRGBWriter::to_stdout(output_transform)),
+        _ => panic!("Unknown output {name}"),
+    }
+}
 
-        image
+fn create_diagnostics(name: &str) -> bool {
+    match name {
+        "nodiagnostics" => false,
+        "diagnostics" => true,
+        _ => panic!("Unknown diagnostics setting {name}"),
     }
 }
 
+// Auxiliary files written next to the regular output for every frame
+fn write_diagnostics(idx: u32, frame: &RenderedFrame) {
+    let heatmap_path = format!("samples{idx}.png");
+    frame.sample_counts.heatmap().write_to_file(Path::new(&heatmap_path)).expect("Failed to write sample heatmap");
+    let aov_file = BufWriter::new(File::create(format!("aovs{idx}.exr")).expect("Failed to create AOV file"));
+    frame.aovs.write_exr(&frame.image, aov_file, ExrCompression::Zip).expect("Failed to write AOVs");
+}
+
 fn main() {
-    let width = 500;
+    let integrator_name = std::env::args().nth(1).unwrap_or_else(|| String::from("whitted"));
+    let sampler_name = std::env::args().nth(2).unwrap_or_else(|| String::from("stratified"));
+    let camera_name = std::env::args().nth(3).unwrap_or_else(|| String::from("perspective"));
+    let stereo_layout = create_stereo_layout(&std::env::args().nth(4).unwrap_or_else(|| String::from("mono")));
+    let denoiser = create_denoiser(&std::env::args().nth(5).unwrap_or_else(|| String::from("none")));
+    let output_name = std::env::args().nth(6).unwrap_or_else(|| String::from("apng"));
+    let diagnostics = create_diagnostics(&std::env::args().nth(7).unwrap_or_else(|| String::from("nodiagnostics")));
     let height = 500;
+    let width = (height as f64 * camera_aspect_ratio(&camera_name)) as u32;
     let frames_per_second = 1;
-    let path = "movie.png";
-    let scene = Box::new(TestScene::new());
+    let scene = TestScene::new(&camera_name, width, height, None);
     let timeline = TimeLine::new(scene.duration(), frames_per_second);
-    let renderer = Renderer::new(width, height, scene);
-    let mut png_writer = {
-        let png_options = PNGWriterOptions {
-            width,
-            height,
-            frame_count: timeline.frame_count(),
-        };
-
-        PNGWriter::to_file(path, png_options)
+    let sampling = SamplingMode::Adaptive(AdaptiveSampling {
+        min_samples: 4,
+        max_samples: 64,
+        variance_threshold: 0.0001,
+    });
+    let settings = RenderSettings {
+        width,
+        height,
+        sampling,
+        sampler: create_sampler(&sampler_name, 0),
+        integrator: create_integrator(&integrator_name),
+        shutter: Shutter {
+            interval: Duration::from_seconds(0.1),
+            time_slices: 4,
+        },
+        filter: ReconstructionFilter::mitchell(2.0),
     };
-
-    for (idx, t) in timeline.iter() {
-        println!("Rendering frame {idx}");
-        let image = renderer.render_frame(t);
-        png_writer.write_frame(image);
+    let (output_width, output_height) = match stereo_layout {
+        None => (width, height),
+        Some(layout) => layout.composite_size(width, height),
+    };
+    let output_transform = OutputTransform {
+        exposure: 0.0,
+        tone_mapper: ToneMapper::AcesFilmic,
+        transfer_function: TransferFunction::Srgb,
+    };
+    let mut frame_writer = create_frame_writer(&output_name, output_width, output_height, &timeline, output_transform);
+
+    match stereo_layout {
+        None => {
+            let renderer = Renderer::new(settings, Box::new(scene));
+
+            for (idx, t) in timeline.iter() {
+                // Progress goes to stderr so that frames can be streamed to stdout
+                eprintln!("Rendering frame {idx}");
+                let frame = renderer.render_frame(t);
+                eprintln!("Took {} samples", frame.sample_counts.total());
+                if diagnostics {
+                    write_diagnostics(idx, &frame);
+                }
+                let image = match denoiser {
+                    Some(denoiser) => denoiser.apply(&frame.image, &frame.aovs.guides()),
+                    None => frame.image,
+                };
+                frame_writer.write_frame(image);
+            }
+        }
+        Some(layout) => {
+            let left = Box::new(TestScene::new(&camera_name, width, height, Some(Eye::Left)));
+            let right = Box::new(TestScene::new(&camera_name, width, height, Some(Eye::Right)));
+            let renderer = StereoRenderer::new(settings, left, right, layout);
+
+            for (idx, t) in timeline.iter() {
+                eprintln!("Rendering frame {idx}");
+                frame_writer.write_frame(renderer.render_frame(t));
+            }
+        }
     }
 }
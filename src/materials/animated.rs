@@ -0,0 +1,64 @@
+use std::rc::Rc;
+
+use crate::animation::{Animation, Duration, TimeStamp};
+
+use super::{material::{Material, MaterialProperties}, uniform::UniformMaterial};
+
+pub struct AnimatedUniformMaterial {
+    properties: Box<dyn Animation<MaterialProperties>>,
+}
+
+impl AnimatedUniformMaterial {
+    pub fn new(properties: Box<dyn Animation<MaterialProperties>>) -> Self {
+        AnimatedUniformMaterial { properties }
+    }
+}
+
+impl Animation<Rc<dyn Material>> for AnimatedUniformMaterial {
+    fn at(&self, t: TimeStamp) -> Rc<dyn Material> {
+        Rc::new(UniformMaterial::new(self.properties.at(t)))
+    }
+
+    fn duration(&self) -> Duration {
+        self.properties.duration()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    #[cfg(test)]
+    use super::*;
+
+    #[cfg(test)]
+    use crate::{
+        animation::LinearAnimation,
+        imaging::color::Color,
+        math::{approx, pt},
+        primitives::LocalPosition,
+    };
+
+    #[rstest]
+    #[case(0.0, 0.0)]
+    #[case(0.5, 0.5)]
+    #[case(1.0, 1.0)]
+    fn diffuse_color_follows_animation(#[case] t: f64, #[case] expected: f64) {
+        let diffuse = LinearAnimation {
+            start: Color::black(),
+            end: Color::white(),
+            duration: Duration::from_seconds(1.0),
+        };
+        let properties = diffuse.map(|diffuse| MaterialProperties {
+            diffuse,
+            reflection: Color::black(),
+            specular_exponent: 1.0,
+            specular_color: Color::black(),
+            brdf: None,
+        });
+        let material = AnimatedUniformMaterial::new(Box::new(properties)).at(TimeStamp::new(t));
+        let local_position = LocalPosition { xyz: pt!(0, 0, 0), uv: pt!(0, 0) };
+
+        assert_eq!(approx(expected), material.at(local_position).diffuse.g());
+    }
+}
@@ -1,3 +1,4 @@
+mod animated;
 mod material;
 mod uniform;
 // mod reflective;
@@ -5,6 +6,7 @@ mod uniform;
 // mod transform;
 pub mod brdf;
 
+pub use animated::AnimatedUniformMaterial;
 pub use material::{Material, MaterialProperties};
 pub use uniform::UniformMaterial;
 // pub use reflective::ReflectiveMaterial;
@@ -39,6 +39,10 @@ impl Angle {
     pub fn cos(&self) -> f64 {
         self.in_radians().cos()
     }
+
+    pub fn tan(&self) -> f64 {
+        self.in_radians().tan()
+    }
 }
 
 impl std::ops::Add for Angle {
@@ -17,8 +17,31 @@ impl CoordinateSystem3D {
 
     pub fn as_transformation(&self) -> Transformation3D {
         let matrix = Matrix::<4, 4>::from_coordinate_system(&self.origin, &self.x_axis, &self.y_axis, &self.z_axis);
-        let inverse_matrix = matrix.transpose();
 
-        Transformation3D { matrix, inverse_matrix }
+        Transformation3D::from_matrix(matrix).expect("Coordinate system axes must be linearly independent")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    #[cfg(test)]
+    use super::*;
+
+    #[cfg(test)]
+    use crate::math::{approx, pt, vc};
+
+    #[rstest]
+    #[case(pt!(0, 0, 0), vc!(1, 0, 0), vc!(0, 1, 0), vc!(0, 0, 1))]
+    #[case(pt!(1, 2, 3), vc!(0, 1, 0), vc!(-1, 0, 0), vc!(0, 0, 1))]
+    #[case(pt!(1, 2, 3), vc!(2, 0, 0), vc!(1, 1, 0), vc!(0, 1, 3))]
+    fn as_transformation_inverse(#[case] origin: Point<3>, #[case] x_axis: Vector<3>, #[case] y_axis: Vector<3>, #[case] z_axis: Vector<3>) {
+        let coordinate_system = CoordinateSystem3D { origin, x_axis, y_axis, z_axis };
+        let transformation = coordinate_system.as_transformation();
+
+        assert_eq!(approx(Matrix::identity()), &transformation.matrix * &transformation.inverse_matrix);
+        assert_eq!(approx(pt!(0, 0, 0)), &transformation.inverse_matrix * &origin);
+        assert_eq!(approx(vc!(0, 0, 1)), &transformation.inverse_matrix * &z_axis);
     }
 }
@@ -0,0 +1,50 @@
+use std::f64::consts::PI;
+
+// Normalized sinc, sin(pi x) / (pi x)
+pub fn sinc(x: f64) -> f64 {
+    if x.abs() < 1e-8 {
+        1.0
+    } else {
+        (PI * x).sin() / (PI * x)
+    }
+}
+
+// Sinc windowed by a stretched sinc, zero beyond the radius
+pub fn lanczos(x: f64, radius: f64) -> f64 {
+    if x.abs() < radius {
+        sinc(x) * sinc(x / radius)
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    #[cfg(test)]
+    use super::*;
+
+    #[cfg(test)]
+    use crate::math::approx;
+
+    #[rstest]
+    #[case(0.0, 1.0)]
+    #[case(1.0, 0.0)]
+    #[case(-2.0, 0.0)]
+    #[case(0.5, 2.0 / PI)]
+    #[case(-0.5, 2.0 / PI)]
+    fn sinc_values(#[case] x: f64, #[case] expected: f64) {
+        assert_eq!(approx(expected), sinc(x));
+    }
+
+    #[rstest]
+    #[case(0.0, 2.0, 1.0)]
+    #[case(0.5, 2.0, 4.0 * 2f64.sqrt() / (PI * PI))]
+    #[case(-0.5, 2.0, 4.0 * 2f64.sqrt() / (PI * PI))]
+    #[case(2.0, 2.0, 0.0)]
+    #[case(2.5, 2.0, 0.0)]
+    fn lanczos_values(#[case] x: f64, #[case] radius: f64, #[case] expected: f64) {
+        assert_eq!(approx(expected), lanczos(x, radius));
+    }
+}
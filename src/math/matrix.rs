@@ -14,6 +14,10 @@ pub struct Matrix<const R: usize, const C: usize> {
 }
 
 impl<const R: usize, const C: usize> Matrix<R, C> {
+    pub fn new(m: [[f64; C]; R]) -> Self {
+        Matrix { m }
+    }
+
     pub fn zero() -> Self {
         let m = [[0.0; C]; R];
 
@@ -31,6 +35,10 @@ impl<const R: usize, const C: usize> Matrix<R, C> {
 
         result
     }
+
+    pub fn at(&self, row: usize, column: usize) -> f64 {
+        self.m[row][column]
+    }
 }
 
 impl<const N: usize> Matrix<N, N> {
@@ -43,6 +51,75 @@ impl<const N: usize> Matrix<N, N> {
 
         result
     }
+
+    pub fn determinant(&self) -> f64 {
+        match self.lu_decomposition() {
+            Some((lu, _, sign)) => (0..N).fold(sign, |product, i| product * lu.m[i][i]),
+            None => 0.0,
+        }
+    }
+
+    pub fn inverse(&self) -> Option<Self> {
+        let (lu, permutation, _) = self.lu_decomposition()?;
+        let mut result = Matrix::<N, N>::zero();
+
+        // Solve LU x = P e_col for each column of the identity
+        for col in 0..N {
+            let mut x = [0.0; N];
+
+            for i in 0..N {
+                let rhs = if permutation[i] == col { 1.0 } else { 0.0 };
+                x[i] = rhs - (0..i).map(|j| lu.m[i][j] * x[j]).sum::<f64>();
+            }
+
+            for i in (0..N).rev() {
+                x[i] = (x[i] - (i + 1..N).map(|j| lu.m[i][j] * x[j]).sum::<f64>()) / lu.m[i][i];
+            }
+
+            for (row, value) in x.iter().enumerate() {
+                result.m[row][col] = *value;
+            }
+        }
+
+        Some(result)
+    }
+
+    // Doolittle decomposition with partial pivoting, packed in a single matrix (L has an implicit unit diagonal).
+    // Also returns the row permutation and its sign; None if the matrix is singular
+    fn lu_decomposition(&self) -> Option<(Self, [usize; N], f64)> {
+        let mut lu = *self;
+        let mut permutation = [0; N];
+        let mut sign = 1.0;
+
+        for (i, p) in permutation.iter_mut().enumerate() {
+            *p = i;
+        }
+
+        for k in 0..N {
+            let pivot = (k..N).max_by(|&a, &b| lu.m[a][k].abs().total_cmp(&lu.m[b][k].abs()))?;
+
+            if lu.m[pivot][k].abs() < 1e-12 {
+                return None;
+            }
+
+            if pivot != k {
+                lu.m.swap(pivot, k);
+                permutation.swap(pivot, k);
+                sign = -sign;
+            }
+
+            for i in k + 1..N {
+                let factor = lu.m[i][k] / lu.m[k][k];
+                lu.m[i][k] = factor;
+
+                for j in k + 1..N {
+                    lu.m[i][j] -= factor * lu.m[k][j];
+                }
+            }
+        }
+
+        Some((lu, permutation, sign))
+    }
 }
 
 impl Matrix<4, 4> {
@@ -80,6 +157,16 @@ impl Matrix<4, 4> {
         Matrix::from_coordinate_system(&origin, &x_axis, &y_axis, &z_axis)
     }
 
+    // Each factor adds a multiple of one coordinate to another, e.g. xy adds xy * y to x
+    pub fn shear(xy: f64, xz: f64, yx: f64, yz: f64, zx: f64, zy: f64) -> Self {
+        Matrix::new([
+            [1.0, xy, xz, 0.0],
+            [yx, 1.0, yz, 0.0],
+            [zx, zy, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
     pub fn rotate_around_x(angle: Angle) -> Self {
         let s = angle.sin();
         let c = angle.cos();
@@ -337,4 +424,60 @@ mod tests {
 
         assert_eq!(approx(scaled_expected), actual);
     }
+
+    #[rstest]
+    #[case(Matrix::new([[2.0, 0.0], [0.0, 3.0]]), 6.0)]
+    #[case(Matrix::new([[0.0, 1.0], [1.0, 0.0]]), -1.0)]
+    #[case(Matrix::new([[1.0, 2.0], [2.0, 4.0]]), 0.0)]
+    #[case(Matrix::new([[4.0, 3.0], [6.0, 3.0]]), -6.0)]
+    fn determinant_2x2(#[case] matrix: Matrix<2, 2>, #[case] expected: f64) {
+        assert_eq!(approx(expected), matrix.determinant());
+    }
+
+    #[rstest]
+    fn determinant_3x3() {
+        let matrix = Matrix::new([[2.0, -3.0, 1.0], [2.0, 0.0, -1.0], [1.0, 4.0, 5.0]]);
+
+        assert_eq!(approx(49.0), matrix.determinant());
+    }
+
+    #[rstest]
+    #[case(Matrix::scale(2.0, 3.0, 4.0), 24.0)]
+    #[case(Matrix::rotate_around_y(Angle::degrees(33.0)), 1.0)]
+    #[case(Matrix::translate(&vc!(1, 2, 3)), 1.0)]
+    #[case(Matrix::scale(-1.0, 1.0, 1.0), -1.0)]
+    #[case(Matrix::shear(1.0, 2.0, 0.0, 0.5, 0.0, 0.0), 1.0)]
+    fn determinant_4x4(#[case] matrix: Matrix<4, 4>, #[case] expected: f64) {
+        assert_eq!(approx(expected), matrix.determinant());
+    }
+
+    #[rstest]
+    #[case(Matrix::scale(2.0, 3.0, 4.0))]
+    #[case(Matrix::translate(&vc!(1, -2, 3)))]
+    #[case(&Matrix::rotate_around_x(Angle::degrees(30.0)) * &Matrix::translate(&vc!(1, 5, 3)))]
+    #[case(Matrix::shear(1.0, 2.0, 0.3, 0.5, -1.0, 0.7))]
+    #[case(Matrix::new([[0.0, 2.0, 0.0, 1.0], [1.0, 0.0, 0.0, 0.0], [0.0, 0.0, 3.0, 0.0], [0.0, 1.0, 0.0, 1.0]]))]
+    fn inverse_4x4(#[case] matrix: Matrix<4, 4>) {
+        let inverse = matrix.inverse().unwrap();
+
+        assert_eq!(approx(Matrix::identity()), &matrix * &inverse);
+        assert_eq!(approx(Matrix::identity()), &inverse * &matrix);
+    }
+
+    #[rstest]
+    fn singular_matrix_has_no_inverse() {
+        let matrix = Matrix::scale(1.0, 0.0, 1.0);
+
+        assert!(matrix.inverse().is_none());
+        assert_eq!(approx(0.0), matrix.determinant());
+    }
+
+    #[rstest]
+    #[case(pt!(0, 1, 0), pt!(2, 1, 0))]
+    #[case(pt!(1, 1, 1), pt!(3, 1, 1))]
+    fn shear_point(#[case] p: Point<3>, #[case] expected: Point<3>) {
+        let matrix = Matrix::shear(2.0, 0.0, 0.0, 0.0, 0.0, 0.0);
+
+        assert_eq!(approx(expected), &matrix * &p);
+    }
 }
@@ -3,11 +3,13 @@ mod approx;
 pub mod coords;
 mod coordsys;
 mod interval;
+mod lanczos;
 mod matrix;
 mod metric;
 mod point;
 mod position;
 mod quadratic;
+mod quaternion;
 mod rasterizer;
 mod ray;
 mod rectangle;
@@ -18,11 +20,13 @@ pub use angle::Angle;
 pub use approx::approx;
 pub use coordsys::CoordinateSystem3D;
 pub use interval::{Interval, IntervalMapper};
+pub use lanczos::lanczos;
 pub use matrix::Matrix;
 pub use metric::Metric;
 pub use point::{pt, Point};
 pub use position::Position;
 pub use quadratic::QuadraticEquation;
+pub use quaternion::Quaternion;
 pub use rasterizer::Rasterizer;
 pub use ray::Ray;
 pub use rectangle::Rectangle;
@@ -0,0 +1,279 @@
+use std::ops::Mul;
+
+use super::{Angle, Matrix, Point, Vector};
+
+#[derive(Debug, Clone, Copy)]
+pub struct Quaternion {
+    pub w: f64,
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Quaternion {
+    pub fn new(w: f64, x: f64, y: f64, z: f64) -> Self {
+        Quaternion { w, x, y, z }
+    }
+
+    pub fn identity() -> Self {
+        Quaternion::new(1.0, 0.0, 0.0, 0.0)
+    }
+
+    pub fn from_axis_angle(axis: &Vector<3>, angle: Angle) -> Self {
+        let axis = axis.normalized();
+        let half = angle * 0.5;
+        let s = half.sin();
+
+        Quaternion::new(half.cos(), axis.x() * s, axis.y() * s, axis.z() * s)
+    }
+
+    // For the identity rotation the axis is arbitrary; x is returned
+    pub fn to_axis_angle(self) -> (Vector<3>, Angle) {
+        let q = self.normalized();
+        let q = if q.w < 0.0 { Quaternion::new(-q.w, -q.x, -q.y, -q.z) } else { q };
+        let axis = Vector::new([q.x, q.y, q.z]);
+        let sin_half = axis.norm();
+
+        if sin_half < 1e-12 {
+            (Vector::<3>::x_axis(), Angle::radians(0.0))
+        } else {
+            (axis * sin_half.recip(), Angle::radians(2.0 * sin_half.atan2(q.w)))
+        }
+    }
+
+    // Rotates around x first, then y, then z
+    pub fn from_euler(x: Angle, y: Angle, z: Angle) -> Self {
+        let qx = Quaternion::from_axis_angle(&Vector::<3>::x_axis(), x);
+        let qy = Quaternion::from_axis_angle(&Vector::<3>::y_axis(), y);
+        let qz = Quaternion::from_axis_angle(&Vector::<3>::z_axis(), z);
+
+        qz * qy * qx
+    }
+
+    pub fn to_euler(self) -> (Angle, Angle, Angle) {
+        let m = self.to_rotation_matrix();
+        let sin_y = (-m.at(2, 0)).clamp(-1.0, 1.0);
+
+        // Gimbal lock: only the sum (or difference) of the x and z angles is determined
+        if sin_y.abs() > 1.0 - 1e-9 {
+            let x = Angle::radians(0.0);
+            let y = Angle::radians(sin_y.asin());
+            let z = Angle::radians((-m.at(0, 1)).atan2(m.at(1, 1)));
+
+            (x, y, z)
+        } else {
+            let x = Angle::radians(m.at(2, 1).atan2(m.at(2, 2)));
+            let y = Angle::radians(sin_y.asin());
+            let z = Angle::radians(m.at(1, 0).atan2(m.at(0, 0)));
+
+            (x, y, z)
+        }
+    }
+
+    // Expects the upper left 3x3 block to be a proper rotation
+    pub fn from_rotation_matrix(matrix: &Matrix<4, 4>) -> Self {
+        let m = |r, c| matrix.at(r, c);
+        let trace = m(0, 0) + m(1, 1) + m(2, 2);
+
+        let q = if trace > 0.0 {
+            let s = (trace + 1.0).sqrt() * 2.0;
+            Quaternion::new(0.25 * s, (m(2, 1) - m(1, 2)) / s, (m(0, 2) - m(2, 0)) / s, (m(1, 0) - m(0, 1)) / s)
+        } else if m(0, 0) > m(1, 1) && m(0, 0) > m(2, 2) {
+            let s = (1.0 + m(0, 0) - m(1, 1) - m(2, 2)).sqrt() * 2.0;
+            Quaternion::new((m(2, 1) - m(1, 2)) / s, 0.25 * s, (m(0, 1) + m(1, 0)) / s, (m(0, 2) + m(2, 0)) / s)
+        } else if m(1, 1) > m(2, 2) {
+            let s = (1.0 + m(1, 1) - m(0, 0) - m(2, 2)).sqrt() * 2.0;
+            Quaternion::new((m(0, 2) - m(2, 0)) / s, (m(0, 1) + m(1, 0)) / s, 0.25 * s, (m(1, 2) + m(2, 1)) / s)
+        } else {
+            let s = (1.0 + m(2, 2) - m(0, 0) - m(1, 1)).sqrt() * 2.0;
+            Quaternion::new((m(1, 0) - m(0, 1)) / s, (m(0, 2) + m(2, 0)) / s, (m(1, 2) + m(2, 1)) / s, 0.25 * s)
+        };
+
+        q.normalized()
+    }
+
+    pub fn to_rotation_matrix(self) -> Matrix<4, 4> {
+        let Quaternion { w, x, y, z } = self.normalized();
+
+        let origin = Point::zero();
+        let x_axis = Vector::new([1.0 - 2.0 * (y * y + z * z), 2.0 * (x * y + w * z), 2.0 * (x * z - w * y)]);
+        let y_axis = Vector::new([2.0 * (x * y - w * z), 1.0 - 2.0 * (x * x + z * z), 2.0 * (y * z + w * x)]);
+        let z_axis = Vector::new([2.0 * (x * z + w * y), 2.0 * (y * z - w * x), 1.0 - 2.0 * (x * x + y * y)]);
+
+        Matrix::from_coordinate_system(&origin, &x_axis, &y_axis, &z_axis)
+    }
+
+    pub fn conjugate(&self) -> Self {
+        Quaternion::new(self.w, -self.x, -self.y, -self.z)
+    }
+
+    pub fn rotate(&self, v: &Vector<3>) -> Vector<3> {
+        let q = self.normalized();
+        let p = Quaternion::new(0.0, v.x(), v.y(), v.z());
+        let r = q * p * q.conjugate();
+
+        Vector::new([r.x, r.y, r.z])
+    }
+
+    pub fn dot(&self, other: &Quaternion) -> f64 {
+        self.w * other.w + self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    pub fn norm(&self) -> f64 {
+        self.dot(self).sqrt()
+    }
+
+    pub fn normalized(&self) -> Self {
+        let norm = self.norm();
+
+        Quaternion::new(self.w / norm, self.x / norm, self.y / norm, self.z / norm)
+    }
+
+    pub fn slerp(&self, other: &Quaternion, t: f64) -> Quaternion {
+        let mut cos_theta = self.dot(other);
+        let mut other = *other;
+
+        // q and -q are the same rotation; take the shortest arc
+        if cos_theta < 0.0 {
+            cos_theta = -cos_theta;
+            other = Quaternion::new(-other.w, -other.x, -other.y, -other.z);
+        }
+
+        let (a, b) = if cos_theta > 0.9995 {
+            (1.0 - t, t)
+        } else {
+            let theta = cos_theta.acos();
+            let sin_theta = theta.sin();
+            (((1.0 - t) * theta).sin() / sin_theta, (t * theta).sin() / sin_theta)
+        };
+
+        Quaternion::new(
+            a * self.w + b * other.w,
+            a * self.x + b * other.x,
+            a * self.y + b * other.y,
+            a * self.z + b * other.z,
+        )
+        .normalized()
+    }
+}
+
+// Composition: (a * b) applies b first, then a
+impl Mul for Quaternion {
+    type Output = Quaternion;
+
+    fn mul(self, rhs: Quaternion) -> Self::Output {
+        Quaternion::new(
+            self.w * rhs.w - self.x * rhs.x - self.y * rhs.y - self.z * rhs.z,
+            self.w * rhs.x + self.x * rhs.w + self.y * rhs.z - self.z * rhs.y,
+            self.w * rhs.y - self.x * rhs.z + self.y * rhs.w + self.z * rhs.x,
+            self.w * rhs.z + self.x * rhs.y - self.y * rhs.x + self.z * rhs.w,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    #[cfg(test)]
+    use super::*;
+
+    #[cfg(test)]
+    use crate::math::{approx::approx, vc};
+
+    #[rstest]
+    fn rotation_matrix_round_trip(
+        #[values(0.0, 30.0, 90.0, 179.0, 180.0, 270.0)] degrees: f64,
+        #[values(0, 1, 2)] axis: usize,
+    ) {
+        let angle = Angle::degrees(degrees);
+        let matrix = match axis {
+            0 => Matrix::rotate_around_x(angle),
+            1 => Matrix::rotate_around_y(angle),
+            _ => Matrix::rotate_around_z(angle),
+        };
+        let actual = Quaternion::from_rotation_matrix(&matrix).to_rotation_matrix();
+
+        assert_eq!(approx(matrix), actual);
+    }
+
+    #[rstest]
+    fn slerp_halfway(#[values(0.0, 40.0, 90.0, 170.0)] degrees: f64) {
+        let start = Quaternion::identity();
+        let end = Quaternion::from_rotation_matrix(&Matrix::rotate_around_z(Angle::degrees(degrees)));
+        let expected = Matrix::rotate_around_z(Angle::degrees(degrees / 2.0));
+        let actual = start.slerp(&end, 0.5).to_rotation_matrix();
+
+        assert_eq!(approx(expected), actual);
+    }
+
+    #[rstest]
+    fn slerp_takes_shortest_arc() {
+        let start = Quaternion::from_rotation_matrix(&Matrix::rotate_around_y(Angle::degrees(170.0)));
+        let end = Quaternion::from_rotation_matrix(&Matrix::rotate_around_y(Angle::degrees(-170.0)));
+        let expected = Matrix::rotate_around_y(Angle::degrees(180.0));
+        let actual = start.slerp(&end, 0.5).to_rotation_matrix();
+
+        assert_eq!(approx(expected), actual);
+    }
+
+    #[rstest]
+    fn axis_angle_matches_axis_rotations(#[values(0.0, 30.0, 90.0, 135.0, 270.0)] degrees: f64) {
+        let angle = Angle::degrees(degrees);
+
+        assert_eq!(approx(Matrix::rotate_around_x(angle)), Quaternion::from_axis_angle(&vc!(1, 0, 0), angle).to_rotation_matrix());
+        assert_eq!(approx(Matrix::rotate_around_y(angle)), Quaternion::from_axis_angle(&vc!(0, 2, 0), angle).to_rotation_matrix());
+        assert_eq!(approx(Matrix::rotate_around_z(angle)), Quaternion::from_axis_angle(&vc!(0, 0, 1), angle).to_rotation_matrix());
+    }
+
+    #[rstest]
+    #[case(vc!(1, 0, 0), 30.0)]
+    #[case(vc!(1, 1, 0), 120.0)]
+    #[case(vc!(-1, 2, 3), 170.0)]
+    fn axis_angle_round_trip(#[case] axis: Vector<3>, #[case] degrees: f64) {
+        let (actual_axis, actual_angle) = Quaternion::from_axis_angle(&axis, Angle::degrees(degrees)).to_axis_angle();
+
+        assert_eq!(approx(axis.normalized()), actual_axis);
+        assert_eq!(approx(degrees), actual_angle.in_degrees());
+    }
+
+    #[rstest]
+    #[case(10.0, 20.0, 30.0)]
+    #[case(-45.0, 60.0, 170.0)]
+    #[case(90.0, -30.0, 0.0)]
+    fn euler_round_trip(#[case] x: f64, #[case] y: f64, #[case] z: f64) {
+        let q = Quaternion::from_euler(Angle::degrees(x), Angle::degrees(y), Angle::degrees(z));
+        let (ax, ay, az) = q.to_euler();
+
+        assert_eq!(approx(x), ax.in_degrees());
+        assert_eq!(approx(y), ay.in_degrees());
+        assert_eq!(approx(z), az.in_degrees());
+    }
+
+    #[rstest]
+    fn euler_applies_x_then_y_then_z() {
+        let (x, y, z) = (Angle::degrees(10.0), Angle::degrees(20.0), Angle::degrees(30.0));
+        let expected = &(&Matrix::rotate_around_z(z) * &Matrix::rotate_around_y(y)) * &Matrix::rotate_around_x(x);
+
+        assert_eq!(approx(expected), Quaternion::from_euler(x, y, z).to_rotation_matrix());
+    }
+
+    #[rstest]
+    fn composition_matches_matrix_product() {
+        let a = Quaternion::from_axis_angle(&vc!(1, 2, 0), Angle::degrees(40.0));
+        let b = Quaternion::from_axis_angle(&vc!(0, -1, 1), Angle::degrees(75.0));
+        let expected = &a.to_rotation_matrix() * &b.to_rotation_matrix();
+
+        assert_eq!(approx(expected), (a * b).to_rotation_matrix());
+    }
+
+    #[rstest]
+    #[case(vc!(1, 0, 0), vc!(0, 1, 0))]
+    #[case(vc!(0, 1, 0), vc!(-1, 0, 0))]
+    #[case(vc!(0, 0, 1), vc!(0, 0, 1))]
+    fn rotate_vector(#[case] v: Vector<3>, #[case] expected: Vector<3>) {
+        let q = Quaternion::from_axis_angle(&vc!(0, 0, 1), Angle::degrees(90.0));
+
+        assert_eq!(approx(expected), q.rotate(&v));
+    }
+}
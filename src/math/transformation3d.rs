@@ -1,4 +1,4 @@
-use super::{angle::Angle, Matrix, Vector};
+use super::{angle::Angle, approx::Approx, Matrix, Point, Quaternion, Vector};
 
 #[derive(Copy, Clone)]
 pub struct Transformation3D {
@@ -27,6 +27,22 @@ impl Transformation3D {
         }
     }
 
+    // None if the shear factors make the matrix singular
+    pub fn shear(xy: f64, xz: f64, yx: f64, yz: f64, zx: f64, zy: f64) -> Option<Transformation3D> {
+        Transformation3D::from_matrix(Matrix::shear(xy, xz, yx, yz, zx, zy))
+    }
+
+    // None if the matrix is singular or its inverse is too inaccurate to be usable
+    pub fn from_matrix(matrix: Matrix<4, 4>) -> Option<Transformation3D> {
+        let inverse_matrix = matrix.inverse()?;
+
+        if !(&matrix * &inverse_matrix).approx_eps(&Matrix::identity(), 1e-6) {
+            return None;
+        }
+
+        Some(Transformation3D { matrix, inverse_matrix })
+    }
+
     pub fn rotate_around_x(angle: Angle) -> Transformation3D {
         let matrix = Matrix::rotate_around_x(angle);
         let inverse_matrix = Matrix::rotate_around_x(-angle);
@@ -57,12 +73,75 @@ impl Transformation3D {
         }
     }
 
+    pub fn rotate_around(axis: &Vector<3>, angle: Angle) -> Transformation3D {
+        Transformation3D::rotate(&Quaternion::from_axis_angle(axis, angle))
+    }
+
+    pub fn rotate(rotation: &Quaternion) -> Transformation3D {
+        let matrix = rotation.to_rotation_matrix();
+        let inverse_matrix = matrix.transpose();
+
+        Transformation3D {
+            matrix,
+            inverse_matrix,
+        }
+    }
+
+    // Places the origin at position with the local z-axis pointing towards target and the y-axis as close to up as possible
+    pub fn look_at(position: &Point<3>, target: &Point<3>, up: &Vector<3>) -> Transformation3D {
+        let z_axis = (*target - *position).normalized();
+        let x_axis = up.cross(&z_axis).normalized();
+        let y_axis = z_axis.cross(&x_axis);
+
+        let rotation = Matrix::from_coordinate_system(&Point::zero(), &x_axis, &y_axis, &z_axis);
+        let position = *position - Point::zero();
+
+        Transformation3D::translate(&position).compose(&Transformation3D::rotate(&Quaternion::from_rotation_matrix(&rotation)))
+    }
+
     pub fn compose(&self, other: &Transformation3D) -> Transformation3D {
         let matrix = &self.matrix * &other.matrix;
         let inverse_matrix = &other.inverse_matrix * &self.inverse_matrix;
 
         Transformation3D { matrix, inverse_matrix }
     }
+
+    // Splits the affine part into translation * rotation * scale, dropping any shear
+    pub fn decompose(&self) -> Decomposition {
+        let m = &self.matrix;
+        let column = |c| Vector::new([m.at(0, c), m.at(1, c), m.at(2, c)]);
+        let (c0, c1, c2) = (column(0), column(1), column(2));
+
+        let x_axis = c0.normalized();
+        let y_axis = (c1 - x_axis * c1.dot(&x_axis)).normalized();
+        let z_axis = x_axis.cross(&y_axis);
+
+        // A negative determinant shows up as a mirrored z axis
+        let scale = Vector::new([c0.norm(), c1.dot(&y_axis), c2.dot(&z_axis)]);
+        let rotation = Matrix::from_coordinate_system(&Point::zero(), &x_axis, &y_axis, &z_axis);
+
+        Decomposition {
+            translation: column(3),
+            rotation: Quaternion::from_rotation_matrix(&rotation),
+            scale,
+        }
+    }
+
+    pub fn from_decomposition(decomposition: &Decomposition) -> Transformation3D {
+        let Decomposition { translation, rotation, scale } = decomposition;
+        let translate = Transformation3D::translate(translation);
+        let scale = Transformation3D::scale(scale.x(), scale.y(), scale.z());
+        let rotate = Transformation3D::rotate(rotation);
+
+        translate.compose(&rotate).compose(&scale)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Decomposition {
+    pub translation: Vector<3>,
+    pub rotation: Quaternion,
+    pub scale: Vector<3>,
 }
 
 #[cfg(test)]
@@ -73,7 +152,7 @@ mod tests {
     use super::*;
 
     #[cfg(test)]
-    use crate::math::{approx::approx, vc};
+    use crate::math::{approx::approx, pt, vc};
 
     #[rstest]
     #[case(vc!(0, 0, 0))]
@@ -134,4 +213,69 @@ mod tests {
             &transformation.matrix * &transformation.inverse_matrix
         );
     }
+
+    #[rstest]
+    #[case(Transformation3D::translate(&vc!(1, 2, 3)))]
+    #[case(Transformation3D::scale(2.0, 3.0, 4.0))]
+    #[case(Transformation3D::scale(-1.0, 1.0, 1.0))]
+    #[case(Transformation3D::rotate_around_x(Angle::degrees(30.0)))]
+    #[case(Transformation3D::translate(&vc!(1, -2, 3)).compose(&Transformation3D::rotate_around_y(Angle::degrees(120.0))).compose(&Transformation3D::scale(2.0, 0.5, 3.0)))]
+    fn decomposition_round_trip(#[case] transformation: Transformation3D) {
+        let actual = Transformation3D::from_decomposition(&transformation.decompose());
+
+        assert_eq!(approx(transformation.matrix), actual.matrix);
+        assert_eq!(approx(Matrix::identity()), &actual.matrix * &actual.inverse_matrix);
+    }
+
+    #[rstest]
+    #[case(vc!(1, 1, 1), 120.0, vc!(1, 0, 0), vc!(0, 1, 0))]
+    #[case(vc!(0, 0, 1), 90.0, vc!(1, 0, 0), vc!(0, 1, 0))]
+    #[case(vc!(1, 1, 0), 180.0, vc!(1, 0, 0), vc!(0, 1, 0))]
+    fn rotate_around_axis(#[case] axis: Vector<3>, #[case] degrees: f64, #[case] v: Vector<3>, #[case] expected: Vector<3>) {
+        let transformation = Transformation3D::rotate_around(&axis, Angle::degrees(degrees));
+
+        assert_eq!(approx(expected), &transformation.matrix * &v);
+        assert_eq!(approx(axis), &transformation.matrix * &axis);
+        assert_eq!(approx(Matrix::identity()), &transformation.matrix * &transformation.inverse_matrix);
+    }
+
+    #[rstest]
+    #[case(pt!(0, 0, 0), pt!(1, 0, 0), vc!(0, 1, 0))]
+    #[case(pt!(1, 2, 3), pt!(1, 2, -3), vc!(0, 1, 0))]
+    #[case(pt!(1, 2, 3), pt!(4, -1, 0), vc!(0, 0, 1))]
+    fn look_at(#[case] position: Point<3>, #[case] target: Point<3>, #[case] up: Vector<3>) {
+        let transformation = Transformation3D::look_at(&position, &target, &up);
+        let z_axis = &transformation.matrix * &Vector::<3>::z_axis();
+        let y_axis = &transformation.matrix * &Vector::<3>::y_axis();
+
+        assert_eq!(approx(position), &transformation.matrix * &Point::zero());
+        assert_eq!(approx((target - position).normalized()), z_axis);
+        assert!(y_axis.dot(&up) > 0.0);
+        assert_eq!(approx(Matrix::identity()), &transformation.matrix * &transformation.inverse_matrix);
+    }
+
+    #[rstest]
+    fn shear_inverse_matrix(#[values(-1.0, 0.0, 0.5, 2.0)] factor: f64) {
+        let transformation = Transformation3D::shear(factor, 0.0, 0.0, factor, 0.0, 0.0).unwrap();
+
+        assert_eq!(approx(Matrix::identity()), &transformation.matrix * &transformation.inverse_matrix);
+    }
+
+    #[rstest]
+    fn shear_rejects_singular_matrix() {
+        assert!(Transformation3D::shear(1.0, 0.0, 1.0, 0.0, 0.0, 0.0).is_none());
+    }
+
+    #[rstest]
+    fn from_matrix_rejects_singular_matrix() {
+        assert!(Transformation3D::from_matrix(Matrix::scale(1.0, 1.0, 0.0)).is_none());
+    }
+
+    #[rstest]
+    fn from_matrix_matches_constructors() {
+        let expected = Transformation3D::translate(&vc!(1, 2, 3)).compose(&Transformation3D::rotate_around_z(Angle::degrees(40.0)));
+        let actual = Transformation3D::from_matrix(expected.matrix).unwrap();
+
+        assert_eq!(approx(expected.inverse_matrix), actual.inverse_matrix);
+    }
 }
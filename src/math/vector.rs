@@ -126,6 +126,19 @@ impl Vector<3> {
 
         result
     }
+
+    // Duff et al., "Building an Orthonormal Basis, Revisited"
+    pub fn orthonormal_basis(&self) -> (Self, Self) {
+        debug_assert!(self.is_unit());
+
+        let sign = 1.0f64.copysign(self.z());
+        let a = -1.0 / (sign + self.z());
+        let b = self.x() * self.y() * a;
+        let u = vc!(1.0 + sign * self.x() * self.x() * a, sign * b, -sign * self.x());
+        let v = vc!(b, sign + self.y() * self.y() * a, -self.y());
+
+        (u, v)
+    }
 }
 
 impl<const N: usize> std::ops::Add for Vector<N> {
@@ -342,6 +355,31 @@ mod tests {
         }
     }
 
+    #[rstest]
+    fn orthonormal_basis() {
+        let values = [-5.0, -1.0, 0.0, 1.0, 2.0];
+
+        for x in values {
+            for y in values {
+                for z in values {
+                    if x == 0.0 && y == 0.0 && z == 0.0 {
+                        continue;
+                    }
+
+                    let w = vc!(x, y, z).normalized();
+                    let (u, v) = w.orthonormal_basis();
+
+                    assert!(u.is_unit());
+                    assert!(v.is_unit());
+                    assert!(u.is_orthogonal_to(&v));
+                    assert!(u.is_orthogonal_to(&w));
+                    assert!(v.is_orthogonal_to(&w));
+                    assert_eq!(approx(w), u.cross(&v));
+                }
+            }
+        }
+    }
+
     #[rstest]
     #[case(vc!(1, -1), vc!(0, 1), vc!(1, 1))]
     #[case(vc!(2, -1), vc!(0, 1), vc!(2, 1))]
@@ -0,0 +1,147 @@
+use std::rc::Rc;
+
+use crate::{
+    animation::{Animation, Duration, TimeStamp},
+    materials::Material,
+    math::transformation3d::Transformation3D,
+};
+
+use super::{primitive::Primitive, Decorator, IdTag, Tagger, Transformer, Union};
+
+pub struct AnimatedTransformer {
+    transformation: Box<dyn Animation<Transformation3D>>,
+    child: Box<dyn Animation<Rc<dyn Primitive>>>,
+}
+
+impl AnimatedTransformer {
+    pub fn new(transformation: Box<dyn Animation<Transformation3D>>, child: Box<dyn Animation<Rc<dyn Primitive>>>) -> Self {
+        AnimatedTransformer { transformation, child }
+    }
+}
+
+impl Animation<Rc<dyn Primitive>> for AnimatedTransformer {
+    fn at(&self, t: TimeStamp) -> Rc<dyn Primitive> {
+        Rc::new(Transformer::new(self.transformation.at(t), self.child.at(t)))
+    }
+
+    fn duration(&self) -> Duration {
+        self.transformation.duration().max(self.child.duration())
+    }
+}
+
+pub struct AnimatedDecorator {
+    material: Box<dyn Animation<Rc<dyn Material>>>,
+    child: Box<dyn Animation<Rc<dyn Primitive>>>,
+}
+
+impl AnimatedDecorator {
+    pub fn new(material: Box<dyn Animation<Rc<dyn Material>>>, child: Box<dyn Animation<Rc<dyn Primitive>>>) -> Self {
+        AnimatedDecorator { material, child }
+    }
+}
+
+impl Animation<Rc<dyn Primitive>> for AnimatedDecorator {
+    fn at(&self, t: TimeStamp) -> Rc<dyn Primitive> {
+        Rc::new(Decorator::new(self.material.at(t), self.child.at(t)))
+    }
+
+    fn duration(&self) -> Duration {
+        self.material.duration().max(self.child.duration())
+    }
+}
+
+pub struct AnimatedTagger {
+    tag: IdTag,
+    child: Box<dyn Animation<Rc<dyn Primitive>>>,
+}
+
+impl AnimatedTagger {
+    pub fn new(tag: IdTag, child: Box<dyn Animation<Rc<dyn Primitive>>>) -> Self {
+        AnimatedTagger { tag, child }
+    }
+}
+
+impl Animation<Rc<dyn Primitive>> for AnimatedTagger {
+    fn at(&self, t: TimeStamp) -> Rc<dyn Primitive> {
+        Rc::new(Tagger::new(self.tag, self.child.at(t)))
+    }
+
+    fn duration(&self) -> Duration {
+        self.child.duration()
+    }
+}
+
+pub struct AnimatedUnion {
+    children: Vec<Box<dyn Animation<Rc<dyn Primitive>>>>,
+}
+
+impl AnimatedUnion {
+    pub fn new(children: Vec<Box<dyn Animation<Rc<dyn Primitive>>>>) -> Self {
+        AnimatedUnion { children }
+    }
+}
+
+impl Animation<Rc<dyn Primitive>> for AnimatedUnion {
+    fn at(&self, t: TimeStamp) -> Rc<dyn Primitive> {
+        let children = self.children.iter().map(|child| child.at(t)).collect();
+
+        Rc::new(Union::new(children))
+    }
+
+    fn duration(&self) -> Duration {
+        self.children
+            .iter()
+            .fold(Duration::from_seconds(0.0), |duration, child| duration.max(child.duration()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    #[cfg(test)]
+    use super::*;
+
+    #[cfg(test)]
+    use crate::{
+        animation::{ConstantAnimation, LinearAnimation},
+        math::{approx, pt, vc, Ray},
+        primitives::Sphere,
+    };
+
+    #[cfg(test)]
+    fn moving_sphere() -> AnimatedTransformer {
+        let transformation = LinearAnimation {
+            start: Transformation3D::translate(&vc!(0, 0, 0)),
+            end: Transformation3D::translate(&vc!(4, 0, 0)),
+            duration: Duration::from_seconds(2.0),
+        };
+        let sphere: Rc<dyn Primitive> = Rc::new(Sphere::new());
+
+        AnimatedTransformer::new(Box::new(transformation), Box::new(ConstantAnimation::timeless(sphere)))
+    }
+
+    #[rstest]
+    #[case(0.0, 0.0)]
+    #[case(1.0, 2.0)]
+    #[case(2.0, 4.0)]
+    fn transformer_follows_animation(#[case] t: f64, #[case] expected_x: f64) {
+        let root = moving_sphere().at(TimeStamp::new(t));
+        let ray = Ray::new(pt!(expected_x, 0, 5), vc!(0, 0, -1));
+        let hit = root.find_first_positive_hit(&ray).unwrap();
+
+        assert_eq!(approx(4.0), hit.t);
+        assert_eq!(approx(pt!(expected_x, 0, 1)), hit.global_position());
+    }
+
+    #[rstest]
+    fn union_duration_is_longest_child() {
+        let empty: Rc<dyn Primitive> = Rc::new(Union::new(vec![]));
+        let union = AnimatedUnion::new(vec![
+            Box::new(ConstantAnimation::timeless(empty)),
+            Box::new(moving_sphere()),
+        ]);
+
+        assert_eq!(2.0, union.duration().in_seconds());
+    }
+}
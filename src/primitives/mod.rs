@@ -1,13 +1,17 @@
+mod animated;
 mod decorator;
 mod plane;
 mod primitive;
 mod sphere;
+mod tagger;
 mod transformer;
 mod union;
 
+pub use animated::{AnimatedDecorator, AnimatedTagger, AnimatedTransformer, AnimatedUnion};
 pub use sphere::Sphere;
 pub use decorator::Decorator;
 pub use plane::PlaneXY;
 pub use primitive::{Hit, LocalPosition, Primitive};
+pub use tagger::{IdTag, Tagger};
 pub use transformer::Transformer;
 pub use union::Union;
\ No newline at end of file
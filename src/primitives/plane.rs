@@ -50,6 +50,8 @@ impl Primitive for PlaneXY {
                     ray: ray.clone(),
                     local_position,
                     transformation: coordinate_system.as_transformation(),
+                    object_id: None,
+                    material_id: None,
                 };
 
                 Some(hit)
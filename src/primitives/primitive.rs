@@ -10,6 +10,8 @@ pub struct Hit {
     pub local_position: LocalPosition,
     pub transformation: Transformation3D,
     pub material_properties: Option<MaterialProperties>,
+    pub object_id: Option<u32>,
+    pub material_id: Option<u32>,
 }
 
 #[derive(Copy, Clone)]
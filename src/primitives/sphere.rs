@@ -83,6 +83,8 @@ impl Primitive for Sphere {
                         local_position,
                         transformation: coordinate_system.as_transformation(),
                         material_properties: None,
+                        object_id: None,
+                        material_id: None,
                     };
 
                     Some(hit)
@@ -0,0 +1,77 @@
+use std::rc::Rc;
+
+use crate::math::Ray;
+
+use super::primitive::{Hit, Primitive};
+
+// Identifiers end up in the object and material ID output passes
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum IdTag {
+    Object(u32),
+    Material(u32),
+}
+
+pub struct Tagger {
+    tag: IdTag,
+    child: Rc<dyn Primitive>,
+}
+
+impl Tagger {
+    pub fn new(tag: IdTag, child: Rc<dyn Primitive>) -> Self {
+        Tagger { tag, child }
+    }
+}
+
+impl Primitive for Tagger {
+    fn find_first_positive_hit(&self, ray: &Ray) -> Option<Hit> {
+        let mut hit = self.child.find_first_positive_hit(ray)?;
+
+        match self.tag {
+            IdTag::Object(id) => hit.object_id = Some(id),
+            IdTag::Material(id) => hit.material_id = Some(id),
+        }
+
+        Some(hit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    #[cfg(test)]
+    use super::*;
+
+    #[cfg(test)]
+    use crate::{
+        math::{pt, vc},
+        primitives::Sphere,
+    };
+
+    #[rstest]
+    fn outer_tag_wins() {
+        let inner = Rc::new(Tagger::new(IdTag::Object(1), Rc::new(Sphere::new())));
+        let outer = Tagger::new(IdTag::Object(2), inner);
+        let hit = outer.find_first_positive_hit(&Ray::new(pt!(0, 0, 5), vc!(0, 0, -1))).unwrap();
+
+        assert_eq!(Some(2), hit.object_id);
+        assert_eq!(None, hit.material_id);
+    }
+
+    #[rstest]
+    fn object_and_material_tags_are_independent() {
+        let sphere = Rc::new(Tagger::new(IdTag::Material(7), Rc::new(Sphere::new())));
+        let tagged = Tagger::new(IdTag::Object(3), sphere);
+        let hit = tagged.find_first_positive_hit(&Ray::new(pt!(0, 0, 5), vc!(0, 0, -1))).unwrap();
+
+        assert_eq!(Some(3), hit.object_id);
+        assert_eq!(Some(7), hit.material_id);
+    }
+
+    #[rstest]
+    fn misses_stay_misses() {
+        let tagged = Tagger::new(IdTag::Object(3), Rc::new(Sphere::new()));
+
+        assert!(tagged.find_first_positive_hit(&Ray::new(pt!(5, 0, 5), vc!(0, 0, -1))).is_none());
+    }
+}
@@ -0,0 +1,79 @@
+pub struct VarianceEstimator {
+    sample_count: u32,
+    mean: f64,
+    sum_of_squared_differences: f64,
+}
+
+// Welford's online algorithm
+impl VarianceEstimator {
+    pub fn new() -> Self {
+        VarianceEstimator {
+            sample_count: 0,
+            mean: 0.0,
+            sum_of_squared_differences: 0.0,
+        }
+    }
+
+    pub fn add(&mut self, x: f64) {
+        self.sample_count += 1;
+
+        let delta = x - self.mean;
+        self.mean += delta / self.sample_count as f64;
+        self.sum_of_squared_differences += delta * (x - self.mean);
+    }
+
+    pub fn sample_count(&self) -> u32 {
+        self.sample_count
+    }
+
+    pub fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    pub fn variance(&self) -> f64 {
+        if self.sample_count < 2 {
+            f64::INFINITY
+        } else {
+            self.sum_of_squared_differences / (self.sample_count - 1) as f64
+        }
+    }
+
+    pub fn variance_of_mean(&self) -> f64 {
+        self.variance() / self.sample_count as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    #[cfg(test)]
+    use super::*;
+
+    use crate::math::approx;
+
+    #[rstest]
+    #[case(vec![1.0, 1.0], 1.0, 0.0)]
+    #[case(vec![1.0, 2.0, 3.0], 2.0, 1.0)]
+    #[case(vec![2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0], 5.0, 32.0 / 7.0)]
+    fn mean_and_variance(#[case] xs: Vec<f64>, #[case] expected_mean: f64, #[case] expected_variance: f64) {
+        let mut estimator = VarianceEstimator::new();
+
+        for x in xs.iter() {
+            estimator.add(*x);
+        }
+
+        assert_eq!(xs.len() as u32, estimator.sample_count());
+        assert_eq!(approx(expected_mean), estimator.mean());
+        assert_eq!(approx(expected_variance), estimator.variance());
+        assert_eq!(approx(expected_variance / xs.len() as f64), estimator.variance_of_mean());
+    }
+
+    #[rstest]
+    fn variance_of_single_sample_is_unknown() {
+        let mut estimator = VarianceEstimator::new();
+        estimator.add(1.0);
+
+        assert_eq!(f64::INFINITY, estimator.variance());
+    }
+}
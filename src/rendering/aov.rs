@@ -0,0 +1,303 @@
+use std::{io::Write, path::Path};
+
+use crate::{
+    imaging::{
+        color::Color,
+        denoise::Guides,
+        exr::{write_exr_channels, ExrChannel, ExrCompression},
+        image::{Image, WriteError},
+    },
+    math::Position,
+    tracing::raytracer::Aovs,
+};
+
+// Per pixel averages of the samples taken inside the pixel; no reconstruction filter is applied
+pub struct AovBuffer {
+    width: u32,
+    height: u32,
+    pixels: Vec<PixelAovs>,
+}
+
+#[derive(Clone, Default)]
+struct PixelAovs {
+    sample_count: u32,
+    hit_count: u32,
+    distance: f64,
+    normal: [f64; 3],
+    uv: [f64; 2],
+    albedo: [f64; 3],
+    direct: [f64; 3],
+    indirect: [f64; 3],
+    object_ids: Vec<(u32, u32)>,
+    material_ids: Vec<(u32, u32)>,
+}
+
+// Depth is zero and IDs are -1 where nothing was hit; IDs are those found in the most samples
+pub struct AovImages {
+    pub depth: Image,
+    pub normal: Image,
+    pub uv: Image,
+    pub albedo: Image,
+    pub direct: Image,
+    pub indirect: Image,
+    pub object_id: Image,
+    pub material_id: Image,
+}
+
+impl AovBuffer {
+    pub fn new(width: u32, height: u32) -> Self {
+        AovBuffer {
+            width,
+            height,
+            pixels: vec![PixelAovs::default(); (width * height) as usize],
+        }
+    }
+
+    pub fn add_sample(&mut self, position: Position<2>, aovs: &Aovs) {
+        let index = (position.y() as u32 * self.width + position.x() as u32) as usize;
+        let pixel = &mut self.pixels[index];
+
+        pixel.sample_count += 1;
+        accumulate(&mut pixel.direct, &aovs.direct);
+        accumulate(&mut pixel.indirect, &aovs.indirect);
+
+        if let Some(surface) = &aovs.surface {
+            pixel.hit_count += 1;
+            pixel.distance += surface.distance;
+            pixel.normal[0] += surface.normal.x();
+            pixel.normal[1] += surface.normal.y();
+            pixel.normal[2] += surface.normal.z();
+            pixel.uv[0] += surface.uv.x();
+            pixel.uv[1] += surface.uv.y();
+            accumulate(&mut pixel.albedo, &surface.albedo);
+            tally(&mut pixel.object_ids, surface.object_id);
+            tally(&mut pixel.material_ids, surface.material_id);
+        }
+    }
+
+    pub fn to_images(&self) -> AovImages {
+        let image = |f: &dyn Fn(&PixelAovs) -> Color| {
+            Image::from_fn(self.width, self.height, |x, y| f(&self.pixels[(y * self.width + x) as usize]))
+        };
+        let gray = |value: f64| Color::new(value, value, value);
+
+        AovImages {
+            depth: image(&|p| gray(p.hit_average(p.distance))),
+            normal: image(&|p| {
+                let [x, y, z] = p.normal;
+                let length = (x * x + y * y + z * z).sqrt();
+
+                if length > 0.0 { Color::new(x / length, y / length, z / length) } else { Color::black() }
+            }),
+            uv: image(&|p| Color::new(p.hit_average(p.uv[0]), p.hit_average(p.uv[1]), 0.0)),
+            albedo: image(&|p| p.sample_average(&p.albedo)),
+            direct: image(&|p| p.sample_average(&p.direct)),
+            indirect: image(&|p| p.sample_average(&p.indirect)),
+            object_id: image(&|p| gray(most_frequent(&p.object_ids))),
+            material_id: image(&|p| gray(most_frequent(&p.material_ids))),
+        }
+    }
+}
+
+impl PixelAovs {
+    fn hit_average(&self, total: f64) -> f64 {
+        if self.hit_count == 0 { 0.0 } else { total / self.hit_count as f64 }
+    }
+
+    fn sample_average(&self, total: &[f64; 3]) -> Color {
+        if self.sample_count == 0 {
+            Color::black()
+        } else {
+            Color::new(total[0], total[1], total[2]) / self.sample_count as f64
+        }
+    }
+}
+
+fn accumulate(total: &mut [f64; 3], color: &Color) {
+    total[0] += color.r();
+    total[1] += color.g();
+    total[2] += color.b();
+}
+
+fn tally(counts: &mut Vec<(u32, u32)>, id: Option<u32>) {
+    if let Some(id) = id {
+        match counts.iter_mut().find(|(other, _)| *other == id) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((id, 1)),
+        }
+    }
+}
+
+fn most_frequent(counts: &[(u32, u32)]) -> f64 {
+    counts
+        .iter()
+        .max_by_key(|(id, count)| (*count, std::cmp::Reverse(*id)))
+        .map_or(-1.0, |(id, _)| *id as f64)
+}
+
+impl AovImages {
+    pub fn guides(&self) -> Guides<'_> {
+        Guides {
+            albedo: &self.albedo,
+            normal: &self.normal,
+            depth: &self.depth,
+        }
+    }
+
+    pub fn layers(&self) -> Vec<(&'static str, &Image)> {
+        vec![
+            ("depth", &self.depth),
+            ("normal", &self.normal),
+            ("uv", &self.uv),
+            ("albedo", &self.albedo),
+            ("direct", &self.direct),
+            ("indirect", &self.indirect),
+            ("object_id", &self.object_id),
+            ("material_id", &self.material_id),
+        ]
+    }
+
+    // Writes one file per layer: frame.pfm becomes frame_depth.pfm, frame_normal.pfm, ...
+    pub fn write_to_files(&self, path: &Path) -> Result<(), WriteError> {
+        let stem = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or_default();
+        let extension = path.extension().and_then(|extension| extension.to_str()).unwrap_or_default();
+
+        for (name, image) in self.layers() {
+            image.write_to_file(&path.with_file_name(format!("{stem}_{name}.{extension}")))?;
+        }
+
+        Ok(())
+    }
+
+    // Single multi-layer file holding the beauty image (with alpha, if any) followed by all passes
+    pub fn write_exr(&self, beauty: &Image, writer: impl Write, compression: ExrCompression) -> Result<(), WriteError> {
+        let width = beauty.width();
+        let height = beauty.height();
+        let channel = |name: &str, image: &Image, component: fn(&Color) -> f64| ExrChannel {
+            name: String::from(name),
+            values: positions(width, height).map(|position| component(image.get(position)) as f32).collect(),
+        };
+        let rgb = |prefix: &str, suffixes: [&str; 3], image: &Image| {
+            vec![
+                channel(&format!("{prefix}{}", suffixes[0]), image, |c| c.r()),
+                channel(&format!("{prefix}{}", suffixes[1]), image, |c| c.g()),
+                channel(&format!("{prefix}{}", suffixes[2]), image, |c| c.b()),
+            ]
+        };
+
+        let mut channels = rgb("", ["R", "G", "B"], beauty);
+        if beauty.has_alpha() {
+            channels.push(ExrChannel {
+                name: String::from("A"),
+                values: positions(width, height).map(|position| beauty.alpha(position) as f32).collect(),
+            });
+        }
+        channels.push(channel("Z", &self.depth, |c| c.r()));
+        channels.extend(rgb("N.", ["X", "Y", "Z"], &self.normal));
+        channels.push(channel("uv.U", &self.uv, |c| c.r()));
+        channels.push(channel("uv.V", &self.uv, |c| c.g()));
+        channels.extend(rgb("albedo.", ["R", "G", "B"], &self.albedo));
+        channels.extend(rgb("direct.", ["R", "G", "B"], &self.direct));
+        channels.extend(rgb("indirect.", ["R", "G", "B"], &self.indirect));
+        channels.push(channel("objectId", &self.object_id, |c| c.r()));
+        channels.push(channel("materialId", &self.material_id, |c| c.r()));
+
+        write_exr_channels(width, height, channels, writer, compression)
+    }
+}
+
+fn positions(width: u32, height: u32) -> impl Iterator<Item = Position<2>> {
+    (0..height).flat_map(move |y| (0..width).map(move |x| Position::<2>::cartesian(x as i32, y as i32)))
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    #[cfg(test)]
+    use super::*;
+
+    #[cfg(test)]
+    use crate::{
+        math::{approx, pt, vc},
+        tracing::raytracer::{SurfaceAovs, TraceResult},
+    };
+
+    #[cfg(test)]
+    fn surface(distance: f64, object_id: Option<u32>) -> Aovs {
+        Aovs {
+            surface: Some(SurfaceAovs {
+                distance,
+                normal: vc!(0, 0, 1),
+                uv: pt!(0.5, 0.25),
+                albedo: Color::new(1.0, 0.5, 0.0),
+                object_id,
+                material_id: None,
+            }),
+            direct: Color::white(),
+            indirect: Color::white() * 0.5,
+        }
+    }
+
+    #[cfg(test)]
+    fn value(image: &Image) -> Color {
+        *image.get(Position::<2>::cartesian(0, 0))
+    }
+
+    #[rstest]
+    fn averages_over_samples() {
+        let mut buffer = AovBuffer::new(1, 1);
+        buffer.add_sample(Position::<2>::cartesian(0, 0), &surface(2.0, Some(4)));
+        buffer.add_sample(Position::<2>::cartesian(0, 0), &surface(4.0, Some(4)));
+        buffer.add_sample(Position::<2>::cartesian(0, 0), &surface(6.0, Some(1)));
+        buffer.add_sample(Position::<2>::cartesian(0, 0), &TraceResult::miss().aovs);
+        let images = buffer.to_images();
+
+        assert_eq!(approx(4.0), value(&images.depth).r());
+        assert_eq!(Color::new(0.0, 0.0, 1.0), value(&images.normal));
+        assert_eq!(approx(0.25), value(&images.uv).g());
+        assert_eq!(approx(0.75), value(&images.albedo).r());
+        assert_eq!(approx(0.75), value(&images.direct).g());
+        assert_eq!(4.0, value(&images.object_id).r());
+        assert_eq!(-1.0, value(&images.material_id).r());
+    }
+
+    #[rstest]
+    fn empty_pixels() {
+        let mut buffer = AovBuffer::new(2, 1);
+        buffer.add_sample(Position::<2>::cartesian(1, 0), &TraceResult::miss().aovs);
+        let images = buffer.to_images();
+
+        for image in [&images.depth, &images.normal, &images.albedo] {
+            assert_eq!(Color::black(), value(image));
+        }
+        assert_eq!(-1.0, value(&images.object_id).r());
+    }
+
+    #[rstest]
+    #[case(&[(3, 2), (1, 2)], 1.0)]
+    #[case(&[(3, 3), (1, 2)], 3.0)]
+    #[case(&[], -1.0)]
+    fn most_frequent_id(#[case] counts: &[(u32, u32)], #[case] expected: f64) {
+        assert_eq!(expected, most_frequent(counts));
+    }
+
+    #[rstest]
+    fn multi_layer_exr() {
+        let mut buffer = AovBuffer::new(2, 2);
+        buffer.add_sample(Position::<2>::cartesian(1, 1), &surface(2.0, Some(4)));
+        let beauty = Image::new(2, 2).with_alpha(vec![0.0, 0.0, 0.0, 1.0]);
+        let mut output = Vec::new();
+        buffer.to_images().write_exr(&beauty, &mut output, ExrCompression::None).unwrap();
+
+        let contains = |name: &str| {
+            let needle = format!("{name}\0");
+            output.windows(needle.len()).any(|window| window == needle.as_bytes())
+        };
+
+        assert_eq!(&[0x76, 0x2f, 0x31, 0x01], &output[..4]);
+        for name in ["A", "Z", "N.X", "uv.V", "albedo.G", "direct.B", "indirect.R", "objectId", "materialId"] {
+            assert!(contains(name), "Missing channel {name}");
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use crate::{
+    imaging::{color::Color, image::Image},
+    math::{Point, Position},
+};
+
+use super::ReconstructionFilter;
+
+// Accumulates filtered samples. Sample positions are in pixel units: pixel (x, y) covers [x, x + 1] x [y, y + 1].
+// Coverage is the fraction of the sample that hit geometry and becomes the alpha of the image.
+pub struct Film {
+    width: u32,
+    height: u32,
+    filter: ReconstructionFilter,
+    weighted_colors: Vec<Color>,
+    weighted_coverage: Vec<f64>,
+    weights: Vec<f64>,
+}
+
+impl Film {
+    pub fn new(width: u32, height: u32, filter: ReconstructionFilter) -> Self {
+        let pixel_count = (width * height) as usize;
+
+        Film {
+            width,
+            height,
+            filter,
+            weighted_colors: vec![Color::black(); pixel_count],
+            weighted_coverage: vec![0.0; pixel_count],
+            weights: vec![0.0; pixel_count],
+        }
+    }
+
+    pub fn add_sample(&mut self, position: &Point<2>, color: &Color, coverage: f64) {
+        let radius = self.filter.radius();
+        let x_range = Self::pixel_range(position.x(), radius, self.width);
+        let y_range = Self::pixel_range(position.y(), radius, self.height);
+
+        for y in y_range {
+            for x in x_range.clone() {
+                let dx = position.x() - (x as f64 + 0.5);
+                let dy = position.y() - (y as f64 + 0.5);
+                let weight = self.filter.evaluate(dx, dy);
+
+                if weight != 0.0 {
+                    let index = (y * self.width + x) as usize;
+                    self.weighted_colors[index] += *color * weight;
+                    self.weighted_coverage[index] += coverage * weight;
+                    self.weights[index] += weight;
+                }
+            }
+        }
+    }
+
+    // Pixels whose center lies within the radius
+    fn pixel_range(coordinate: f64, radius: f64, size: u32) -> std::ops::Range<u32> {
+        let first = (coordinate - 0.5 - radius).ceil().max(0.0) as u32;
+        let last = ((coordinate - 0.5 + radius).floor() + 1.0).clamp(0.0, size as f64) as u32;
+
+        first..last.max(first)
+    }
+
+    // Misses contribute black, so colors come out premultiplied by the coverage
+    pub fn to_image(&self) -> Image {
+        let mut image = Image::new(self.width, self.height);
+        let mut alpha = vec![0.0; (self.width * self.height) as usize];
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let index = (y * self.width + x) as usize;
+                let weight = self.weights[index];
+
+                // Filters with negative lobes can cancel out completely
+                if weight.abs() > 1e-12 {
+                    *image.get_mut(Position::<2>::cartesian(x as i32, y as i32)) = self.weighted_colors[index] / weight;
+                    alpha[index] = (self.weighted_coverage[index] / weight).clamp(0.0, 1.0);
+                }
+            }
+        }
+
+        image.with_alpha(alpha)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    #[cfg(test)]
+    use super::*;
+
+    #[cfg(test)]
+    use crate::math::{approx, pt};
+
+    #[cfg(test)]
+    fn pixel(image: &Image, x: i32, y: i32) -> f64 {
+        image.get(Position::<2>::cartesian(x, y)).r()
+    }
+
+    #[rstest]
+    fn pixel_box_averages_within_pixel() {
+        let mut film = Film::new(2, 1, ReconstructionFilter::pixel_box());
+        film.add_sample(&pt!(0.25, 0.5), &Color::white(), 1.0);
+        film.add_sample(&pt!(0.75, 0.5), &Color::black(), 1.0);
+        film.add_sample(&pt!(1.5, 0.5), &(Color::white() * 4.0), 1.0);
+        let image = film.to_image();
+
+        assert_eq!(approx(0.5), pixel(&image, 0, 0));
+        assert_eq!(approx(4.0), pixel(&image, 1, 0));
+    }
+
+    #[rstest]
+    fn tent_splats_to_neighbors() {
+        let mut film = Film::new(3, 1, ReconstructionFilter::Tent { radius: 1.5 });
+        film.add_sample(&pt!(0.5, 0.5), &Color::white(), 1.0);
+        film.add_sample(&pt!(2.5, 0.5), &Color::black(), 1.0);
+        let image = film.to_image();
+
+        assert_eq!(approx(1.0), pixel(&image, 0, 0));
+        assert_eq!(approx(0.5), pixel(&image, 1, 0));
+        assert_eq!(approx(0.0), pixel(&image, 2, 0));
+    }
+
+    #[rstest]
+    fn constant_signal_is_preserved(
+        #[values(
+            ReconstructionFilter::pixel_box(),
+            ReconstructionFilter::Tent { radius: 1.0 },
+            ReconstructionFilter::Gaussian { radius: 1.5, alpha: 2.0 },
+            ReconstructionFilter::mitchell(2.0),
+            ReconstructionFilter::Lanczos { radius: 3.0 }
+        )]
+        filter: ReconstructionFilter,
+    ) {
+        let mut film = Film::new(4, 3, filter);
+        for i in 0..16 {
+            for j in 0..12 {
+                film.add_sample(&pt!((i as f64 + 0.5) / 4.0, (j as f64 + 0.5) / 4.0), &(Color::white() * 0.7), 1.0);
+            }
+        }
+        let image = film.to_image();
+
+        for y in 0..3 {
+            for x in 0..4 {
+                assert_eq!(approx(0.7), pixel(&image, x, y));
+                assert_eq!(approx(1.0), image.alpha(Position::<2>::cartesian(x, y)));
+            }
+        }
+    }
+
+    #[rstest]
+    fn coverage_becomes_alpha() {
+        let mut film = Film::new(2, 1, ReconstructionFilter::pixel_box());
+        film.add_sample(&pt!(0.25, 0.5), &Color::white(), 1.0);
+        film.add_sample(&pt!(0.75, 0.5), &Color::black(), 0.0);
+        film.add_sample(&pt!(1.25, 0.5), &Color::black(), 0.0);
+        film.add_sample(&pt!(1.75, 0.5), &Color::black(), 0.0);
+        let image = film.to_image();
+
+        assert_eq!(approx(0.5), image.alpha(Position::<2>::cartesian(0, 0)));
+        assert_eq!(approx(0.0), image.alpha(Position::<2>::cartesian(1, 0)));
+    }
+}
@@ -0,0 +1,117 @@
+use crate::math::lanczos;
+
+// Radii are expressed in pixels. Filters are separable: the weight of a sample is the product
+// of the one dimensional filter evaluated at the horizontal and vertical offset to the pixel center.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReconstructionFilter {
+    Box { radius: f64 },
+    Tent { radius: f64 },
+    Gaussian { radius: f64, alpha: f64 },
+    MitchellNetravali { radius: f64, b: f64, c: f64 },
+    Lanczos { radius: f64 },
+}
+
+impl ReconstructionFilter {
+    // Box filter covering exactly one pixel, equivalent to plain per pixel averaging
+    pub fn pixel_box() -> Self {
+        ReconstructionFilter::Box { radius: 0.5 }
+    }
+
+    pub fn mitchell(radius: f64) -> Self {
+        ReconstructionFilter::MitchellNetravali {
+            radius,
+            b: 1.0 / 3.0,
+            c: 1.0 / 3.0,
+        }
+    }
+
+    pub fn radius(&self) -> f64 {
+        match *self {
+            ReconstructionFilter::Box { radius } => radius,
+            ReconstructionFilter::Tent { radius } => radius,
+            ReconstructionFilter::Gaussian { radius, .. } => radius,
+            ReconstructionFilter::MitchellNetravali { radius, .. } => radius,
+            ReconstructionFilter::Lanczos { radius } => radius,
+        }
+    }
+
+    pub fn evaluate(&self, dx: f64, dy: f64) -> f64 {
+        self.evaluate_1d(dx) * self.evaluate_1d(dy)
+    }
+
+    fn evaluate_1d(&self, x: f64) -> f64 {
+        let x = x.abs();
+
+        if x > self.radius() {
+            return 0.0;
+        }
+
+        match *self {
+            ReconstructionFilter::Box { .. } => 1.0,
+            ReconstructionFilter::Tent { radius } => radius - x,
+            ReconstructionFilter::Gaussian { radius, alpha } => {
+                ((-alpha * x * x).exp() - (-alpha * radius * radius).exp()).max(0.0)
+            }
+            ReconstructionFilter::MitchellNetravali { radius, b, c } => mitchell_netravali(2.0 * x / radius, b, c),
+            ReconstructionFilter::Lanczos { radius } => lanczos(x, radius),
+        }
+    }
+}
+
+// Defined on [0, 2]
+fn mitchell_netravali(x: f64, b: f64, c: f64) -> f64 {
+    let x2 = x * x;
+    let x3 = x2 * x;
+
+    if x < 1.0 {
+        ((12.0 - 9.0 * b - 6.0 * c) * x3 + (-18.0 + 12.0 * b + 6.0 * c) * x2 + (6.0 - 2.0 * b)) / 6.0
+    } else {
+        ((-b - 6.0 * c) * x3 + (6.0 * b + 30.0 * c) * x2 + (-12.0 * b - 48.0 * c) * x + (8.0 * b + 24.0 * c)) / 6.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    #[cfg(test)]
+    use super::*;
+
+    #[cfg(test)]
+    use crate::math::approx;
+
+    #[rstest]
+    fn zero_outside_radius(
+        #[values(
+            ReconstructionFilter::pixel_box(),
+            ReconstructionFilter::Tent { radius: 1.0 },
+            ReconstructionFilter::Gaussian { radius: 1.5, alpha: 2.0 },
+            ReconstructionFilter::mitchell(2.0),
+            ReconstructionFilter::Lanczos { radius: 3.0 }
+        )]
+        filter: ReconstructionFilter,
+        #[values(0.01, 0.5, 2.0)] excess: f64,
+    ) {
+        let outside = filter.radius() + excess;
+
+        assert_eq!(0.0, filter.evaluate(outside, 0.0));
+        assert_eq!(0.0, filter.evaluate(0.0, -outside));
+        assert!(filter.evaluate(0.0, 0.0) > 0.0);
+    }
+
+    #[rstest]
+    #[case(ReconstructionFilter::Tent { radius: 2.0 }, 1.0, 0.0, 2.0)]
+    #[case(ReconstructionFilter::Tent { radius: 2.0 }, 1.0, 1.0, 1.0)]
+    #[case(ReconstructionFilter::mitchell(2.0), 0.0, 0.0, (8.0 / 9.0) * (8.0 / 9.0))]
+    #[case(ReconstructionFilter::mitchell(2.0), 1.0, 0.0, 8.0 / 9.0 / 18.0)]
+    #[case(ReconstructionFilter::Lanczos { radius: 2.0 }, 1.0, 0.0, 0.0)]
+    #[case(ReconstructionFilter::Lanczos { radius: 2.0 }, 0.5, 0.0, 4.0 * 2f64.sqrt() / (std::f64::consts::PI * std::f64::consts::PI))]
+    fn filter_values(#[case] filter: ReconstructionFilter, #[case] dx: f64, #[case] dy: f64, #[case] expected: f64) {
+        assert_eq!(approx(expected), filter.evaluate(dx, dy));
+    }
+
+    #[rstest]
+    fn mitchell_has_negative_lobes() {
+        assert!(ReconstructionFilter::mitchell(2.0).evaluate(1.5, 0.0) < 0.0);
+    }
+}
@@ -0,0 +1,176 @@
+// Regression tests comparing renders of fixed scenes against reference PNGs in the golden directory.
+// Run the tests with RRT_UPDATE_GOLDEN set to regenerate the references after an intended change.
+use std::{
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+use rstest::rstest;
+
+use crate::{
+    animation::{Animation, Duration, TimeStamp},
+    cameras::{
+        fov::FieldOfView,
+        perspective::{PerspectiveCamera, PerspectiveCameraParameters},
+    },
+    imaging::{
+        color::Color,
+        compare::{compare, difference_image, ImageComparison},
+        image::{Image, ImageFormat},
+    },
+    lights::{light::LightSource, point::PointLight, sphere::SphereLight},
+    materials::{MaterialProperties, UniformMaterial},
+    math::{transformation3d::Transformation3D, Angle},
+    primitives::{Decorator, PlaneXY, Primitive, Sphere, Transformer, Union},
+    pt,
+    samplers::StratifiedSampler2D,
+    tracing::{integrator::Integrator, pathtracer::PathTracer, scene::Scene, whitted::WhittedIntegrator},
+    vc,
+};
+
+use super::{ReconstructionFilter, RenderSettings, Renderer, SamplingMode, Shutter};
+
+struct Tolerance {
+    min_psnr: f64,
+    max_flip: f64,
+}
+
+const TOLERANCE: Tolerance = Tolerance {
+    min_psnr: 35.0,
+    max_flip: 0.05,
+};
+
+struct StillScene {
+    light_color: Color,
+    area_light: bool,
+}
+
+impl Animation<Scene> for StillScene {
+    fn duration(&self) -> Duration {
+        Duration::from_seconds(0.0)
+    }
+
+    fn at(&self, _t: TimeStamp) -> Scene {
+        let material = |diffuse: Color| {
+            Rc::new(UniformMaterial::new(MaterialProperties {
+                diffuse,
+                specular_color: Color::white() * 0.5,
+                specular_exponent: 20.0,
+                brdf: None,
+                reflection: Color::white() * 0.1,
+            }))
+        };
+        let sphere = |x: f64, y: f64, z: f64, color: Color| -> Rc<dyn Primitive> {
+            let translated = Rc::new(Transformer::new(Transformation3D::translate(&vc!(x, y, z)), Rc::new(Sphere::new())));
+
+            Rc::new(Decorator::new(material(color), translated))
+        };
+        let root = Rc::new(Union::new(vec![
+            sphere(-1.2, 0.0, 1.0, Color::red()),
+            sphere(1.2, 0.5, 1.5, Color::new(0.2, 0.4, 1.0)),
+            Rc::new(Decorator::new(material(Color::white() * 0.8), Rc::new(PlaneXY::new()))),
+        ]));
+        let light_source: Rc<dyn LightSource> = if self.area_light {
+            Rc::new(SphereLight::new(self.light_color, pt!(0, 4, 4), 1.0))
+        } else {
+            Rc::new(PointLight::new(self.light_color, pt!(0, 4, 4)))
+        };
+        let parameters = PerspectiveCameraParameters::new(pt!(0, 4, 8), pt!(0, 0, 1), vc!(0, 1, 0), FieldOfView::Vertical(Angle::degrees(45.0)), 48, 48);
+
+        Scene {
+            camera: Box::new(PerspectiveCamera::new(&parameters)),
+            root,
+            light_sources: vec![light_source],
+        }
+    }
+}
+
+fn render(scene: StillScene, integrator: Rc<dyn Integrator>, samples_per_pixel: u32) -> Image {
+    let settings = RenderSettings {
+        width: 48,
+        height: 48,
+        sampling: SamplingMode::Fixed { samples_per_pixel },
+        sampler: Rc::new(StratifiedSampler2D::new()),
+        integrator,
+        shutter: Shutter::instantaneous(),
+        filter: ReconstructionFilter::pixel_box(),
+    };
+
+    Renderer::new(settings, Box::new(scene)).render_frame(TimeStamp::zero()).image
+}
+
+fn golden_directory() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("golden")
+}
+
+// Goes through PNG encoding so that both sides are quantized the same way
+fn quantize(image: &Image) -> Image {
+    let mut buffer = Vec::new();
+    image.write(&mut buffer).expect("Failed to encode image");
+
+    Image::read_as(&buffer[..], ImageFormat::Png).expect("Failed to decode image")
+}
+
+fn check_golden(name: &str, image: &Image, tolerance: &Tolerance) -> Result<ImageComparison, String> {
+    let path = golden_directory().join(format!("{name}.png"));
+    let actual = quantize(image);
+    let reference = Image::read_from_file(&path).map_err(|error| format!("Failed to read {}: {:?}", path.display(), error))?;
+
+    if (reference.width(), reference.height()) != (actual.width(), actual.height()) {
+        return Err(format!("{name}: size differs from reference"));
+    }
+
+    let comparison = compare(&reference, &actual);
+    if comparison.psnr < tolerance.min_psnr || comparison.flip > tolerance.max_flip {
+        Err(format!("{name}: {comparison:?}"))
+    } else {
+        Ok(comparison)
+    }
+}
+
+// On failure, the render and a difference image are left in target/golden for inspection
+fn assert_matches_golden(name: &str, image: &Image) {
+    if std::env::var_os("RRT_UPDATE_GOLDEN").is_some() {
+        quantize(image)
+            .write_to_file(&golden_directory().join(format!("{name}.png")))
+            .expect("Failed to write reference image");
+    }
+
+    if let Err(message) = check_golden(name, image, &TOLERANCE) {
+        let directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("target").join("golden");
+        std::fs::create_dir_all(&directory).expect("Failed to create output directory");
+        image.write_to_file(&directory.join(format!("{name}.png"))).expect("Failed to write render");
+
+        if let Ok(reference) = Image::read_from_file(&golden_directory().join(format!("{name}.png"))) {
+            if (reference.width(), reference.height()) == (image.width(), image.height()) {
+                difference_image(&reference, &quantize(image))
+                    .write_to_file(&directory.join(format!("{name}-diff.png")))
+                    .expect("Failed to write difference image");
+            }
+        }
+
+        panic!("{message}");
+    }
+}
+
+#[rstest]
+fn whitted_spheres() {
+    let scene = StillScene { light_color: Color::white(), area_light: false };
+
+    assert_matches_golden("whitted_spheres", &render(scene, Rc::new(WhittedIntegrator::new()), 4));
+}
+
+#[rstest]
+fn path_traced_spheres() {
+    let scene = StillScene { light_color: Color::white() * 30.0, area_light: true };
+
+    assert_matches_golden("path_traced_spheres", &render(scene, Rc::new(PathTracer::new(3, 2)), 16));
+}
+
+#[rstest]
+fn shading_changes_are_detected() {
+    let scene = StillScene { light_color: Color::white() * 0.7, area_light: false };
+    let image = render(scene, Rc::new(WhittedIntegrator::new()), 4);
+
+    assert!(check_golden("whitted_spheres", &image, &TOLERANCE).is_err());
+}
@@ -0,0 +1,19 @@
+mod adaptive;
+mod aov;
+mod film;
+mod filter;
+#[cfg(test)]
+mod golden;
+mod renderer;
+mod settings;
+mod statistics;
+mod stereo;
+
+pub use adaptive::VarianceEstimator;
+pub use aov::{AovBuffer, AovImages};
+pub use film::Film;
+pub use filter::ReconstructionFilter;
+pub use renderer::{RenderedFrame, Renderer};
+pub use settings::{AdaptiveSampling, RenderSettings, SamplingMode, Shutter};
+pub use statistics::SampleCounts;
+pub use stereo::StereoRenderer;
@@ -0,0 +1,251 @@
+use crate::{
+    animation::{Animation, TimeStamp},
+    cameras::camera::CameraSample,
+    imaging::{color::Color, image::Image},
+    math::{Position, Rasterizer, Rectangle},
+    pt,
+    tracing::{raytracer::RayTracer, scene::Scene},
+    util::{hash, Random},
+    vc,
+};
+
+use super::{AovBuffer, AovImages, Film, RenderSettings, SampleCounts, VarianceEstimator};
+
+pub struct Renderer {
+    settings: RenderSettings,
+    scene: Box<dyn Animation<Scene>>,
+}
+
+pub struct RenderedFrame {
+    pub image: Image,
+    pub sample_counts: SampleCounts,
+    pub aovs: AovImages,
+}
+
+impl Renderer {
+    pub fn new(settings: RenderSettings, scene: Box<dyn Animation<Scene>>) -> Self {
+        Renderer { settings, scene }
+    }
+
+    fn create_rasterizer(&self) -> Rasterizer<2> {
+        let rectangle = Rectangle::new(pt!(0, 0), vc!(1, 0), vc!(0, 1));
+
+        Rasterizer::new(rectangle, self.settings.width, self.settings.height)
+    }
+
+    pub fn render_frame(&self, t: TimeStamp) -> RenderedFrame {
+        let width = self.settings.width;
+        let height = self.settings.height;
+        let mut film = Film::new(width, height, self.settings.filter);
+        let mut sample_counts = SampleCounts::new(width, height);
+        let mut aov_buffer = AovBuffer::new(width, height);
+
+        let rasterizer = self.create_rasterizer();
+        let sampler = &self.settings.sampler;
+        let shutter = &self.settings.shutter;
+        let ray_tracers: Vec<RayTracer> = shutter
+            .slice_times(t)
+            .into_iter()
+            .map(|time| RayTracer::new(self.scene.at(time), self.settings.integrator.clone()))
+            .collect();
+        let sampling = &self.settings.sampling;
+
+        for y in 0..height {
+            for x in 0..width {
+                let position = Position::<2>::cartesian(x as i32, y as i32);
+                let pixel = rasterizer.at(position);
+                let mut estimator = VarianceEstimator::new();
+                let mut samples = sampler.sample(pixel);
+                let mut random = Random::new(hash(&[t.in_seconds().to_bits(), x as u64, y as u64]));
+
+                while !sampling.is_done(&estimator) {
+                    let lens = pt!(random.next_f64(), random.next_f64());
+                    let camera_sample = CameraSample::new(samples.current(), lens, random.next_f64());
+                    let ray_tracer = &ray_tracers[shutter.slice_index(camera_sample.time)];
+                    let camera_rays = ray_tracer.scene.camera.enumerate_rays(&camera_sample);
+                    let mut sample_color = Color::black();
+                    let mut ray_count = 0;
+                    let mut hit_count = 0;
+
+                    for ray in camera_rays {
+                        let trace_result = ray_tracer.trace(&ray, &mut random);
+                        sample_color += &trace_result.color;
+                        aov_buffer.add_sample(position, &trace_result.aovs);
+                        ray_count += 1;
+
+                        if trace_result.hit() {
+                            hit_count += 1;
+                        }
+                    }
+
+                    let coverage = if ray_count == 0 { 0.0 } else { hit_count as f64 / ray_count as f64 };
+                    let screen = camera_sample.screen;
+                    film.add_sample(&pt!(screen.x() * width as f64, screen.y() * height as f64), &sample_color, coverage);
+                    estimator.add(sample_color.intensity());
+                    samples.refine();
+                }
+
+                sample_counts.set(position, estimator.sample_count());
+            }
+        }
+
+        RenderedFrame {
+            image: film.to_image(),
+            sample_counts,
+            aovs: aov_buffer.to_images(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use rstest::rstest;
+
+    #[cfg(test)]
+    use super::*;
+
+    use crate::{
+        animation::Duration,
+        cameras::orthographic::{OrthographicCamera, OrthographicCameraParameters},
+        lights::sphere::SphereLight,
+        math::transformation3d::Transformation3D,
+        primitives::{IdTag, Sphere, Tagger, Transformer, Union},
+        rendering::{ReconstructionFilter, SamplingMode, Shutter},
+        samplers::StratifiedSampler2D,
+        tracing::pathtracer::PathTracer,
+    };
+
+    // Light moves from x = -1 to x = 1 during the first second
+    struct MovingLight { }
+
+    impl Animation<Scene> for MovingLight {
+        fn duration(&self) -> Duration {
+            Duration::from_seconds(1.0)
+        }
+
+        fn at(&self, t: TimeStamp) -> Scene {
+            let x = -1.0 + 2.0 * t.in_seconds();
+            let camera = OrthographicCamera::new(&OrthographicCameraParameters {
+                eye: pt!(0, 0, 5),
+                look_at: pt!(0, 0, 0),
+                up: vc!(0, 1, 0),
+                view_height: 4.0,
+                aspect_ratio: 1.0,
+            });
+
+            Scene {
+                camera: Box::new(camera),
+                root: Rc::new(Union::new(vec![])),
+                light_sources: vec![Rc::new(SphereLight::new(Color::white(), pt!(x, 0, 0), 0.5))],
+            }
+        }
+    }
+
+    fn render(shutter: Shutter) -> Image {
+        let settings = RenderSettings {
+            width: 4,
+            height: 1,
+            sampling: SamplingMode::Fixed { samples_per_pixel: 256 },
+            sampler: Rc::new(StratifiedSampler2D::new()),
+            integrator: Rc::new(PathTracer::new(1, 1)),
+            shutter,
+            filter: ReconstructionFilter::pixel_box(),
+        };
+        let renderer = Renderer::new(settings, Box::new(MovingLight { }));
+
+        renderer.render_frame(TimeStamp::zero()).image
+    }
+
+    fn brightness(image: &Image, x: i32) -> f64 {
+        image.get(Position::<2>::cartesian(x, 0)).r()
+    }
+
+    #[rstest]
+    fn instantaneous_shutter_is_sharp() {
+        let image = render(Shutter::instantaneous());
+
+        assert!(brightness(&image, 1) > 0.0);
+        assert_eq!(0.0, brightness(&image, 2));
+        assert_eq!(0.0, brightness(&image, 3));
+    }
+
+    #[rstest]
+    fn open_shutter_blurs_motion() {
+        let image = render(Shutter {
+            interval: Duration::from_seconds(1.0),
+            time_slices: 16,
+        });
+
+        // The light sweeps over both middle pixels while the shutter is open
+        for x in 1..3 {
+            let value = brightness(&image, x);
+
+            assert!(0.01 < value && value < 0.5, "Pixel {x} has brightness {value}");
+        }
+    }
+
+    // Sphere of radius 1.5 seen through a 4 x 1 view; the outer pixels are partially covered
+    struct CenteredSphere { }
+
+    impl Animation<Scene> for CenteredSphere {
+        fn duration(&self) -> Duration {
+            Duration::from_seconds(0.0)
+        }
+
+        fn at(&self, _t: TimeStamp) -> Scene {
+            let camera = OrthographicCamera::new(&OrthographicCameraParameters {
+                eye: pt!(0, 0, 5),
+                look_at: pt!(0, 0, 0),
+                up: vc!(0, 1, 0),
+                view_height: 1.0,
+                aspect_ratio: 4.0,
+            });
+            let sphere = Rc::new(Transformer::new(Transformation3D::scale(1.5, 1.5, 1.5), Rc::new(Sphere::new())));
+
+            Scene {
+                camera: Box::new(camera),
+                root: Rc::new(Tagger::new(IdTag::Object(5), sphere)),
+                light_sources: vec![],
+            }
+        }
+    }
+
+    fn render_centered_sphere() -> RenderedFrame {
+        let settings = RenderSettings {
+            width: 4,
+            height: 1,
+            sampling: SamplingMode::Fixed { samples_per_pixel: 16 },
+            sampler: Rc::new(StratifiedSampler2D::new()),
+            integrator: Rc::new(PathTracer::new(1, 1)),
+            shutter: Shutter::instantaneous(),
+            filter: ReconstructionFilter::pixel_box(),
+        };
+
+        Renderer::new(settings, Box::new(CenteredSphere { })).render_frame(TimeStamp::zero())
+    }
+
+    #[rstest]
+    fn coverage_is_fraction_of_hits() {
+        let image = render_centered_sphere().image;
+        let alpha: Vec<f64> = (0..4).map(|x| image.alpha(Position::<2>::cartesian(x, 0))).collect();
+
+        assert_eq!(vec![1.0, 1.0], alpha[1..3]);
+        assert!(0.25 < alpha[0] && alpha[0] < 0.75);
+        assert!(0.25 < alpha[3] && alpha[3] < 0.75);
+    }
+
+    #[rstest]
+    fn aovs_describe_first_hit() {
+        let aovs = render_centered_sphere().aovs;
+        let center = Position::<2>::cartesian(1, 0);
+        let depth = aovs.depth.get(center).r();
+
+        // The sphere's surface lies between z = 1.1 and z = 1.5 within the pixel
+        assert!((3.5..3.9).contains(&depth), "Depth is {depth}");
+        assert!(aovs.normal.get(center).b() > 0.7);
+        assert_eq!(5.0, aovs.object_id.get(center).r());
+        assert_eq!(-1.0, aovs.material_id.get(center).r());
+    }
+}
@@ -0,0 +1,177 @@
+use std::rc::Rc;
+
+use crate::{
+    animation::{Duration, TimeStamp},
+    samplers::Sampler2D,
+    tracing::integrator::Integrator,
+};
+
+use super::{ReconstructionFilter, VarianceEstimator};
+
+#[derive(Clone)]
+pub struct RenderSettings {
+    pub width: u32,
+    pub height: u32,
+    pub sampling: SamplingMode,
+    pub sampler: Rc<dyn Sampler2D>,
+    pub integrator: Rc<dyn Integrator>,
+    pub shutter: Shutter,
+    pub filter: ReconstructionFilter,
+}
+
+// The shutter opens at the frame's time stamp and stays open for the given interval.
+// The scene is evaluated at the center of each time slice; every sample picks one slice.
+#[derive(Debug, Clone, Copy)]
+pub struct Shutter {
+    pub interval: Duration,
+    pub time_slices: u32,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum SamplingMode {
+    Fixed { samples_per_pixel: u32 },
+    Adaptive(AdaptiveSampling),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct AdaptiveSampling {
+    pub min_samples: u32,
+    pub max_samples: u32,
+    pub variance_threshold: f64,
+}
+
+impl SamplingMode {
+    pub fn is_done(&self, estimator: &VarianceEstimator) -> bool {
+        let sample_count = estimator.sample_count();
+
+        match self {
+            SamplingMode::Fixed { samples_per_pixel } => sample_count >= *samples_per_pixel,
+            SamplingMode::Adaptive(adaptive) => {
+                if sample_count >= adaptive.max_samples {
+                    true
+                } else if sample_count < adaptive.min_samples.max(2) {
+                    false
+                } else {
+                    estimator.variance_of_mean() <= adaptive.variance_threshold
+                }
+            }
+        }
+    }
+}
+
+impl Shutter {
+    pub fn instantaneous() -> Self {
+        Shutter {
+            interval: Duration::from_seconds(0.0),
+            time_slices: 1,
+        }
+    }
+
+    pub fn slice_times(&self, t: TimeStamp) -> Vec<TimeStamp> {
+        debug_assert!(self.time_slices > 0);
+
+        (0..self.time_slices)
+            .map(|index| t + self.interval * ((index as f64 + 0.5) / self.time_slices as f64))
+            .collect()
+    }
+
+    // Index of the time slice containing the given relative time in [0, 1)
+    pub fn slice_index(&self, time: f64) -> usize {
+        ((time * self.time_slices as f64) as usize).min(self.time_slices as usize - 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    #[cfg(test)]
+    use super::*;
+
+    #[rstest]
+    #[case(1, 0, false)]
+    #[case(1, 1, true)]
+    #[case(4, 3, false)]
+    #[case(4, 4, true)]
+    fn fixed(#[case] samples_per_pixel: u32, #[case] sample_count: u32, #[case] expected: bool) {
+        let mode = SamplingMode::Fixed { samples_per_pixel };
+        let mut estimator = VarianceEstimator::new();
+
+        for _ in 0..sample_count {
+            estimator.add(1.0);
+        }
+
+        assert_eq!(expected, mode.is_done(&estimator));
+    }
+
+    #[rstest]
+    fn adaptive_stops_early_on_constant_samples() {
+        let mode = SamplingMode::Adaptive(AdaptiveSampling {
+            min_samples: 4,
+            max_samples: 64,
+            variance_threshold: 0.001,
+        });
+        let mut estimator = VarianceEstimator::new();
+
+        for _ in 0..3 {
+            estimator.add(0.5);
+            assert!(!mode.is_done(&estimator));
+        }
+
+        estimator.add(0.5);
+        assert!(mode.is_done(&estimator));
+    }
+
+    #[rstest]
+    fn adaptive_continues_on_noisy_samples() {
+        let mode = SamplingMode::Adaptive(AdaptiveSampling {
+            min_samples: 4,
+            max_samples: 64,
+            variance_threshold: 0.001,
+        });
+        let mut estimator = VarianceEstimator::new();
+
+        for i in 0..63 {
+            estimator.add((i % 2) as f64);
+            assert!(!mode.is_done(&estimator));
+        }
+
+        estimator.add(0.0);
+        assert!(mode.is_done(&estimator));
+    }
+
+    #[rstest]
+    fn instantaneous_shutter_has_single_slice() {
+        let shutter = Shutter::instantaneous();
+        let times = shutter.slice_times(TimeStamp::new(2.0));
+
+        assert_eq!(vec![TimeStamp::new(2.0)], times);
+        assert_eq!(0, shutter.slice_index(0.99));
+    }
+
+    #[rstest]
+    fn slice_times_are_centered() {
+        let shutter = Shutter {
+            interval: Duration::from_seconds(0.5),
+            time_slices: 4,
+        };
+        let times: Vec<f64> = shutter.slice_times(TimeStamp::new(1.0)).iter().map(|t| t.in_seconds()).collect();
+
+        assert_eq!(vec![1.0625, 1.1875, 1.3125, 1.4375], times);
+    }
+
+    #[rstest]
+    #[case(0.0, 0)]
+    #[case(0.24, 0)]
+    #[case(0.25, 1)]
+    #[case(0.99, 3)]
+    #[case(1.0, 3)]
+    fn slice_index(#[case] time: f64, #[case] expected: usize) {
+        let shutter = Shutter {
+            interval: Duration::from_seconds(1.0),
+            time_slices: 4,
+        };
+
+        assert_eq!(expected, shutter.slice_index(time));
+    }
+}
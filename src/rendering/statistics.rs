@@ -0,0 +1,81 @@
+use crate::{
+    imaging::{colormap::heat_color, image::Image},
+    math::Position,
+};
+
+pub struct SampleCounts {
+    width: u32,
+    height: u32,
+    counts: Vec<u32>,
+}
+
+impl SampleCounts {
+    pub fn new(width: u32, height: u32) -> Self {
+        let counts = vec![0; (width * height) as usize];
+
+        SampleCounts {
+            width,
+            height,
+            counts,
+        }
+    }
+
+    pub fn get(&self, position: Position<2>) -> u32 {
+        self.counts[self.index_of(position)]
+    }
+
+    pub fn set(&mut self, position: Position<2>, count: u32) {
+        let index = self.index_of(position);
+        self.counts[index] = count;
+    }
+
+    pub fn total(&self) -> u64 {
+        self.counts.iter().map(|&count| count as u64).sum()
+    }
+
+    pub fn heatmap(&self) -> Image {
+        let mut image = Image::new(self.width, self.height);
+        let max = self.counts.iter().copied().max().unwrap_or(0).max(1);
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let position = Position::<2>::cartesian(x as i32, y as i32);
+                let t = self.get(position) as f64 / max as f64;
+
+                *image.get_mut(position) = heat_color(t);
+            }
+        }
+
+        image
+    }
+
+    fn index_of(&self, position: Position<2>) -> usize {
+        let x = position.x() as usize;
+        let y = position.y() as usize;
+
+        self.width as usize * y + x
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    #[cfg(test)]
+    use super::*;
+
+    #[cfg(test)]
+    use crate::imaging::color::Color;
+
+    #[rstest]
+    fn heatmap_is_relative_to_maximum() {
+        let mut counts = SampleCounts::new(2, 1);
+        counts.set(Position::<2>::cartesian(0, 0), 0);
+        counts.set(Position::<2>::cartesian(1, 0), 16);
+        let heatmap = counts.heatmap();
+
+        assert_eq!(16, counts.total());
+        assert_eq!(Color::black(), *heatmap.get(Position::<2>::cartesian(0, 0)));
+        assert_eq!(Color::red(), *heatmap.get(Position::<2>::cartesian(1, 0)));
+    }
+}
@@ -0,0 +1,102 @@
+use crate::{
+    animation::{Animation, TimeStamp},
+    imaging::{image::Image, stereo::StereoLayout},
+    tracing::scene::Scene,
+};
+
+use super::{RenderSettings, Renderer};
+
+// Renders a scene once per eye and composes both views into a single image.
+// The scenes are expected to differ only in their camera, e.g. as produced by StereoRig.
+pub struct StereoRenderer {
+    left: Renderer,
+    right: Renderer,
+    layout: StereoLayout,
+}
+
+impl StereoRenderer {
+    pub fn new(settings: RenderSettings, left: Box<dyn Animation<Scene>>, right: Box<dyn Animation<Scene>>, layout: StereoLayout) -> Self {
+        StereoRenderer {
+            left: Renderer::new(settings.clone(), left),
+            right: Renderer::new(settings, right),
+            layout,
+        }
+    }
+
+    pub fn render_frame(&self, t: TimeStamp) -> Image {
+        let left = self.left.render_frame(t);
+        let right = self.right.render_frame(t);
+
+        self.layout.compose(&left.image, &right.image)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use rstest::rstest;
+
+    #[cfg(test)]
+    use super::*;
+
+    use crate::{
+        animation::Duration,
+        cameras::{fov::FieldOfView, perspective::PerspectiveCameraParameters, stereo::{ConvergenceMethod, Eye, StereoRig}},
+        imaging::color::Color,
+        lights::sphere::SphereLight,
+        math::{pt, vc, Angle, Position},
+        primitives::Union,
+        rendering::{ReconstructionFilter, SamplingMode, Shutter},
+        samplers::StratifiedSampler2D,
+        tracing::pathtracer::PathTracer,
+    };
+
+    struct LightScene {
+        eye: Eye,
+    }
+
+    impl Animation<Scene> for LightScene {
+        fn duration(&self) -> Duration {
+            Duration::from_seconds(1.0)
+        }
+
+        fn at(&self, _t: TimeStamp) -> Scene {
+            let parameters = PerspectiveCameraParameters::new(pt!(0, 0, 10), pt!(0, 0, 0), vc!(0, 1, 0), FieldOfView::Vertical(Angle::degrees(20.0)), 9, 9);
+            let rig = StereoRig {
+                interocular_distance: 2.0,
+                convergence_distance: 10.0,
+                method: ConvergenceMethod::OffAxis,
+            };
+
+            Scene {
+                camera: Box::new(rig.camera(&parameters, self.eye)),
+                root: Rc::new(Union::new(vec![])),
+                light_sources: vec![Rc::new(SphereLight::new(Color::white(), pt!(0, 0, 0), 0.3))],
+            }
+        }
+    }
+
+    #[rstest]
+    fn eyes_see_converged_object_at_same_position(#[values(StereoLayout::SideBySide, StereoLayout::OverUnder, StereoLayout::Anaglyph)] layout: StereoLayout) {
+        let settings = RenderSettings {
+            width: 9,
+            height: 9,
+            sampling: SamplingMode::Fixed { samples_per_pixel: 1 },
+            sampler: Rc::new(StratifiedSampler2D::new()),
+            integrator: Rc::new(PathTracer::new(1, 1)),
+            shutter: Shutter::instantaneous(),
+            filter: ReconstructionFilter::pixel_box(),
+        };
+        let renderer = StereoRenderer::new(settings, Box::new(LightScene { eye: Eye::Left }), Box::new(LightScene { eye: Eye::Right }), layout);
+        let image = renderer.render_frame(TimeStamp::zero());
+        let (width, height) = layout.composite_size(9, 9);
+
+        assert_eq!((width, height), (image.width(), image.height()));
+
+        // The light lies at the convergence distance, so both eyes see it in the center and not in the corner
+        assert_eq!(Color::white(), *image.get(Position::<2>::cartesian(4, 4)));
+        assert_eq!(Color::black(), *image.get(Position::<2>::cartesian(0, 0)));
+        assert_eq!(Color::white(), *image.get(Position::<2>::cartesian((width - 5) as i32, (height - 5) as i32)));
+    }
+}
@@ -0,0 +1,152 @@
+use std::f64::consts::PI;
+
+use crate::{math::{Point, Vector}, pt, util::Random};
+
+const SAMPLE_COUNT: usize = 100000;
+const SUBDIVISIONS: usize = 16;
+const MINIMUM_EXPECTED_COUNT: f64 = 5.0;
+
+// Standard normal quantile for a significance level of 0.001
+const Z_SCORE: f64 = 3.09;
+
+// Pearson's chi-square goodness of fit test.
+// Warped samples are mapped onto (u, v) in [0, 1)^2 and binned; the expected bin counts
+// are obtained by numerically integrating the density (expressed in du dv) over each bin.
+pub struct ChiSquareTest {
+    u_bins: usize,
+    v_bins: usize,
+}
+
+#[derive(Debug)]
+pub struct ChiSquareOutcome {
+    pub statistic: f64,
+    pub degrees_of_freedom: usize,
+    pub critical_value: f64,
+}
+
+impl ChiSquareTest {
+    pub fn new(u_bins: usize, v_bins: usize) -> Self {
+        ChiSquareTest { u_bins, v_bins }
+    }
+
+    pub fn run<S, D>(&self, seed: u64, sample: S, density: D) -> ChiSquareOutcome
+    where
+        S: Fn(&Point<2>) -> (f64, f64),
+        D: Fn(f64, f64) -> f64,
+    {
+        let observed = self.observe(seed, sample);
+        let expected = self.expect(density);
+
+        let mut statistic = 0.0;
+        let mut degrees_of_freedom: usize = 0;
+        let mut pooled_observed = 0.0;
+        let mut pooled_expected = 0.0;
+
+        for (observed, expected) in observed.into_iter().zip(expected) {
+            if expected < MINIMUM_EXPECTED_COUNT {
+                pooled_observed += observed;
+                pooled_expected += expected;
+            } else {
+                statistic += (observed - expected).powi(2) / expected;
+                degrees_of_freedom += 1;
+            }
+        }
+
+        if pooled_expected > 0.0 {
+            statistic += (pooled_observed - pooled_expected).powi(2) / pooled_expected;
+            degrees_of_freedom += 1;
+        } else if pooled_observed > 0.0 {
+            statistic = f64::INFINITY;
+        }
+
+        degrees_of_freedom = degrees_of_freedom.saturating_sub(1).max(1);
+
+        ChiSquareOutcome {
+            statistic,
+            degrees_of_freedom,
+            critical_value: critical_value(degrees_of_freedom),
+        }
+    }
+
+    fn bin_index(&self, u: f64, v: f64) -> usize {
+        let i = ((u * self.u_bins as f64) as usize).min(self.u_bins - 1);
+        let j = ((v * self.v_bins as f64) as usize).min(self.v_bins - 1);
+
+        i * self.v_bins + j
+    }
+
+    fn observe<S: Fn(&Point<2>) -> (f64, f64)>(&self, seed: u64, sample: S) -> Vec<f64> {
+        let mut random = Random::new(seed);
+        let mut result = vec![0.0; self.u_bins * self.v_bins];
+
+        for _ in 0..SAMPLE_COUNT {
+            let (u, v) = sample(&pt!(random.next_f64(), random.next_f64()));
+            result[self.bin_index(u, v)] += 1.0;
+        }
+
+        result
+    }
+
+    fn expect<D: Fn(f64, f64) -> f64>(&self, density: D) -> Vec<f64> {
+        let du = 1.0 / (self.u_bins * SUBDIVISIONS) as f64;
+        let dv = 1.0 / (self.v_bins * SUBDIVISIONS) as f64;
+        let mut result = vec![0.0; self.u_bins * self.v_bins];
+
+        for i in 0..self.u_bins * SUBDIVISIONS {
+            for j in 0..self.v_bins * SUBDIVISIONS {
+                let u = (i as f64 + 0.5) * du;
+                let v = (j as f64 + 0.5) * dv;
+
+                result[self.bin_index(u, v)] += density(u, v) * du * dv * SAMPLE_COUNT as f64;
+            }
+        }
+
+        result
+    }
+}
+
+impl ChiSquareOutcome {
+    pub fn passes(&self) -> bool {
+        self.statistic <= self.critical_value
+    }
+}
+
+// Wilson-Hilferty approximation of the chi-square quantile
+fn critical_value(degrees_of_freedom: usize) -> f64 {
+    let k = degrees_of_freedom as f64;
+    let a = 2.0 / (9.0 * k);
+
+    k * (1.0 - a + Z_SCORE * a.sqrt()).powi(3)
+}
+
+// Equal solid angle parameterization: u = (cos(theta) + 1) / 2, v = phi / 2pi, so that d(omega) = 4pi du dv
+pub fn spherical_coordinates(direction: &Vector<3>) -> (f64, f64) {
+    let direction = direction.normalized();
+    let u = (direction.z().clamp(-1.0, 1.0) + 1.0) / 2.0;
+    let v = direction.y().atan2(direction.x()).rem_euclid(2.0 * PI) / (2.0 * PI);
+
+    (u, v)
+}
+
+pub fn direction(u: f64, v: f64) -> Vector<3> {
+    let cos_theta = 2.0 * u - 1.0;
+    let sin_theta = (1.0 - cos_theta * cos_theta).max(0.0).sqrt();
+    let phi = 2.0 * PI * v;
+
+    Vector::new([sin_theta * phi.cos(), sin_theta * phi.sin(), cos_theta])
+}
+
+// Equal area parameterization of the unit disk: u = r^2, v = phi / 2pi, so that dA = pi du dv
+pub fn polar_coordinates(x: f64, y: f64) -> (f64, f64) {
+    let u = x * x + y * y;
+    let v = y.atan2(x).rem_euclid(2.0 * PI) / (2.0 * PI);
+
+    (u, v)
+}
+
+pub fn cartesian_coordinates(u: f64, v: f64) -> (f64, f64) {
+    let radius = u.sqrt();
+    let phi = 2.0 * PI * v;
+
+    (radius * phi.cos(), radius * phi.sin())
+}
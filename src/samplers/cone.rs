@@ -0,0 +1,77 @@
+use std::f64::consts::PI;
+
+use crate::math::{Point, Vector};
+
+use super::{sphere::from_spherical, Warp};
+
+// Uniform over the directions within the given angle of the z-axis.
+// pdf = 1 / (2pi (1 - cos(theta_max))) with respect to solid angle
+pub struct UniformConeSampler {
+    cos_max_angle: f64,
+}
+
+impl UniformConeSampler {
+    pub fn new(cos_max_angle: f64) -> Self {
+        debug_assert!((-1.0..1.0).contains(&cos_max_angle));
+
+        UniformConeSampler { cos_max_angle }
+    }
+}
+
+impl Warp<Vector<3>> for UniformConeSampler {
+    fn warp(&self, sample: &Point<2>) -> Vector<3> {
+        let cos_theta = 1.0 - sample.x() * (1.0 - self.cos_max_angle);
+
+        from_spherical(cos_theta, 2.0 * PI * sample.y())
+    }
+
+    fn pdf(&self, direction: &Vector<3>) -> f64 {
+        if direction.normalized().z() >= self.cos_max_angle {
+            1.0 / (2.0 * PI * (1.0 - self.cos_max_angle))
+        } else {
+            0.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    #[cfg(test)]
+    use super::*;
+
+    use crate::{math::approx, pt, vc, samplers::chisquare::{self, ChiSquareTest}};
+
+    #[rstest]
+    fn corner_points_along_axis() {
+        let sampler = UniformConeSampler::new(0.5);
+
+        assert_eq!(approx(vc!(0, 0, 1)), sampler.warp(&pt!(0, 0)));
+    }
+
+    #[rstest]
+    fn samples_inside_cone(#[values(0.5, 0.9, -0.5)] cos_max_angle: f64) {
+        let sampler = UniformConeSampler::new(cos_max_angle);
+
+        for x in [0.0, 0.25, 0.5, 0.999] {
+            let direction = sampler.warp(&pt!(x, 0.3));
+
+            assert!(direction.is_unit());
+            assert!(direction.z() >= cos_max_angle - 0.000001);
+        }
+    }
+
+    #[rstest]
+    fn distribution_matches_pdf(#[values(0.6, 0.0, -0.6)] cos_max_angle: f64) {
+        let sampler = UniformConeSampler::new(cos_max_angle);
+        let test = ChiSquareTest::new(10, 20);
+        let outcome = test.run(
+            0,
+            |sample| chisquare::spherical_coordinates(&sampler.warp(sample)),
+            |u, v| sampler.pdf(&chisquare::direction(u, v)) * 4.0 * PI,
+        );
+
+        assert!(outcome.passes(), "{outcome:?}");
+    }
+}
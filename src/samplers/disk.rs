@@ -0,0 +1,88 @@
+use std::f64::consts::PI;
+
+use crate::{math::Point, pt};
+
+use super::Warp;
+
+// Concentric mapping (Shirley and Chiu) onto the unit disk.
+// pdf = 1 / pi with respect to area
+pub struct UniformDiskSampler { }
+
+impl UniformDiskSampler {
+    pub fn new() -> Self {
+        UniformDiskSampler { }
+    }
+}
+
+impl Warp<Point<2>> for UniformDiskSampler {
+    fn warp(&self, sample: &Point<2>) -> Point<2> {
+        let a = 2.0 * sample.x() - 1.0;
+        let b = 2.0 * sample.y() - 1.0;
+
+        if a == 0.0 && b == 0.0 {
+            pt!(0, 0)
+        } else {
+            let (radius, phi) = if a.abs() > b.abs() {
+                (a, PI / 4.0 * (b / a))
+            } else {
+                (b, PI / 2.0 - PI / 4.0 * (a / b))
+            };
+
+            pt!(radius * phi.cos(), radius * phi.sin())
+        }
+    }
+
+    fn pdf(&self, point: &Point<2>) -> f64 {
+        if point.x() * point.x() + point.y() * point.y() <= 1.0 {
+            1.0 / PI
+        } else {
+            0.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    #[cfg(test)]
+    use super::*;
+
+    use crate::{samplers::chisquare::{self, ChiSquareTest}, math::approx};
+
+    #[rstest]
+    fn center_maps_to_origin() {
+        let sampler = UniformDiskSampler::new();
+
+        assert_eq!(approx(pt!(0, 0)), sampler.warp(&pt!(0.5, 0.5)));
+    }
+
+    #[rstest]
+    fn corners_map_to_boundary(#[values(0.0, 1.0)] x: f64, #[values(0.0, 1.0)] y: f64) {
+        let sampler = UniformDiskSampler::new();
+        let point = sampler.warp(&pt!(x, y));
+
+        assert!((point.x().hypot(point.y()) - 1.0).abs() < 0.000001);
+    }
+
+    #[rstest]
+    fn distribution_matches_pdf(#[values(0, 1)] seed: u64) {
+        let sampler = UniformDiskSampler::new();
+        let test = ChiSquareTest::new(10, 20);
+
+        // Bins are equal-area cells in (r^2, phi), so dA = pi du dv
+        let outcome = test.run(
+            seed,
+            |sample| {
+                let point = sampler.warp(sample);
+                chisquare::polar_coordinates(point.x(), point.y())
+            },
+            |u, v| {
+                let (x, y) = chisquare::cartesian_coordinates(u, v);
+                sampler.pdf(&pt!(x, y)) * PI
+            },
+        );
+
+        assert!(outcome.passes(), "{outcome:?}");
+    }
+}
@@ -0,0 +1,98 @@
+use super::sampler::{rectangle_seed, IndexedRefiner, Sampler2D};
+use crate::{math::{Point, Rectangle}, pt, util::{Random, Refine}};
+
+pub struct HaltonSampler2D {
+    seed: u64,
+}
+
+impl HaltonSampler2D {
+    pub fn new(seed: u64) -> Self {
+        HaltonSampler2D { seed }
+    }
+}
+
+impl Sampler2D for HaltonSampler2D {
+    fn sample(&self, rectangle: Rectangle<2>) -> Box<dyn Refine<Point<2>>> {
+        // Cranley-Patterson rotation decorrelates pixels while keeping the sequence's stratification
+        let mut random = Random::new(rectangle_seed(self.seed, &rectangle));
+        let dx = random.next_f64();
+        let dy = random.next_f64();
+
+        Box::new(IndexedRefiner::new(rectangle, move |index| {
+            let x = (radical_inverse(index, 2) + dx).fract();
+            let y = (radical_inverse(index, 3) + dy).fract();
+
+            pt!(x, y)
+        }))
+    }
+}
+
+pub fn radical_inverse(index: u32, base: u32) -> f64 {
+    let inverse_base = 1.0 / base as f64;
+    let mut index = index;
+    let mut factor = inverse_base;
+    let mut result = 0.0;
+
+    while index > 0 {
+        result += (index % base) as f64 * factor;
+        index /= base;
+        factor *= inverse_base;
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    #[cfg(test)]
+    use super::*;
+
+    use crate::{math::approx, vc};
+
+    #[rstest]
+    #[case(0, 2, 0.0)]
+    #[case(1, 2, 0.5)]
+    #[case(2, 2, 0.25)]
+    #[case(3, 2, 0.75)]
+    #[case(4, 2, 0.125)]
+    #[case(1, 3, 1.0 / 3.0)]
+    #[case(2, 3, 2.0 / 3.0)]
+    #[case(3, 3, 1.0 / 9.0)]
+    #[case(5, 3, 7.0 / 9.0)]
+    fn radical_inverses(#[case] index: u32, #[case] base: u32, #[case] expected: f64) {
+        assert_eq!(approx(expected), radical_inverse(index, base));
+    }
+
+    #[rstest]
+    fn stratified_in_both_dimensions(#[values(0, 1, 2)] seed: u64) {
+        let sampler = HaltonSampler2D::new(seed);
+        let mut refiner = sampler.sample(Rectangle::new(pt!(0, 0), vc!(1, 0), vc!(0, 1)));
+        let mut x_strata = [false; 8];
+        let mut y_strata = [false; 9];
+        let mut points = Vec::new();
+
+        for _ in 0..72 {
+            points.push(refiner.current());
+            refiner.refine();
+        }
+
+        for p in points.iter().take(8) {
+            let stratum = (p.x() * 8.0) as usize;
+            assert!(!x_strata[stratum]);
+            x_strata[stratum] = true;
+        }
+
+        for p in points.iter().take(9) {
+            let stratum = (p.y() * 9.0) as usize;
+            assert!(!y_strata[stratum]);
+            y_strata[stratum] = true;
+        }
+
+        for p in points {
+            assert!((0.0..1.0).contains(&p.x()));
+            assert!((0.0..1.0).contains(&p.y()));
+        }
+    }
+}
@@ -1,36 +1,106 @@
+use std::f64::consts::PI;
+
 use crate::{util::Refine, samplers::{Sampler2D, StratifiedSampler2D}, vc, pt};
 
-use crate::math::{Point, IntervalMapper, Angle, Vector, Interval, Rectangle, coords::Spherical};
+use crate::math::{Point, Vector, Rectangle};
+
+use super::{disk::UniformDiskSampler, sphere::from_spherical, Warp};
+
+// All hemisphere samplers are centered around the z-axis
+
+// pdf = 1 / 2pi with respect to solid angle
+pub struct UniformHemisphereSampler { }
+
+// pdf = cos(theta) / pi with respect to solid angle
+pub struct CosineHemisphereSampler {
+    disk: UniformDiskSampler,
+}
+
+// pdf = (n + 1) / 2pi * cos(theta)^n with respect to solid angle
+pub struct CosinePowerHemisphereSampler {
+    exponent: f64,
+}
 
+// Stratified cosine-weighted directions
 pub struct HemisphereSampler {
     samples: Box<dyn Refine<Point<2>>>,
-    azimuth_mapper: IntervalMapper<f64, Angle>,
-    elevation_mapper: IntervalMapper<f64, Angle>,
+    warp: CosineHemisphereSampler,
 }
 
-impl HemisphereSampler {
+impl UniformHemisphereSampler {
     pub fn new() -> Self {
-        HemisphereSampler {
-            samples: Self::create_samples(),
-            azimuth_mapper: Self::create_azimuth_mapper(),
-            elevation_mapper: Self::create_elevation_mapper(),
+        UniformHemisphereSampler { }
+    }
+}
+
+impl Warp<Vector<3>> for UniformHemisphereSampler {
+    fn warp(&self, sample: &Point<2>) -> Vector<3> {
+        from_spherical(sample.x(), 2.0 * PI * sample.y())
+    }
+
+    fn pdf(&self, direction: &Vector<3>) -> f64 {
+        if direction.z() >= 0.0 {
+            1.0 / (2.0 * PI)
+        } else {
+            0.0
         }
     }
+}
+
+impl CosineHemisphereSampler {
+    pub fn new() -> Self {
+        CosineHemisphereSampler {
+            disk: UniformDiskSampler::new(),
+        }
+    }
+}
+
+impl Warp<Vector<3>> for CosineHemisphereSampler {
+    // Malley's method: project uniformly distributed points on the disk up onto the hemisphere
+    fn warp(&self, sample: &Point<2>) -> Vector<3> {
+        let point = self.disk.warp(sample);
+        let z = (1.0 - point.x() * point.x() - point.y() * point.y()).max(0.0).sqrt();
+
+        vc!(point.x(), point.y(), z)
+    }
+
+    fn pdf(&self, direction: &Vector<3>) -> f64 {
+        direction.normalized().z().max(0.0) / PI
+    }
+}
+
+impl CosinePowerHemisphereSampler {
+    pub fn new(exponent: f64) -> Self {
+        debug_assert!(exponent >= 0.0);
 
-    fn create_azimuth_mapper() -> IntervalMapper<f64, Angle> {
-        let start = Angle::degrees(0.0);
-        let end = Angle::degrees(180.0);
-        let azimuth_interval = Interval::new(start, end);
-        let unit_interval = Interval::new(0.0, 1.0);
-        IntervalMapper::new(unit_interval, azimuth_interval)
+        CosinePowerHemisphereSampler { exponent }
     }
+}
+
+impl Warp<Vector<3>> for CosinePowerHemisphereSampler {
+    fn warp(&self, sample: &Point<2>) -> Vector<3> {
+        let cos_theta = (1.0 - sample.x()).powf(1.0 / (self.exponent + 1.0));
 
-    fn create_elevation_mapper() -> IntervalMapper<f64, Angle> {
-        let start = Angle::degrees(-90.0);
-        let end = Angle::degrees(90.0);
-        let azimuth_interval = Interval::new(start, end);
-        let unit_interval = Interval::new(0.0, 1.0);
-        IntervalMapper::new(unit_interval, azimuth_interval)
+        from_spherical(cos_theta, 2.0 * PI * sample.y())
+    }
+
+    fn pdf(&self, direction: &Vector<3>) -> f64 {
+        let cos_theta = direction.normalized().z();
+
+        if cos_theta >= 0.0 {
+            (self.exponent + 1.0) / (2.0 * PI) * cos_theta.powf(self.exponent)
+        } else {
+            0.0
+        }
+    }
+}
+
+impl HemisphereSampler {
+    pub fn new() -> Self {
+        HemisphereSampler {
+            samples: Self::create_samples(),
+            warp: CosineHemisphereSampler::new(),
+        }
     }
 
     fn create_samples() -> Box<dyn Refine<Point<2>>> {
@@ -38,25 +108,15 @@ impl HemisphereSampler {
         let sampler = StratifiedSampler2D::new();
         sampler.sample(rectangle)
     }
+
+    pub fn pdf(&self, direction: &Vector<3>) -> f64 {
+        self.warp.pdf(direction)
+    }
 }
 
 impl Refine<Vector<3>> for HemisphereSampler {
     fn current(&self) -> Vector<3> {
-        let sample = self.samples.current();
-        let x = sample.x();
-        let y = sample.y();
-        let spherical_coordinates = Spherical {
-            radius: 1.0,
-            azimuth: self.azimuth_mapper.map(x),
-            elevation: self.elevation_mapper.map(y),
-        };
-        let cartesian_coordinates = spherical_coordinates.to_cartesian3d();
-
-        let x = cartesian_coordinates.x;
-        let y = cartesian_coordinates.y;
-        let z = cartesian_coordinates.z;
-
-        vc!(x, y, z)
+        self.warp.warp(&self.samples.current())
     }
 
     fn refine(&mut self) {
@@ -68,24 +128,129 @@ impl Refine<Vector<3>> for HemisphereSampler {
 mod test {
     use rstest::rstest;
 
-    use crate::math::approx;
+    use crate::{math::approx, samplers::chisquare::{self, ChiSquareTest}};
 
     #[cfg(test)]
     use super::*;
 
+    fn check_distribution(warp: &dyn Warp<Vector<3>>, seed: u64) -> chisquare::ChiSquareOutcome {
+        let test = ChiSquareTest::new(10, 20);
+
+        test.run(
+            seed,
+            |sample| chisquare::spherical_coordinates(&warp.warp(sample)),
+            |u, v| warp.pdf(&chisquare::direction(u, v)) * 4.0 * PI,
+        )
+    }
+
     #[rstest]
     fn hemispherical_refiner() {
         let mut vector = HemisphereSampler::new();
-        let constant = 2.0f64.sqrt() / 2.0;
 
         assert_eq!(approx(vc!(0, 0, 1)), vector.current());
-        vector.refine();
-        assert_eq!(approx(vc!(0.5, -constant, 0.5)), vector.current());
-        vector.refine();
-        assert_eq!(approx(vc!(-0.5, -constant, 0.5)), vector.current());
-        vector.refine();
-        assert_eq!(approx(vc!(0.5, constant, 0.5)), vector.current());
-        vector.refine();
-        assert_eq!(approx(vc!(-0.5, constant, 0.5)), vector.current());
-    }
-}
\ No newline at end of file
+
+        for _ in 0..100 {
+            vector.refine();
+            let direction = vector.current();
+
+            assert!(direction.is_unit());
+            assert!(direction.z() >= 0.0);
+        }
+    }
+
+    #[rstest]
+    fn hemispherical_refiner_covers_hemisphere_evenly() {
+        let mut vector = HemisphereSampler::new();
+        // Complete grids of 1, 4, ..., 1024 samples
+        let sample_count = 1365;
+        let mut total = vc!(0, 0, 0);
+
+        for _ in 0..sample_count {
+            total = total + vector.current();
+            vector.refine();
+        }
+
+        let mean = total * (1.0 / sample_count as f64);
+
+        // The mean of cos(theta) under a cosine-weighted distribution is 2/3
+        assert!(mean.x().abs() < 0.01);
+        assert!(mean.y().abs() < 0.01);
+        assert!((mean.z() - 2.0 / 3.0).abs() < 0.01);
+    }
+
+    #[rstest]
+    fn samples_in_upper_hemisphere(#[values(0.0, 0.25, 0.5, 0.999)] x: f64, #[values(0.0, 0.4, 0.9)] y: f64) {
+        let sample = pt!(x, y);
+        let warps: [&dyn Warp<Vector<3>>; 3] = [
+            &UniformHemisphereSampler::new(),
+            &CosineHemisphereSampler::new(),
+            &CosinePowerHemisphereSampler::new(20.0),
+        ];
+
+        for warp in warps {
+            let direction = warp.warp(&sample);
+
+            assert!(direction.is_unit());
+            assert!(direction.z() >= 0.0);
+        }
+    }
+
+    #[rstest]
+    fn uniform_distribution_matches_pdf(#[values(0, 1)] seed: u64) {
+        let outcome = check_distribution(&UniformHemisphereSampler::new(), seed);
+
+        assert!(outcome.passes(), "{outcome:?}");
+    }
+
+    #[rstest]
+    fn cosine_distribution_matches_pdf(#[values(0, 1)] seed: u64) {
+        let outcome = check_distribution(&CosineHemisphereSampler::new(), seed);
+
+        assert!(outcome.passes(), "{outcome:?}");
+    }
+
+    #[rstest]
+    fn cosine_power_distribution_matches_pdf(#[values(0.0, 1.0, 5.0, 20.0)] exponent: f64) {
+        let outcome = check_distribution(&CosinePowerHemisphereSampler::new(exponent), 0);
+
+        assert!(outcome.passes(), "{outcome:?}");
+    }
+
+    #[rstest]
+    fn mismatched_pdf_is_detected() {
+        let uniform = UniformHemisphereSampler::new();
+        let cosine = CosineHemisphereSampler::new();
+        let test = ChiSquareTest::new(10, 20);
+        let outcome = test.run(
+            0,
+            |sample| chisquare::spherical_coordinates(&uniform.warp(sample)),
+            |u, v| cosine.pdf(&chisquare::direction(u, v)) * 4.0 * PI,
+        );
+
+        assert!(!outcome.passes(), "{outcome:?}");
+    }
+
+    #[rstest]
+    fn pdfs_integrate_to_one() {
+        let warps: [&dyn Warp<Vector<3>>; 3] = [
+            &UniformHemisphereSampler::new(),
+            &CosineHemisphereSampler::new(),
+            &CosinePowerHemisphereSampler::new(10.0),
+        ];
+        let resolution = 400;
+
+        for warp in warps {
+            let mut total = 0.0;
+
+            for i in 0..resolution {
+                for j in 0..resolution {
+                    let u = (i as f64 + 0.5) / resolution as f64;
+                    let v = (j as f64 + 0.5) / resolution as f64;
+                    total += warp.pdf(&chisquare::direction(u, v)) * 4.0 * PI / (resolution * resolution) as f64;
+                }
+            }
+
+            assert!((total - 1.0).abs() < 0.001, "{total}");
+        }
+    }
+}
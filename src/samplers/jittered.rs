@@ -0,0 +1,98 @@
+use super::sampler::{rectangle_seed, Sampler2D};
+use crate::{math::{Point, Position, Rasterizer, Rectangle}, pt, util::{Random, Refine}};
+
+pub struct JitteredSampler2D {
+    seed: u64,
+}
+
+struct SampleRefiner {
+    rasterizer: Rasterizer<2>,
+    random: Random,
+    x: i32,
+    y: i32,
+    offset: Point<2>,
+}
+
+impl JitteredSampler2D {
+    pub fn new(seed: u64) -> Self {
+        JitteredSampler2D { seed }
+    }
+}
+
+impl Sampler2D for JitteredSampler2D {
+    fn sample(&self, rectangle: Rectangle<2>) -> Box<dyn Refine<Point<2>>> {
+        let mut random = Random::new(rectangle_seed(self.seed, &rectangle));
+        let offset = pt!(random.next_f64(), random.next_f64());
+        let rasterizer = Rasterizer::<2>::new(rectangle, 1, 1);
+
+        Box::new(SampleRefiner {
+            rasterizer,
+            random,
+            x: 0,
+            y: 0,
+            offset,
+        })
+    }
+}
+
+impl Refine<Point<2>> for SampleRefiner {
+    fn current(&self) -> Point<2> {
+        self.rasterizer.at(Position::<2>::cartesian(self.x, self.y)).from_relative(&self.offset)
+    }
+
+    fn refine(&mut self) {
+        self.x += 1;
+        if self.x == self.rasterizer.width as i32 {
+            self.x = 0;
+            self.y += 1;
+
+            if self.y == self.rasterizer.height as i32 {
+                self.y = 0;
+                self.rasterizer.width *= 2;
+                self.rasterizer.height *= 2;
+            }
+        }
+
+        self.offset = pt!(self.random.next_f64(), self.random.next_f64());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    #[cfg(test)]
+    use super::*;
+
+    use crate::vc;
+
+    #[rstest]
+    fn one_sample_per_cell(#[values(0, 1, 2, 3)] seed: u64) {
+        let sampler = JitteredSampler2D::new(seed);
+        let mut refiner = sampler.sample(Rectangle::new(pt!(0, 0), vc!(16, 0), vc!(0, 16)));
+
+        for level in 0..4 {
+            let cells = 1 << level;
+            let cell_size = 16.0 / cells as f64;
+
+            for y in 0..cells {
+                for x in 0..cells {
+                    let p = refiner.current();
+
+                    assert!(x as f64 * cell_size <= p.x() && p.x() < (x + 1) as f64 * cell_size);
+                    assert!(y as f64 * cell_size <= p.y() && p.y() < (y + 1) as f64 * cell_size);
+
+                    refiner.refine();
+                }
+            }
+        }
+    }
+
+    #[rstest]
+    fn jittered_away_from_center() {
+        let sampler = JitteredSampler2D::new(0);
+        let refiner = sampler.sample(Rectangle::new(pt!(0, 0), vc!(1, 0), vc!(0, 1)));
+
+        assert_ne!(pt!(0.5, 0.5), refiner.current());
+    }
+}
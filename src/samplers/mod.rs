@@ -1,7 +1,28 @@
 mod sampler;
 mod stratified;
 mod hemisphere;
+mod random;
+mod jittered;
+mod multijittered;
+mod halton;
+mod sobol;
+mod warp;
+mod disk;
+mod sphere;
+mod cone;
+mod polygon;
+#[cfg(test)]
+mod chisquare;
 
 pub use sampler::Sampler2D;
 pub use stratified::StratifiedSampler2D;
-pub use hemisphere::HemisphereSampler;
+pub use hemisphere::{HemisphereSampler, CosineHemisphereSampler, CosinePowerHemisphereSampler};
+pub use random::RandomSampler2D;
+pub use jittered::JitteredSampler2D;
+pub use multijittered::MultiJitteredSampler2D;
+pub use halton::HaltonSampler2D;
+pub use sobol::SobolSampler2D;
+pub use warp::Warp;
+pub use disk::UniformDiskSampler;
+pub use cone::UniformConeSampler;
+pub use polygon::UniformPolygonSampler;
@@ -0,0 +1,170 @@
+use super::sampler::{rectangle_seed, IndexedRefiner, Sampler2D};
+use crate::{math::{Point, Rectangle}, pt, util::Refine};
+
+// Kensler, "Correlated Multi-Jittered Sampling"
+pub struct MultiJitteredSampler2D {
+    seed: u64,
+    columns: u32,
+    rows: u32,
+}
+
+impl MultiJitteredSampler2D {
+    pub fn new(seed: u64, columns: u32, rows: u32) -> Self {
+        debug_assert!(columns > 0 && rows > 0);
+
+        MultiJitteredSampler2D { seed, columns, rows }
+    }
+}
+
+impl Sampler2D for MultiJitteredSampler2D {
+    fn sample(&self, rectangle: Rectangle<2>) -> Box<dyn Refine<Point<2>>> {
+        let pattern = rectangle_seed(self.seed, &rectangle) as u32;
+        let columns = self.columns;
+        let rows = self.rows;
+        let pattern_size = columns * rows;
+
+        Box::new(IndexedRefiner::new(rectangle, move |index| {
+            let pattern = pattern.wrapping_add(index / pattern_size);
+
+            correlated_multi_jittered(index % pattern_size, columns, rows, pattern)
+        }))
+    }
+}
+
+fn correlated_multi_jittered(index: u32, columns: u32, rows: u32, pattern: u32) -> Point<2> {
+    let m = columns;
+    let n = rows;
+    let s = permute(index, m * n, pattern.wrapping_mul(0x51633e2d));
+    let sx = permute(s % m, m, pattern.wrapping_mul(0xa511e9b3));
+    let sy = permute(s / m, n, pattern.wrapping_mul(0x63d83595));
+    let jx = random_float(s, pattern.wrapping_mul(0xa399d265));
+    let jy = random_float(s, pattern.wrapping_mul(0x711ad6a5));
+
+    let x = ((s % m) as f64 + (sy as f64 + jx) / n as f64) / m as f64;
+    let y = ((s / m) as f64 + (sx as f64 + jy) / m as f64) / n as f64;
+
+    pt!(x, y)
+}
+
+fn permute(i: u32, length: u32, pattern: u32) -> u32 {
+    if length == 1 {
+        return 0;
+    }
+
+    let p = pattern;
+    let mut w = length - 1;
+    w |= w >> 1;
+    w |= w >> 2;
+    w |= w >> 4;
+    w |= w >> 8;
+    w |= w >> 16;
+
+    let mut i = i;
+
+    loop {
+        i ^= p;
+        i = i.wrapping_mul(0xe170893d);
+        i ^= p >> 16;
+        i ^= (i & w) >> 4;
+        i ^= p >> 8;
+        i = i.wrapping_mul(0x0929eb3f);
+        i ^= p >> 23;
+        i ^= (i & w) >> 1;
+        i = i.wrapping_mul(1 | p >> 27);
+        i = i.wrapping_mul(0x6935fa69);
+        i ^= (i & w) >> 11;
+        i = i.wrapping_mul(0x74dcb303);
+        i ^= (i & w) >> 2;
+        i = i.wrapping_mul(0x9e501cc3);
+        i ^= (i & w) >> 2;
+        i = i.wrapping_mul(0xc860a3df);
+        i &= w;
+        i ^= i >> 5;
+
+        if i < length {
+            break;
+        }
+    }
+
+    (i.wrapping_add(p)) % length
+}
+
+fn random_float(i: u32, pattern: u32) -> f64 {
+    let p = pattern;
+    let mut i = i ^ p;
+
+    i ^= i >> 17;
+    i ^= i >> 10;
+    i = i.wrapping_mul(0xb36534e5);
+    i ^= i >> 12;
+    i ^= i >> 21;
+    i = i.wrapping_mul(0x93fc4795);
+    i ^= 0xdf6e307f;
+    i ^= i >> 17;
+    i = i.wrapping_mul(1 | p >> 18);
+
+    i as f64 / 4294967296.0
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    #[cfg(test)]
+    use super::*;
+
+    use crate::vc;
+
+    #[rstest]
+    fn permute_is_permutation(#[values(1, 2, 3, 7, 16, 100)] length: u32, #[values(0, 1, 12345)] pattern: u32) {
+        let mut seen = vec![false; length as usize];
+
+        for i in 0..length {
+            let j = permute(i, length, pattern);
+            assert!(!seen[j as usize]);
+            seen[j as usize] = true;
+        }
+    }
+
+    #[rstest]
+    fn multi_jittered_stratification(#[values((4, 4), (3, 5), (8, 2))] dimensions: (u32, u32), #[values(0, 1, 2)] seed: u64) {
+        let (columns, rows) = dimensions;
+        let count = (columns * rows) as usize;
+        let sampler = MultiJitteredSampler2D::new(seed, columns, rows);
+        let mut refiner = sampler.sample(Rectangle::new(pt!(0, 0), vc!(1, 0), vc!(0, 1)));
+        let mut cells = vec![false; count];
+        let mut x_strata = vec![false; count];
+        let mut y_strata = vec![false; count];
+
+        for _ in 0..count {
+            let p = refiner.current();
+            let cell = (p.y() * rows as f64) as usize * columns as usize + (p.x() * columns as f64) as usize;
+            let x_stratum = (p.x() * count as f64) as usize;
+            let y_stratum = (p.y() * count as f64) as usize;
+
+            assert!(!cells[cell]);
+            assert!(!x_strata[x_stratum]);
+            assert!(!y_strata[y_stratum]);
+
+            cells[cell] = true;
+            x_strata[x_stratum] = true;
+            y_strata[y_stratum] = true;
+
+            refiner.refine();
+        }
+    }
+
+    #[rstest]
+    fn next_pattern_differs() {
+        let sampler = MultiJitteredSampler2D::new(0, 2, 2);
+        let mut refiner = sampler.sample(Rectangle::new(pt!(0, 0), vc!(1, 0), vc!(0, 1)));
+        let mut points = Vec::new();
+
+        for _ in 0..8 {
+            points.push(refiner.current());
+            refiner.refine();
+        }
+
+        assert_ne!(points[0..4], points[4..8]);
+    }
+}
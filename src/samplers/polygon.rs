@@ -0,0 +1,128 @@
+use std::f64::consts::PI;
+
+use crate::{math::{Angle, Point}, pt};
+
+use super::Warp;
+
+// Uniform over the regular polygon inscribed in the unit circle with a vertex at the given rotation.
+// pdf = 1 / area with respect to area
+pub struct UniformPolygonSampler {
+    sides: u32,
+    rotation: Angle,
+}
+
+impl UniformPolygonSampler {
+    pub fn new(sides: u32, rotation: Angle) -> Self {
+        debug_assert!(sides >= 3);
+
+        UniformPolygonSampler { sides, rotation }
+    }
+
+    fn sector_angle(&self) -> f64 {
+        2.0 * PI / self.sides as f64
+    }
+
+    fn vertex(&self, index: u32) -> Point<2> {
+        let angle = self.rotation.in_radians() + index as f64 * self.sector_angle();
+
+        pt!(angle.cos(), angle.sin())
+    }
+
+    pub fn area(&self) -> f64 {
+        self.sides as f64 / 2.0 * self.sector_angle().sin()
+    }
+
+    pub fn contains(&self, point: &Point<2>) -> bool {
+        let relative_angle = (point.y().atan2(point.x()) - self.rotation.in_radians()).rem_euclid(2.0 * PI);
+        let sector = (relative_angle / self.sector_angle()).floor();
+        let edge_normal_angle = self.rotation.in_radians() + (sector + 0.5) * self.sector_angle();
+        let apothem = (self.sector_angle() / 2.0).cos();
+
+        point.x() * edge_normal_angle.cos() + point.y() * edge_normal_angle.sin() <= apothem + 0.000000001
+    }
+}
+
+impl Warp<Point<2>> for UniformPolygonSampler {
+    fn warp(&self, sample: &Point<2>) -> Point<2> {
+        // Pick a triangle between the center and one of the edges, then sample it uniformly
+        let scaled = sample.x() * self.sides as f64;
+        let index = (scaled as u32).min(self.sides - 1);
+        let u = scaled - index as f64;
+        let v = sample.y();
+        let first = self.vertex(index);
+        let second = self.vertex(index + 1);
+        let scale = u.sqrt();
+
+        pt!(
+            scale * ((1.0 - v) * first.x() + v * second.x()),
+            scale * ((1.0 - v) * first.y() + v * second.y())
+        )
+    }
+
+    fn pdf(&self, point: &Point<2>) -> f64 {
+        if self.contains(point) {
+            1.0 / self.area()
+        } else {
+            0.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    #[cfg(test)]
+    use super::*;
+
+    use crate::samplers::chisquare::{self, ChiSquareTest};
+
+    #[rstest]
+    fn square_area() {
+        let sampler = UniformPolygonSampler::new(4, Angle::degrees(45.0));
+
+        assert!((sampler.area() - 2.0).abs() < 0.000001);
+    }
+
+    #[rstest]
+    #[case(pt!(0, 0), true)]
+    #[case(pt!(0.7, 0.7), true)]
+    #[case(pt!(0.75, 0), false)]
+    #[case(pt!(0.7, -0.7), true)]
+    #[case(pt!(-0.71, 0.71), false)]
+    fn square_contains(#[case] point: Point<2>, #[case] expected: bool) {
+        let sampler = UniformPolygonSampler::new(4, Angle::degrees(45.0));
+
+        assert_eq!(expected, sampler.contains(&point));
+    }
+
+    #[rstest]
+    fn samples_inside_polygon(#[values(3, 5, 6, 8)] sides: u32) {
+        let sampler = UniformPolygonSampler::new(sides, Angle::degrees(10.0));
+
+        for x in [0.0, 0.1, 0.5, 0.77, 0.999] {
+            for y in [0.0, 0.3, 0.999] {
+                assert!(sampler.contains(&sampler.warp(&pt!(x, y))));
+            }
+        }
+    }
+
+    #[rstest]
+    fn distribution_matches_pdf(#[values(3, 5, 6)] sides: u32) {
+        let sampler = UniformPolygonSampler::new(sides, Angle::degrees(0.0));
+        let test = ChiSquareTest::new(10, 30);
+        let outcome = test.run(
+            0,
+            |sample| {
+                let point = sampler.warp(sample);
+                chisquare::polar_coordinates(point.x(), point.y())
+            },
+            |u, v| {
+                let (x, y) = chisquare::cartesian_coordinates(u, v);
+                sampler.pdf(&pt!(x, y)) * PI
+            },
+        );
+
+        assert!(outcome.passes(), "{outcome:?}");
+    }
+}
@@ -0,0 +1,104 @@
+use super::sampler::{rectangle_seed, Sampler2D};
+use crate::{math::{Point, Rectangle}, pt, util::{Random, Refine}};
+
+pub struct RandomSampler2D {
+    seed: u64,
+}
+
+struct SampleRefiner {
+    rectangle: Rectangle<2>,
+    random: Random,
+    current: Point<2>,
+}
+
+impl RandomSampler2D {
+    pub fn new(seed: u64) -> Self {
+        RandomSampler2D { seed }
+    }
+}
+
+impl Sampler2D for RandomSampler2D {
+    fn sample(&self, rectangle: Rectangle<2>) -> Box<dyn Refine<Point<2>>> {
+        let random = Random::new(rectangle_seed(self.seed, &rectangle));
+        let mut result = SampleRefiner {
+            rectangle,
+            random,
+            current: pt!(0, 0),
+        };
+        result.refine();
+
+        Box::new(result)
+    }
+}
+
+impl Refine<Point<2>> for SampleRefiner {
+    fn current(&self) -> Point<2> {
+        self.rectangle.from_relative(&self.current)
+    }
+
+    fn refine(&mut self) {
+        self.current = pt!(self.random.next_f64(), self.random.next_f64());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    #[cfg(test)]
+    use super::*;
+
+    use crate::vc;
+
+    fn collect(sampler: &dyn Sampler2D, rectangle: Rectangle<2>, count: usize) -> Vec<Point<2>> {
+        let mut refiner = sampler.sample(rectangle);
+        let mut result = Vec::new();
+
+        for _ in 0..count {
+            result.push(refiner.current());
+            refiner.refine();
+        }
+
+        result
+    }
+
+    #[rstest]
+    fn samples_inside_rectangle() {
+        let sampler = RandomSampler2D::new(0);
+        let rectangle = Rectangle::new(pt!(2, 3), vc!(2, 0), vc!(0, 4));
+
+        for p in collect(&sampler, rectangle, 1000) {
+            assert!(2.0 <= p.x() && p.x() < 4.0);
+            assert!(3.0 <= p.y() && p.y() < 7.0);
+        }
+    }
+
+    #[rstest]
+    fn current_is_stable() {
+        let sampler = RandomSampler2D::new(0);
+        let refiner = sampler.sample(Rectangle::new(pt!(0, 0), vc!(1, 0), vc!(0, 1)));
+
+        assert_eq!(refiner.current(), refiner.current());
+    }
+
+    #[rstest]
+    fn reproducible() {
+        let rectangle = || Rectangle::new(pt!(0, 0), vc!(1, 0), vc!(0, 1));
+        let xs = collect(&RandomSampler2D::new(5), rectangle(), 10);
+        let ys = collect(&RandomSampler2D::new(5), rectangle(), 10);
+
+        assert_eq!(xs, ys);
+    }
+
+    #[rstest]
+    fn decorrelated_between_pixels() {
+        let sampler = RandomSampler2D::new(5);
+        let xs = collect(&sampler, Rectangle::new(pt!(0, 0), vc!(1, 0), vc!(0, 1)), 10);
+        let ys: Vec<Point<2>> = collect(&sampler, Rectangle::new(pt!(1, 0), vc!(1, 0), vc!(0, 1)), 10)
+            .into_iter()
+            .map(|p| pt!(p.x() - 1.0, p.y()))
+            .collect();
+
+        assert_ne!(xs, ys);
+    }
+}
@@ -1,5 +1,40 @@
-use crate::{math::{Point, Rectangle}, util::Refine};
+use crate::{math::{Point, Rectangle}, util::{hash, Refine}};
 
 pub trait Sampler2D {
     fn sample(&self, rectangle: Rectangle<2>) -> Box<dyn Refine<Point<2>>>;
 }
+
+// Derives a seed unique to the given rectangle so that neighbouring pixels get decorrelated sample patterns
+pub fn rectangle_seed(seed: u64, rectangle: &Rectangle<2>) -> u64 {
+    let origin = rectangle.origin;
+
+    hash(&[seed, origin.x().to_bits(), origin.y().to_bits()])
+}
+
+pub struct IndexedRefiner<F: Fn(u32) -> Point<2>> {
+    rectangle: Rectangle<2>,
+    index: u32,
+    generate: F,
+}
+
+impl<F: Fn(u32) -> Point<2>> IndexedRefiner<F> {
+    pub fn new(rectangle: Rectangle<2>, generate: F) -> Self {
+        IndexedRefiner {
+            rectangle,
+            index: 0,
+            generate,
+        }
+    }
+}
+
+impl<F: Fn(u32) -> Point<2>> Refine<Point<2>> for IndexedRefiner<F> {
+    fn current(&self) -> Point<2> {
+        let relative = (self.generate)(self.index);
+
+        self.rectangle.from_relative(&relative)
+    }
+
+    fn refine(&mut self) {
+        self.index += 1;
+    }
+}
@@ -0,0 +1,137 @@
+use super::sampler::{rectangle_seed, IndexedRefiner, Sampler2D};
+use crate::{math::{Point, Rectangle}, pt, util::{hash, Refine}};
+
+// Burley, "Practical Hash-based Owen Scrambling"
+pub struct SobolSampler2D {
+    seed: u64,
+}
+
+impl SobolSampler2D {
+    pub fn new(seed: u64) -> Self {
+        SobolSampler2D { seed }
+    }
+}
+
+impl Sampler2D for SobolSampler2D {
+    fn sample(&self, rectangle: Rectangle<2>) -> Box<dyn Refine<Point<2>>> {
+        let seed = rectangle_seed(self.seed, &rectangle);
+        let index_seed = hash(&[seed, 0]) as u32;
+        let x_seed = hash(&[seed, 1]) as u32;
+        let y_seed = hash(&[seed, 2]) as u32;
+
+        Box::new(IndexedRefiner::new(rectangle, move |index| {
+            let index = nested_uniform_scramble(index, index_seed);
+            let x = nested_uniform_scramble(sobol(index, 0), x_seed);
+            let y = nested_uniform_scramble(sobol(index, 1), y_seed);
+
+            pt!(to_unit_interval(x), to_unit_interval(y))
+        }))
+    }
+}
+
+fn direction_number(dimension: usize, bit: u32) -> u32 {
+    match dimension {
+        0 => 1 << (31 - bit),
+        1 => {
+            let mut v = 1u32 << 31;
+
+            for _ in 0..bit {
+                v ^= v >> 1;
+            }
+
+            v
+        }
+        _ => panic!("Only two dimensions are supported"),
+    }
+}
+
+pub fn sobol(index: u32, dimension: usize) -> u32 {
+    let mut result = 0;
+
+    for bit in 0..32 {
+        if (index >> bit) & 1 == 1 {
+            result ^= direction_number(dimension, bit);
+        }
+    }
+
+    result
+}
+
+fn laine_karras_permutation(x: u32, seed: u32) -> u32 {
+    let mut x = x.wrapping_add(seed);
+
+    x ^= x.wrapping_mul(0x6c50b47c);
+    x ^= x.wrapping_mul(0xb82f1e52);
+    x ^= x.wrapping_mul(0xc7afe638);
+    x ^= x.wrapping_mul(0x8d22f6e6);
+
+    x
+}
+
+pub fn nested_uniform_scramble(x: u32, seed: u32) -> u32 {
+    laine_karras_permutation(x.reverse_bits(), seed).reverse_bits()
+}
+
+fn to_unit_interval(x: u32) -> f64 {
+    x as f64 / 4294967296.0
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    #[cfg(test)]
+    use super::*;
+
+    use crate::vc;
+
+    #[rstest]
+    #[case(0, 0.0, 0.0)]
+    #[case(1, 0.5, 0.5)]
+    #[case(2, 0.25, 0.75)]
+    #[case(3, 0.75, 0.25)]
+    #[case(4, 0.125, 0.625)]
+    #[case(5, 0.625, 0.125)]
+    #[case(6, 0.375, 0.375)]
+    #[case(7, 0.875, 0.875)]
+    fn unscrambled(#[case] index: u32, #[case] expected_x: f64, #[case] expected_y: f64) {
+        assert_eq!(expected_x, to_unit_interval(sobol(index, 0)));
+        assert_eq!(expected_y, to_unit_interval(sobol(index, 1)));
+    }
+
+    #[rstest]
+    fn scrambling_is_bijective_on_high_bits(#[values(0, 1, 0xdeadbeef)] seed: u32) {
+        let mut seen = [false; 256];
+
+        for i in 0..256u32 {
+            let scrambled = nested_uniform_scramble(i << 24, seed) >> 24;
+            assert!(!seen[scrambled as usize]);
+            seen[scrambled as usize] = true;
+        }
+    }
+
+    #[rstest]
+    fn scrambled_points_form_net(#[values(0, 1, 2, 3)] seed: u64) {
+        let sampler = SobolSampler2D::new(seed);
+        let mut refiner = sampler.sample(Rectangle::new(pt!(0, 0), vc!(1, 0), vc!(0, 1)));
+        let mut points = Vec::new();
+
+        for _ in 0..16 {
+            points.push(refiner.current());
+            refiner.refine();
+        }
+
+        // Every elementary interval of area 1/16 contains exactly one point
+        for log_columns in 0..=4 {
+            let columns = 1 << log_columns;
+            let rows = 16 / columns;
+            let mut seen = [false; 16];
+
+            for p in points.iter() {
+                let cell = (p.y() * rows as f64) as usize * columns + (p.x() * columns as f64) as usize;
+                assert!(!seen[cell]);
+                seen[cell] = true;
+            }
+        }
+    }
+}
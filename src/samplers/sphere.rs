@@ -0,0 +1,62 @@
+use std::f64::consts::PI;
+
+use crate::math::{Point, Vector};
+
+use super::Warp;
+
+// pdf = 1 / 4pi with respect to solid angle
+pub struct UniformSphereSampler { }
+
+impl UniformSphereSampler {
+    pub fn new() -> Self {
+        UniformSphereSampler { }
+    }
+}
+
+impl Warp<Vector<3>> for UniformSphereSampler {
+    fn warp(&self, sample: &Point<2>) -> Vector<3> {
+        let cos_theta = 1.0 - 2.0 * sample.x();
+
+        from_spherical(cos_theta, 2.0 * PI * sample.y())
+    }
+
+    fn pdf(&self, _direction: &Vector<3>) -> f64 {
+        1.0 / (4.0 * PI)
+    }
+}
+
+pub(super) fn from_spherical(cos_theta: f64, phi: f64) -> Vector<3> {
+    let sin_theta = (1.0 - cos_theta * cos_theta).max(0.0).sqrt();
+
+    Vector::new([sin_theta * phi.cos(), sin_theta * phi.sin(), cos_theta])
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    #[cfg(test)]
+    use super::*;
+
+    use crate::{pt, samplers::chisquare::{self, ChiSquareTest}};
+
+    #[rstest]
+    fn unit_vectors(#[values(0.0, 0.3, 0.999)] x: f64, #[values(0.0, 0.5, 0.8)] y: f64) {
+        let sampler = UniformSphereSampler::new();
+
+        assert!(sampler.warp(&pt!(x, y)).is_unit());
+    }
+
+    #[rstest]
+    fn distribution_matches_pdf(#[values(0, 1)] seed: u64) {
+        let sampler = UniformSphereSampler::new();
+        let test = ChiSquareTest::new(10, 20);
+        let outcome = test.run(
+            seed,
+            |sample| chisquare::spherical_coordinates(&sampler.warp(sample)),
+            |u, v| sampler.pdf(&chisquare::direction(u, v)) * 4.0 * PI,
+        );
+
+        assert!(outcome.passes(), "{outcome:?}");
+    }
+}
@@ -0,0 +1,10 @@
+use crate::math::Point;
+
+// Maps uniformly distributed points in [0, 1)^2 onto some domain.
+// pdf returns the density of the resulting distribution with respect to the natural measure of that domain
+// (solid angle for directions, area for points on a disk).
+pub trait Warp<T> {
+    fn warp(&self, sample: &Point<2>) -> T;
+
+    fn pdf(&self, value: &T) -> f64;
+}
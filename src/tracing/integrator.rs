@@ -0,0 +1,7 @@
+use crate::{math::Ray, util::Random};
+
+use super::{raytracer::TraceResult, scene::Scene};
+
+pub trait Integrator {
+    fn trace(&self, scene: &Scene, ray: &Ray, random: &mut Random) -> TraceResult;
+}
@@ -1,2 +1,5 @@
+pub mod integrator;
+pub mod pathtracer;
 pub mod raytracer;
 pub mod scene;
+pub mod whitted;
@@ -0,0 +1,486 @@
+use std::f64::consts::PI;
+
+use crate::{
+    imaging::color::Color,
+    materials::MaterialProperties,
+    math::{Point, Ray, Vector},
+    pt,
+    samplers::{CosineHemisphereSampler, CosinePowerHemisphereSampler, Warp},
+    util::Random,
+};
+
+use super::{integrator::Integrator, raytracer::{Aovs, SurfaceAovs, TraceResult}, scene::Scene};
+
+const NUDGE: f64 = 0.00001;
+
+pub struct PathTracer {
+    max_depth: u32,
+    roulette_depth: u32,
+}
+
+struct Scattering<'a> {
+    material_properties: &'a MaterialProperties,
+    normal: Vector<3>,
+    outgoing: Vector<3>,
+    reflected: Vector<3>,
+    mirror_probability: f64,
+    glossy_probability: f64,
+    diffuse_lobe: CosineHemisphereSampler,
+    glossy_lobe: CosinePowerHemisphereSampler,
+}
+
+struct ScatteringSample {
+    direction: Vector<3>,
+    weight: Color,
+    pdf: f64,
+    is_specular: bool,
+}
+
+impl PathTracer {
+    pub fn new(max_depth: u32, roulette_depth: u32) -> Self {
+        PathTracer {
+            max_depth,
+            roulette_depth,
+        }
+    }
+
+    fn sample_light(&self, scene: &Scene, position: &Point<3>, scattering: &Scattering, random: &mut Random) -> Color {
+        let light_count = scene.light_sources.len();
+
+        if light_count == 0 {
+            return Color::black();
+        }
+
+        let light_source = &scene.light_sources[random.next_below(light_count as u32) as usize];
+        let selection_probability = 1.0 / light_count as f64;
+        let sample = pt!(random.next_f64(), random.next_f64());
+
+        match light_source.sample_towards(position, &sample) {
+            None => Color::black(),
+            Some(light_sample) => {
+                let cos_theta = scattering.normal.dot(&light_sample.direction);
+
+                if cos_theta <= 0.0 || light_sample.pdf <= 0.0 || is_occluded(scene, position, &light_sample.direction, light_sample.distance) {
+                    Color::black()
+                } else {
+                    let light_pdf = light_sample.pdf * selection_probability;
+                    let f = scattering.evaluate(&light_sample.direction);
+                    let mis_weight = if light_sample.is_delta {
+                        1.0
+                    } else {
+                        power_heuristic(light_pdf, scattering.pdf(&light_sample.direction))
+                    };
+
+                    f * light_sample.color * (cos_theta * mis_weight / light_pdf)
+                }
+            }
+        }
+    }
+}
+
+impl Integrator for PathTracer {
+    fn trace(&self, scene: &Scene, ray: &Ray, random: &mut Random) -> TraceResult {
+        // Light reaching the primary hit (or the camera) straight from a light source counts as direct
+        let mut direct = Color::black();
+        let mut indirect = Color::black();
+        let mut surface = None;
+        let mut throughput = Color::white();
+        let origin = ray.origin;
+        let mut ray = *ray;
+        let mut previous_pdf = 0.0;
+        let mut previous_specular = true;
+
+        for depth in 0..self.max_depth {
+            let hit = scene.root.find_first_positive_hit(&ray);
+            let light_hit = find_light_hit(scene, &ray, hit.as_ref().map(|hit| hit.t));
+
+            if let Some(light_hit) = light_hit {
+                let mis_weight = if previous_specular {
+                    1.0
+                } else {
+                    let light_pdf = light_hit.pdf / scene.light_sources.len() as f64;
+                    power_heuristic(previous_pdf, light_pdf)
+                };
+
+                let contribution = throughput * light_hit.color * mis_weight;
+                if depth <= 1 { direct += contribution } else { indirect += contribution }
+                break;
+            }
+
+            let hit = match hit {
+                None => break,
+                Some(hit) => hit,
+            };
+
+            if depth == 0 {
+                surface = Some(SurfaceAovs::from_hit(&hit, &origin));
+            }
+
+            let material_properties = match &hit.material_properties {
+                None => break,
+                Some(material_properties) => material_properties,
+            };
+
+            let position = hit.global_position();
+            let scattering = Scattering::new(material_properties, hit.normal(), -ray.direction);
+
+            if scattering.has_non_specular_component() {
+                let contribution = throughput * self.sample_light(scene, &position, &scattering, random);
+                if depth == 0 { direct += contribution } else { indirect += contribution }
+            }
+
+            let sample = match scattering.sample(random) {
+                None => break,
+                Some(sample) => sample,
+            };
+
+            throughput = throughput * sample.weight;
+            previous_pdf = sample.pdf;
+            previous_specular = sample.is_specular;
+
+            if depth + 1 >= self.roulette_depth {
+                let survival_probability = throughput.intensity().clamp(0.05, 1.0);
+
+                if random.next_f64() >= survival_probability {
+                    break;
+                }
+
+                throughput /= survival_probability;
+            }
+
+            ray = Ray::new(position, sample.direction).nudged(NUDGE);
+        }
+
+        TraceResult {
+            color: direct + indirect,
+            aovs: Aovs { surface, direct, indirect },
+        }
+    }
+}
+
+impl<'a> Scattering<'a> {
+    fn new(material_properties: &'a MaterialProperties, normal: Vector<3>, outgoing: Vector<3>) -> Self {
+        let outgoing = outgoing.normalized();
+        let normal = {
+            let normal = normal.normalized();
+
+            if normal.dot(&outgoing) < 0.0 { -normal } else { normal }
+        };
+        let reflected = (-outgoing).reflect(&normal);
+        let (mirror_probability, glossy_probability) = {
+            let mirror = material_properties.reflection.intensity();
+            let diffuse = material_properties.diffuse.intensity();
+            let glossy = material_properties.specular_color.intensity();
+            let total = mirror + diffuse + glossy;
+
+            if total > 0.0 { (mirror / total, glossy / total) } else { (0.0, 0.0) }
+        };
+
+        Scattering {
+            material_properties,
+            normal,
+            outgoing,
+            reflected,
+            mirror_probability,
+            glossy_probability,
+            diffuse_lobe: CosineHemisphereSampler::new(),
+            glossy_lobe: CosinePowerHemisphereSampler::new(material_properties.specular_exponent),
+        }
+    }
+
+    fn has_non_specular_component(&self) -> bool {
+        self.mirror_probability < 1.0
+    }
+
+    fn evaluate(&self, incoming: &Vector<3>) -> Color {
+        let properties = self.material_properties;
+        let brdf_factor = match &properties.brdf {
+            None => 1.0,
+            Some(brdf) => brdf.compute(&self.outgoing, incoming),
+        };
+        let diffuse = properties.diffuse * (brdf_factor / PI);
+        let specular = {
+            let exponent = properties.specular_exponent;
+            let cos_alpha = self.reflected.dot(incoming).max(0.0);
+
+            properties.specular_color * ((exponent + 2.0) / (2.0 * PI) * cos_alpha.powf(exponent))
+        };
+
+        diffuse + specular
+    }
+
+    fn diffuse_probability(&self) -> f64 {
+        1.0 - self.mirror_probability - self.glossy_probability
+    }
+
+    // Mixture of the cosine-weighted diffuse lobe and the Phong lobe around the mirror direction
+    fn pdf(&self, incoming: &Vector<3>) -> f64 {
+        if self.normal.dot(incoming) <= 0.0 {
+            return 0.0;
+        }
+
+        let diffuse_pdf = self.diffuse_lobe.pdf(&to_local(incoming, &self.normal));
+        let glossy_pdf = if self.glossy_probability > 0.0 {
+            self.glossy_lobe.pdf(&to_local(incoming, &self.reflected))
+        } else {
+            0.0
+        };
+
+        self.diffuse_probability() * diffuse_pdf + self.glossy_probability * glossy_pdf
+    }
+
+    fn sample(&self, random: &mut Random) -> Option<ScatteringSample> {
+        let selector = random.next_f64();
+        let sample = pt!(random.next_f64(), random.next_f64());
+
+        if selector < self.mirror_probability {
+            Some(ScatteringSample {
+                direction: self.reflected,
+                weight: self.material_properties.reflection / self.mirror_probability,
+                pdf: 0.0,
+                is_specular: true,
+            })
+        } else {
+            let direction = if selector < self.mirror_probability + self.glossy_probability {
+                from_local(&self.glossy_lobe.warp(&sample), &self.reflected)
+            } else {
+                from_local(&self.diffuse_lobe.warp(&sample), &self.normal)
+            };
+            let pdf = self.pdf(&direction);
+
+            if pdf <= 0.0 {
+                None
+            } else {
+                let cos_theta = self.normal.dot(&direction);
+
+                Some(ScatteringSample {
+                    direction,
+                    weight: self.evaluate(&direction) * (cos_theta / pdf),
+                    pdf,
+                    is_specular: false,
+                })
+            }
+        }
+    }
+}
+
+// Expresses a direction given relative to the frame around axis (as z-axis) in global coordinates
+fn from_local(direction: &Vector<3>, axis: &Vector<3>) -> Vector<3> {
+    let (u, v) = axis.orthonormal_basis();
+
+    u * direction.x() + v * direction.y() + *axis * direction.z()
+}
+
+fn to_local(direction: &Vector<3>, axis: &Vector<3>) -> Vector<3> {
+    let (u, v) = axis.orthonormal_basis();
+
+    Vector::new([direction.dot(&u), direction.dot(&v), direction.dot(axis)])
+}
+
+fn power_heuristic(pdf: f64, other_pdf: f64) -> f64 {
+    let a = pdf * pdf;
+    let b = other_pdf * other_pdf;
+
+    if a + b > 0.0 { a / (a + b) } else { 0.0 }
+}
+
+fn is_occluded(scene: &Scene, position: &Point<3>, direction: &Vector<3>, distance: f64) -> bool {
+    let ray = Ray::new(*position, *direction).nudged(NUDGE);
+
+    match scene.root.find_first_positive_hit(&ray) {
+        None => false,
+        Some(hit) => hit.t < distance * 0.999,
+    }
+}
+
+struct NearestLightHit {
+    color: Color,
+    pdf: f64,
+}
+
+fn find_light_hit(scene: &Scene, ray: &Ray, max_t: Option<f64>) -> Option<NearestLightHit> {
+    let normalized_ray = Ray::new(ray.origin, ray.direction.normalized());
+    let max_distance = max_t.map(|t| t * ray.direction.norm()).unwrap_or(f64::INFINITY);
+    let mut result: Option<(f64, NearestLightHit)> = None;
+
+    for light_source in scene.light_sources.iter() {
+        if let Some(light_hit) = light_source.find_hit(&normalized_ray) {
+            let is_nearest = light_hit.t < max_distance && match &result {
+                None => true,
+                Some((t, _)) => light_hit.t < *t,
+            };
+
+            if is_nearest {
+                result = Some((light_hit.t, NearestLightHit { color: light_hit.color, pdf: light_hit.pdf }));
+            }
+        }
+    }
+
+    result.map(|(_, light_hit)| light_hit)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use rstest::rstest;
+
+    #[cfg(test)]
+    use super::*;
+
+    use crate::{
+        cameras::{camera::Camera, perspective::{ApertureShape, PerspectiveCamera, PerspectiveCameraParameters}},
+        lights::{point::PointLight, sphere::SphereLight},
+        materials::UniformMaterial,
+        math::vc,
+        primitives::{Decorator, PlaneXY, Primitive, Union},
+    };
+
+    fn create_camera() -> Box<dyn Camera> {
+        Box::new(PerspectiveCamera::new(&PerspectiveCameraParameters {
+            aspect_ratio: 1.0,
+            distance_to_screen: 1.0,
+            eye: pt!(0, 0, 5),
+            look_at: pt!(0, 0, 0),
+            up: vc!(0, 1, 0),
+            aperture_radius: 0.0,
+            focal_distance: 1.0,
+            aperture_shape: ApertureShape::Circular,
+            shift: vc!(0, 0),
+            distortion: None,
+        }))
+    }
+
+    fn create_white_plane() -> Rc<dyn Primitive> {
+        let material = Rc::new(UniformMaterial::new(MaterialProperties {
+            diffuse: Color::white(),
+            specular_color: Color::black(),
+            specular_exponent: 1.0,
+            brdf: None,
+            reflection: Color::black(),
+        }));
+
+        Rc::new(Decorator::new(material, Rc::new(PlaneXY::new())))
+    }
+
+    #[rstest]
+    fn empty_scene_is_black() {
+        let scene = Scene {
+            camera: create_camera(),
+            root: Rc::new(Union::new(vec![])),
+            light_sources: vec![],
+        };
+        let path_tracer = PathTracer::new(8, 3);
+        let ray = Ray::new(pt!(0, 0, 5), vc!(0, 0, -1));
+        let result = path_tracer.trace(&scene, &ray, &mut Random::new(0));
+
+        assert_eq!(Color::black(), result.color);
+        assert!(!result.hit());
+    }
+
+    #[rstest]
+    fn directly_visible_area_light(#[values(0, 1, 2)] seed: u64) {
+        let scene = Scene {
+            camera: create_camera(),
+            root: Rc::new(Union::new(vec![])),
+            light_sources: vec![Rc::new(SphereLight::new(Color::new(1.0, 2.0, 3.0), pt!(0, 0, 0), 1.0))],
+        };
+        let path_tracer = PathTracer::new(8, 3);
+        let ray = Ray::new(pt!(0, 0, 5), vc!(0, 0, -1));
+        let result = path_tracer.trace(&scene, &ray, &mut Random::new(seed));
+
+        assert_eq!(Color::new(1.0, 2.0, 3.0), result.color);
+        assert!(!result.hit());
+    }
+
+    #[rstest]
+    fn diffuse_plane_under_point_light(#[values(0, 1, 2)] seed: u64) {
+        let scene = Scene {
+            camera: create_camera(),
+            root: create_white_plane(),
+            light_sources: vec![Rc::new(PointLight::new(Color::white(), pt!(0, 0, 2)))],
+        };
+        let path_tracer = PathTracer::new(8, 3);
+        let ray = Ray::new(pt!(0, 0, 5), vc!(0, 0, -1));
+        let result = path_tracer.trace(&scene, &ray, &mut Random::new(seed));
+        let expected = 1.0 / PI / 4.0;
+
+        assert!((expected - result.color.r()).abs() < 0.0001);
+        assert!((expected - result.color.g()).abs() < 0.0001);
+        assert!((expected - result.color.b()).abs() < 0.0001);
+        assert!(result.hit());
+    }
+
+    #[rstest]
+    fn diffuse_plane_under_area_light_converges() {
+        let radius = 0.5;
+        let height = 2.0;
+        let scene = Scene {
+            camera: create_camera(),
+            root: create_white_plane(),
+            light_sources: vec![Rc::new(SphereLight::new(Color::white(), pt!(0, 0, height), radius))],
+        };
+        let path_tracer = PathTracer::new(8, 3);
+        let ray = Ray::new(pt!(4, 0, 1), vc!(-4, 0, -1));
+        let mut random = Random::new(7);
+        let sample_count = 20000;
+        let mut total = 0.0;
+
+        for _ in 0..sample_count {
+            total += path_tracer.trace(&scene, &ray, &mut random).color.r();
+        }
+
+        // Irradiance from a sphere directly overhead is pi * sin^2(theta_max)
+        let expected = (radius / height).powi(2);
+        let actual = total / sample_count as f64;
+
+        assert!((expected - actual).abs() < 0.002, "Expected {expected}, got {actual}");
+    }
+
+    #[rstest]
+    fn scattering_weights_are_unbiased(#[values(1.0, 10.0, 50.0)] specular_exponent: f64) {
+        let material_properties = MaterialProperties {
+            diffuse: Color::new(0.5, 0.5, 0.5),
+            specular_color: Color::new(0.3, 0.3, 0.3),
+            specular_exponent,
+            brdf: None,
+            reflection: Color::black(),
+        };
+        let outgoing = vc!(0.5, 0, 0.75f64.sqrt());
+        let scattering = Scattering::new(&material_properties, vc!(0, 0, 1), outgoing);
+
+        let estimate = {
+            let mut random = Random::new(3);
+            let sample_count = 200000;
+            let mut total = 0.0;
+
+            for _ in 0..sample_count {
+                if let Some(sample) = scattering.sample(&mut random) {
+                    total += sample.weight.r();
+                }
+            }
+
+            total / sample_count as f64
+        };
+
+        let expected = {
+            let resolution = 1000;
+            let mut total = 0.0;
+
+            for i in 0..resolution {
+                for j in 0..resolution {
+                    let cos_theta = (i as f64 + 0.5) / resolution as f64;
+                    let sin_theta = (1.0 - cos_theta * cos_theta).sqrt();
+                    let phi = 2.0 * PI * (j as f64 + 0.5) / resolution as f64;
+                    let incoming = vc!(sin_theta * phi.cos(), sin_theta * phi.sin(), cos_theta);
+
+                    total += scattering.evaluate(&incoming).r() * cos_theta;
+                }
+            }
+
+            total * 2.0 * PI / (resolution * resolution) as f64
+        };
+
+        assert!((expected - estimate).abs() < 0.005, "Expected {expected}, got {estimate}");
+    }
+}
@@ -1,12 +1,12 @@
 use std::rc::Rc;
 
 use crate::{
-    cameras::perspective::PerspectiveCamera, lights::light::LightSource,
+    cameras::camera::Camera, lights::light::LightSource,
     primitives::Primitive,
 };
 
 pub struct Scene {
-    pub camera: PerspectiveCamera,
+    pub camera: Box<dyn Camera>,
     pub root: Rc<dyn Primitive>,
     pub light_sources: Vec<Rc<dyn LightSource>>,
 }
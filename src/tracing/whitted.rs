@@ -0,0 +1,182 @@
+use std::f64::consts::PI;
+
+use crate::{
+    imaging::color::Color,
+    lights::light::{LightRay, LightSource},
+    math::Ray,
+    primitives::Hit, samplers::HemisphereSampler, util::{Random, Refine}, materials::MaterialProperties,
+};
+
+use super::{integrator::Integrator, raytracer::{Aovs, SurfaceAovs, TraceResult}, scene::Scene};
+
+pub struct WhittedIntegrator { }
+
+impl WhittedIntegrator {
+    pub fn new() -> Self {
+        WhittedIntegrator { }
+    }
+
+    fn weighted_trace(&self, scene: &Scene, ray: &Ray, weight: f64) -> TraceResult {
+        if weight < 0.01 {
+            TraceResult::miss()
+        } else {
+            match scene.root.find_first_positive_hit(ray) {
+                None => TraceResult::miss(),
+                Some(hit) => {
+                    debug_assert!(hit.t > 0.0, "find_first_positive_hit returned hit with negative t-value: {}", hit.t);
+
+                    let surface = SurfaceAovs::from_hit(&hit, &ray.origin);
+                    let (direct, indirect) = self.determine_lighting(scene, hit, weight);
+
+                    TraceResult {
+                        color: direct + indirect,
+                        aovs: Aovs {
+                            surface: Some(surface),
+                            direct,
+                            indirect,
+                        },
+                    }
+                }
+            }
+        }
+    }
+
+    // Direct illumination from the light sources and everything arriving by way of other surfaces
+    fn determine_lighting(&self, scene: &Scene, hit: Hit, weight: f64) -> (Color, Color) {
+        match &hit.material_properties {
+            None => (Color::black(), Color::black()),
+            Some(material_properties) => {
+                let direct = self.direct_illumination(scene, &hit, material_properties);
+                let indirect = self.reflection(scene, &hit, material_properties, weight) + self.indirect_illumination(scene, &hit, material_properties, weight);
+
+                (direct, indirect)
+            }
+        }
+    }
+
+    fn reflection(&self, scene: &Scene, hit: &Hit, material_properties: &MaterialProperties, weight: f64) -> Color {
+        let reflection = &material_properties.reflection;
+
+        if reflection.is_not_black() {
+            let reflected_ray = {
+                let reflected_direction = hit.ray.direction.reflect(&hit.normal());
+                Ray::new(hit.global_position(), reflected_direction)
+            }.nudged(0.00001);
+            self.weighted_trace(scene, &reflected_ray, weight * reflection.intensity()).color * material_properties.reflection
+        } else {
+            Color::black()
+        }
+    }
+
+    fn direct_illumination(&self, scene: &Scene, hit: &Hit, material_properties: &MaterialProperties) -> Color {
+        self.process_lights(scene, hit, material_properties)
+    }
+
+    fn indirect_illumination(&self, scene: &Scene, hit: &Hit, material_properties: &MaterialProperties, weight: f64) -> Color {
+        match &material_properties.brdf {
+            None => {
+                Color::black()
+            }
+            Some(brdf) => {
+                let mut sampler = HemisphereSampler::new();
+                let mut accumulated_color = Color::black();
+                let sample_count = 100;
+                let origin = hit.global_position();
+                let outgoing = (&hit.transformation.inverse_matrix * &-hit.ray.direction).normalized();
+
+                for _ in 0..sample_count {
+                    let direction = sampler.current();
+                    let pdf = sampler.pdf(&direction);
+
+                    if pdf > 0.0 {
+                        let transformed_direction = &hit.transformation.matrix * &direction;
+                        let ray = Ray::new(origin, transformed_direction).nudged(0.00001);
+                        let brdf_factor = brdf.compute(&outgoing, &direction);
+                        let reflectance = material_properties.diffuse * (brdf_factor * direction.z() / (PI * pdf));
+
+                        accumulated_color += self.weighted_trace(scene, &ray, weight * 0.09).color * reflectance;
+                    }
+
+                    sampler.refine();
+                }
+
+                accumulated_color / sample_count as f64
+            }
+        }
+    }
+
+    fn process_lights(&self, scene: &Scene, hit: &Hit, material_properties: &MaterialProperties) -> Color {
+        let mut result = Color::black();
+
+        for light_source in scene.light_sources.iter() {
+            result += self.process_light(scene, hit, light_source.as_ref(), material_properties);
+        }
+
+        result
+    }
+
+    fn process_light(
+        &self,
+        scene: &Scene,
+        hit: &Hit,
+        light_source: &dyn LightSource,
+        material_properties: &MaterialProperties,
+    ) -> Color {
+        let mut result = Color::black();
+        let mut n_lightrays = 0;
+
+        for light_ray in light_source.lightrays_to(hit.global_position()) {
+            result += self.process_light_ray(scene, hit, &light_ray, material_properties);
+
+            n_lightrays += 1;
+        }
+
+        result /= n_lightrays as f64;
+        result
+    }
+
+    fn process_light_ray(
+        &self,
+        scene: &Scene,
+        hit: &Hit,
+        light_ray: &LightRay,
+        material_properties: &MaterialProperties,
+    ) -> Color {
+        let is_shadowed = match scene.root.find_first_positive_hit(&light_ray.ray) {
+            None => false,
+            Some(ref hit) => hit.t < 0.999,
+        };
+
+        if is_shadowed {
+            Color::black()
+        } else {
+            self.compute_diffuse_lighting(hit, material_properties, light_ray) + self.compute_specular_lighting(hit, material_properties, light_ray)
+        }
+    }
+
+    fn compute_diffuse_lighting(&self, hit: &Hit, material_properties: &MaterialProperties, light_ray: &LightRay) -> Color {
+        let cos_angle = -hit.normal().cos_angle_between(&light_ray.ray.direction);
+
+        if cos_angle > 0.0 {
+            light_ray.color * cos_angle * material_properties.diffuse
+        } else {
+            Color::black()
+        }
+    }
+
+    fn compute_specular_lighting(&self, hit: &Hit, material_properties: &MaterialProperties, light_ray: &LightRay) -> Color {
+        let reflected_light_direction = light_ray.ray.direction.reflect(&hit.normal()).normalized();
+        let eye_direction = {
+            let eye = &hit.ray.origin;
+            (eye - &hit.global_position()).normalized()
+        };
+
+        light_ray.color * material_properties.specular_color * reflected_light_direction.dot(&eye_direction).powf(material_properties.specular_exponent)
+    }
+}
+
+impl Integrator for WhittedIntegrator {
+    fn trace(&self, scene: &Scene, ray: &Ray, _random: &mut Random) -> TraceResult {
+        self.weighted_trace(scene, ray, 1.0)
+    }
+}
@@ -1,5 +1,7 @@
 pub mod algorithms;
 pub mod tag;
+mod random;
 mod refining;
 
+pub use random::{hash, Random};
 pub use refining::{Refine, Constant};
@@ -0,0 +1,145 @@
+const MULTIPLIER: u64 = 6364136223846793005;
+const DEFAULT_STREAM: u64 = 1442695040888963407;
+
+// PCG32 (XSH-RR variant)
+#[derive(Debug, Clone)]
+pub struct Random {
+    state: u64,
+    increment: u64,
+}
+
+impl Random {
+    pub fn new(seed: u64) -> Self {
+        Self::with_stream(seed, DEFAULT_STREAM)
+    }
+
+    pub fn with_stream(seed: u64, stream: u64) -> Self {
+        let mut result = Random {
+            state: 0,
+            increment: (stream << 1) | 1,
+        };
+
+        result.next_u32();
+        result.state = result.state.wrapping_add(seed);
+        result.next_u32();
+
+        result
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        let old_state = self.state;
+        self.state = old_state.wrapping_mul(MULTIPLIER).wrapping_add(self.increment);
+
+        let xorshifted = (((old_state >> 18) ^ old_state) >> 27) as u32;
+        let rotation = (old_state >> 59) as u32;
+
+        xorshifted.rotate_right(rotation)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        let high = self.next_u32() as u64;
+        let low = self.next_u32() as u64;
+
+        (high << 32) | low
+    }
+
+    // Uniformly distributed in [0, 1)
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+    }
+
+    // Uniformly distributed in [0, bound)
+    pub fn next_below(&mut self, bound: u32) -> u32 {
+        debug_assert!(bound > 0);
+
+        let threshold = bound.wrapping_neg() % bound;
+
+        loop {
+            let r = self.next_u32();
+
+            if r >= threshold {
+                return r % bound;
+            }
+        }
+    }
+}
+
+pub fn hash(values: &[u64]) -> u64 {
+    let mut result = 0x9E3779B97F4A7C15u64;
+
+    for value in values {
+        result = mix(result ^ mix(*value));
+    }
+
+    result
+}
+
+// SplitMix64 finalizer
+fn mix(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9E3779B97F4A7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    #[cfg(test)]
+    use super::*;
+
+    #[rstest]
+    fn same_seed_same_sequence(#[values(0, 1, 42, 123456789)] seed: u64) {
+        let mut random1 = Random::new(seed);
+        let mut random2 = Random::new(seed);
+
+        for _ in 0..100 {
+            assert_eq!(random1.next_u32(), random2.next_u32());
+        }
+    }
+
+    #[rstest]
+    fn different_seeds_different_sequences() {
+        let mut random1 = Random::new(1);
+        let mut random2 = Random::new(2);
+        let xs: Vec<u32> = (0..10).map(|_| random1.next_u32()).collect();
+        let ys: Vec<u32> = (0..10).map(|_| random2.next_u32()).collect();
+
+        assert_ne!(xs, ys);
+    }
+
+    #[rstest]
+    fn next_f64_in_unit_interval(#[values(0, 7, 99)] seed: u64) {
+        let mut random = Random::new(seed);
+
+        for _ in 0..10000 {
+            let x = random.next_f64();
+            assert!((0.0..1.0).contains(&x));
+        }
+    }
+
+    #[rstest]
+    fn next_f64_mean() {
+        let mut random = Random::new(5);
+        let n = 100000;
+        let mean = (0..n).map(|_| random.next_f64()).sum::<f64>() / n as f64;
+
+        assert!((mean - 0.5).abs() < 0.01);
+    }
+
+    #[rstest]
+    fn next_below(#[values(1, 2, 3, 10, 1000)] bound: u32) {
+        let mut random = Random::new(3);
+
+        for _ in 0..1000 {
+            assert!(random.next_below(bound) < bound);
+        }
+    }
+
+    #[rstest]
+    fn hash_depends_on_order() {
+        assert_ne!(hash(&[1, 2]), hash(&[2, 1]));
+        assert_eq!(hash(&[1, 2]), hash(&[1, 2]));
+    }
+}